@@ -1,6 +1,5 @@
-use app::DirType;
 use common::{
-    clap::{self, Args, Parser, Subcommand},
+    clap::{self, Parser, Subcommand},
     eyre::{bail, Result},
     tracing,
 };
@@ -10,7 +9,7 @@ use version::STENCILA_VERSION;
 use crate::{
     compile, convert, execute,
     logging::{LoggingFormat, LoggingLevel},
-    new, preview, render, sync, uninstall, upgrade,
+    new, preview, render, sync, uninstall, upgrade, vars,
 };
 
 /// CLI subcommands and global options
@@ -78,6 +77,14 @@ pub struct Cli {
     /// Output a link to more easily report an issue
     #[arg(long, global = true)]
     pub error_link: bool,
+
+    /// The config profile to use
+    ///
+    /// Applies the overrides in the corresponding `[profile.<NAME>]` table
+    /// of `stencila.toml` files. Defaults to the `STENCILA_CONFIG_PROFILE`
+    /// environment variable.
+    #[arg(long, global = true, env = config::PROFILE_ENV)]
+    pub profile: Option<String>,
 }
 
 impl Cli {
@@ -104,6 +111,7 @@ pub enum Command {
     Compile(compile::Cli),
     Execute(execute::Cli),
     Render(render::Cli),
+    Vars(vars::Cli),
 
     Preview(preview::Cli),
     Publish(publish::cli::Cli),
@@ -122,16 +130,7 @@ pub enum Command {
     Upgrade(upgrade::Cli),
     Uninstall(uninstall::Cli),
 
-    Config(ConfigOptions),
-}
-
-#[derive(Debug, Args)]
-pub struct ConfigOptions {
-    #[arg(long, default_value = "config")]
-    dir: DirType,
-
-    #[arg(long)]
-    ensure: bool,
+    Config(config::cli::Cli),
 }
 
 impl Cli {
@@ -153,6 +152,7 @@ impl Cli {
             Command::Compile(compile) => compile.run().await?,
             Command::Execute(execute) => execute.run().await?,
             Command::Render(render) => render.run().await?,
+            Command::Vars(vars) => vars.run().await?,
 
             Command::Preview(preview) => preview.run().await?,
             Command::Publish(publish) => publish.run().await?,
@@ -169,11 +169,7 @@ impl Cli {
             Command::Upgrade(upgrade) => upgrade.run().await?,
             Command::Uninstall(uninstall) => uninstall.run()?,
 
-            Command::Config(options) => {
-                // TODO: Make options.dir an option, and if it not there, show all folders.
-                let dir = app::get_app_dir(options.dir, options.ensure)?;
-                println!("{}", dir.display());
-            }
+            Command::Config(config) => config.run().await?,
 
             // Handled before this function
             Command::Lsp => bail!("The LSP command should already been run"),
@@ -1,10 +1,14 @@
 use std::path::PathBuf;
 
-use cli_utils::{Code, ToStdout};
+use cli_utils::{
+    table::{self, Attribute, Cell},
+    Code, ToStdout,
+};
 use codecs::LossesResponse;
 use common::{
     clap::{self, Parser},
     eyre::Result,
+    tokio,
 };
 use document::{CommandWait, Document, SaveDocumentSidecar, SaveDocumentSource};
 use format::Format;
@@ -47,6 +51,21 @@ pub struct Cli {
     #[arg(long)]
     no_save: bool,
 
+    /// Show which nodes would be executed, and why, without executing them
+    #[arg(long)]
+    plan: bool,
+
+    /// Show a summary of the execution status of nodes after executing
+    #[arg(long)]
+    summary: bool,
+
+    /// Watch the document and re-execute it when the file changes
+    ///
+    /// Only the nodes whose code, or dependencies, have changed
+    /// will be re-executed on each change.
+    #[arg(long, short)]
+    watch: bool,
+
     /// Arguments to pass through to any CLI tool delegated to for encoding to the output format (e.g. Pandoc)
     #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
     passthrough_args: Vec<String>,
@@ -62,12 +81,53 @@ impl Cli {
             encode_options,
             strip_options,
             no_save,
+            plan,
+            summary,
+            watch,
             passthrough_args,
         } = self;
 
         let doc = Document::open(&input).await?;
         doc.compile(CommandWait::Yes).await?;
-        doc.execute(execute_options, CommandWait::Yes).await?;
+
+        if plan {
+            let plan = doc.plan(execute_options).await?;
+            println!("{plan}");
+
+            return Ok(());
+        }
+
+        doc.execute(execute_options.clone(), CommandWait::Yes).await?;
+
+        if summary {
+            let summary = doc.execution_summary().await;
+
+            let mut table = table::new();
+            table.set_header(["Type", "Id", "Status", "Duration", "Messages"]);
+            for node in &summary.nodes {
+                table.add_row([
+                    Cell::new(node.node_type).add_attribute(Attribute::Bold),
+                    Cell::new(&node.node_id),
+                    Cell::new(&node.status),
+                    Cell::new(
+                        node.duration
+                            .as_ref()
+                            .map(|duration| duration.humanize(true))
+                            .unwrap_or_default(),
+                    ),
+                    Cell::new(node.messages.len()),
+                ]);
+            }
+            println!("{table}");
+
+            println!(
+                "{succeeded} succeeded, {failed} failed, {skipped} skipped in {duration}ms",
+                succeeded = summary.succeeded,
+                failed = summary.failed,
+                skipped = summary.skipped,
+                duration = summary.duration
+            );
+        }
 
         if !no_save {
             doc.save_with(
@@ -98,6 +158,14 @@ impl Cli {
             }
         }
 
+        if watch {
+            doc.watch_execute(execute_options).await?;
+
+            // Sleep forever (or until Ctrl+C)
+            use tokio::time::{sleep, Duration};
+            sleep(Duration::from_secs(u64::MAX)).await;
+        }
+
         Ok(())
     }
 }
@@ -15,3 +15,4 @@ mod render;
 mod sync;
 mod uninstall;
 pub mod upgrade;
+mod vars;
@@ -13,6 +13,10 @@ use cli::{
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    if let Some(profile) = &cli.profile {
+        std::env::set_var(config::PROFILE_ENV, profile);
+    }
+
     let (log_level, log_format, error_details) = if cli.debug {
         (LoggingLevel::Debug, LoggingFormat::Pretty, "all")
     } else if cli.trace {
@@ -27,7 +27,55 @@ pub struct StripOptions {
 
 /// Command line arguments for decoding nodes from other formats
 #[derive(Debug, Args)]
-pub struct DecodeOptions {}
+pub struct DecodeOptions {
+    /// Decode only the coarse structure of the document
+    ///
+    /// Supported by some formats (e.g. Markdown) to decode only headings,
+    /// block boundaries, and metadata, skipping inline parsing and large
+    /// data. Much faster for large documents.
+    #[arg(long)]
+    coarse: bool,
+
+    /// The path of a file of macro definitions to read before decoding
+    ///
+    /// Supported by some formats (e.g. LaTeX) to expand custom commands
+    /// defined in a preamble or macros file.
+    #[arg(long)]
+    macros: Option<PathBuf>,
+
+    /// The delimiter between fields of tabular data (e.g. CSV)
+    #[arg(long)]
+    delimiter: Option<char>,
+
+    /// The quote character around fields of tabular data (e.g. CSV)
+    #[arg(long)]
+    quote: Option<char>,
+
+    /// Treat the first row of tabular data as data, not a header row
+    #[arg(long)]
+    no_header: bool,
+
+    /// The type of a column of tabular data
+    ///
+    /// Each use should have the form `name=type` (or `index=type` when
+    /// there is no header row) where type is one of `boolean`, `integer`,
+    /// `number`, or `string`.
+    #[arg(long = "column-type")]
+    column_types: Vec<String>,
+
+    /// The decimal separator used when parsing numbers from tabular data
+    ///
+    /// For example, use `,` for European style numbers such as `1.234,56`.
+    #[arg(long)]
+    decimal_separator: Option<char>,
+
+    /// The name of a sheet to decode from a workbook (e.g. XLSX, ODS)
+    ///
+    /// May be used multiple times to select several sheets. When not used,
+    /// all sheets are decoded.
+    #[arg(long = "sheet")]
+    sheets: Vec<String>,
+}
 
 impl DecodeOptions {
     /// Build a set of [`codecs::DecodeOptions`] from command line arguments
@@ -46,6 +94,14 @@ impl DecodeOptions {
         codecs::DecodeOptions {
             codec,
             format,
+            coarse: self.coarse.then_some(true),
+            macros: self.macros.clone(),
+            delimiter: self.delimiter,
+            quote: self.quote,
+            header: self.no_header.then_some(false),
+            column_types: self.column_types.clone(),
+            decimal_separator: self.decimal_separator,
+            sheets: self.sheets.clone(),
             strip_scopes: strip_options.strip_scopes,
             strip_types: strip_options.strip_types,
             strip_props: strip_options.strip_props,
@@ -71,6 +127,22 @@ pub struct EncodeOptions {
     #[arg(long, short)]
     render: bool,
 
+    /// Pre-render math to MathML
+    ///
+    /// Transpiles the TeX of `MathBlock` and `MathInline` nodes to MathML
+    /// at encode time so that published documents do not require client-side
+    /// math rendering (e.g. KaTeX or MathJax).
+    #[arg(long)]
+    render_math: bool,
+
+    /// The theme to use when encoding to HTML and HTML-based formats
+    ///
+    /// May be the name of a built-in theme (e.g. `default`, `latex`), the
+    /// path of a local CSS file, or the name of a CSS file in a `themes`
+    /// directory beside the document.
+    #[arg(long)]
+    theme: Option<String>,
+
     /// Use compact form of encoding if possible
     ///
     /// Use this flag to produce the compact forms of encoding (e.g. no indentation)
@@ -122,14 +194,18 @@ impl EncodeOptions {
 
         let render = self.render.then_some(true);
 
+        let render_math = self.render_math.then_some(true);
+
         let from_path = input.map(PathBuf::from);
 
         codecs::EncodeOptions {
             codec,
             format,
+            theme: self.theme.clone(),
             compact,
             standalone,
             render,
+            render_math,
             from_path,
             strip_scopes: strip_options.strip_scopes,
             strip_types: strip_options.strip_types,
@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+
+use cli_utils::{
+    table::{self, Attribute, Cell},
+    Code, ToStdout,
+};
+use common::{
+    clap::{self, Parser},
+    eyre::Result,
+    itertools::Itertools,
+    serde_json,
+};
+use document::{CommandWait, Document};
+use format::Format;
+use node_execute::ExecuteOptions;
+
+/// List the variables in a document
+///
+/// Executes the document and then lists the variables in its kernels,
+/// with a hint of the current value of each, the node that last writes
+/// it, and the nodes that read it.
+#[derive(Debug, Parser)]
+pub struct Cli {
+    /// The path of the document to list variables for
+    input: PathBuf,
+
+    /// Do not execute the document before listing variables
+    ///
+    /// Note that, because kernels are started afresh for each command,
+    /// using this flag will usually result in an empty list.
+    #[arg(long)]
+    no_execute: bool,
+
+    /// Output the list of variables as JSON
+    #[arg(long)]
+    json: bool,
+
+    #[clap(flatten)]
+    execute_options: ExecuteOptions,
+}
+
+impl Cli {
+    pub async fn run(self) -> Result<()> {
+        let Self {
+            input,
+            no_execute,
+            json,
+            execute_options,
+        } = self;
+
+        let doc = Document::open(&input).await?;
+        doc.compile(CommandWait::Yes).await?;
+        if !no_execute {
+            doc.execute(execute_options, CommandWait::Yes).await?;
+        }
+
+        let variables = doc.variables().await?;
+
+        if json {
+            let content = serde_json::to_string_pretty(&variables)?;
+            Code::new(Format::Json, &content).to_stdout();
+
+            return Ok(());
+        }
+
+        let mut table = table::new();
+        table.set_header(["Name", "Type", "Hint", "Written by", "Read by"]);
+
+        for info in variables {
+            let variable = info.variable;
+            let hint = variable
+                .hint
+                .as_ref()
+                .and_then(|hint| serde_json::to_string(hint).ok())
+                .unwrap_or_default();
+
+            table.add_row([
+                Cell::new(variable.name).add_attribute(Attribute::Bold),
+                Cell::new(variable.node_type.or(variable.native_type).unwrap_or_default()),
+                Cell::new(hint),
+                Cell::new(info.written_by.unwrap_or_default()),
+                Cell::new(info.read_by.iter().join(", ")),
+            ]);
+        }
+
+        println!("{table}");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+//! Conversion between Stencila [`Datatable`]s and Arrow [`RecordBatch`]es
+//!
+//! These functions are public so that they can be reused when bridging
+//! datatables to Arrow-based kernels and other consumers of Arrow data.
+
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array, ArrayRef, AsArray, BooleanArray, Float64Array, Int64Array, StringArray, UInt64Array,
+    },
+    datatypes::{DataType, Field, Float64Type, Int64Type, Schema, UInt64Type},
+    record_batch::RecordBatch,
+    util::display::array_value_to_string,
+};
+
+use codec::{
+    common::{eyre::Result, serde_json},
+    schema::{Datatable, DatatableColumn, Primitive},
+};
+
+/// Convert a [`Datatable`] to an Arrow [`RecordBatch`]
+pub fn datatable_to_record_batch(datatable: &Datatable) -> Result<RecordBatch> {
+    let rows = datatable
+        .columns
+        .iter()
+        .map(|column| column.values.len())
+        .max()
+        .unwrap_or_default();
+
+    let mut fields = Vec::new();
+    let mut arrays: Vec<ArrayRef> = Vec::new();
+    for column in &datatable.columns {
+        let (data_type, array) = column_to_array(column, rows);
+        fields.push(Field::new(
+            column.name.clone(),
+            data_type,
+            array.null_count() > 0,
+        ));
+        arrays.push(array);
+    }
+
+    Ok(RecordBatch::try_new(
+        Arc::new(Schema::new(fields)),
+        arrays,
+    )?)
+}
+
+/// Convert Arrow [`RecordBatch`]es to a [`Datatable`]
+pub fn record_batches_to_datatable(batches: &[RecordBatch]) -> Result<Datatable> {
+    let Some(first) = batches.first() else {
+        return Ok(Datatable::new(Vec::new()));
+    };
+
+    let mut columns = Vec::new();
+    for (index, field) in first.schema().fields().iter().enumerate() {
+        let mut values = Vec::new();
+        for batch in batches {
+            let array = batch.column(index);
+            for row in 0..array.len() {
+                values.push(array_value_to_primitive(array, row)?);
+            }
+        }
+        columns.push(DatatableColumn::new(field.name().clone(), values));
+    }
+
+    Ok(Datatable::new(columns))
+}
+
+/// Convert a [`DatatableColumn`] to an Arrow [`ArrayRef`]
+///
+/// The Arrow data type is inferred from the values of the column: booleans,
+/// integers, and numbers map to the corresponding Arrow types, anything
+/// else (including columns of mixed types) falls back to strings.
+fn column_to_array(column: &DatatableColumn, rows: usize) -> (DataType, ArrayRef) {
+    let values = &column.values;
+
+    let all = |pred: fn(&Primitive) -> bool| {
+        values
+            .iter()
+            .all(|value| matches!(value, Primitive::Null(..)) || pred(value))
+    };
+
+    if all(|value| matches!(value, Primitive::Boolean(..))) {
+        let array: BooleanArray = (0..rows)
+            .map(|index| match values.get(index) {
+                Some(Primitive::Boolean(value)) => Some(*value),
+                _ => None,
+            })
+            .collect();
+        return (DataType::Boolean, Arc::new(array));
+    }
+
+    if all(|value| matches!(value, Primitive::Integer(..))) {
+        let array: Int64Array = (0..rows)
+            .map(|index| match values.get(index) {
+                Some(Primitive::Integer(value)) => Some(*value),
+                _ => None,
+            })
+            .collect();
+        return (DataType::Int64, Arc::new(array));
+    }
+
+    if all(|value| matches!(value, Primitive::UnsignedInteger(..))) {
+        let array: UInt64Array = (0..rows)
+            .map(|index| match values.get(index) {
+                Some(Primitive::UnsignedInteger(value)) => Some(*value),
+                _ => None,
+            })
+            .collect();
+        return (DataType::UInt64, Arc::new(array));
+    }
+
+    if all(|value| {
+        matches!(
+            value,
+            Primitive::Number(..) | Primitive::Integer(..) | Primitive::UnsignedInteger(..)
+        )
+    }) {
+        let array: Float64Array = (0..rows)
+            .map(|index| match values.get(index) {
+                Some(Primitive::Number(value)) => Some(*value),
+                Some(Primitive::Integer(value)) => Some(*value as f64),
+                Some(Primitive::UnsignedInteger(value)) => Some(*value as f64),
+                _ => None,
+            })
+            .collect();
+        return (DataType::Float64, Arc::new(array));
+    }
+
+    let array: StringArray = (0..rows)
+        .map(|index| match values.get(index) {
+            None | Some(Primitive::Null(..)) => None,
+            Some(Primitive::String(value)) => Some(value.clone()),
+            Some(value) => Some(serde_json::to_string(value).unwrap_or_default()),
+        })
+        .collect();
+    (DataType::Utf8, Arc::new(array))
+}
+
+/// Convert a value of an Arrow [`Array`] to a [`Primitive`]
+fn array_value_to_primitive(array: &ArrayRef, row: usize) -> Result<Primitive> {
+    if array.is_null(row) {
+        return Ok(Primitive::Null(Default::default()));
+    }
+
+    Ok(match array.data_type() {
+        DataType::Boolean => Primitive::Boolean(array.as_boolean().value(row)),
+        DataType::Int64 => Primitive::Integer(array.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt64 => {
+            Primitive::UnsignedInteger(array.as_primitive::<UInt64Type>().value(row))
+        }
+        DataType::Float64 => Primitive::Number(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Utf8 => Primitive::String(array.as_string::<i32>().value(row).to_string()),
+        _ => Primitive::String(array_value_to_string(array, row)?),
+    })
+}
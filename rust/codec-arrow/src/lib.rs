@@ -0,0 +1,116 @@
+use std::{fs::File, path::Path};
+
+use arrow::ipc::{
+    reader::{FileReader, StreamReader},
+    writer::FileWriter,
+};
+
+use codec::{
+    common::{
+        async_trait::async_trait,
+        eyre::{bail, Result},
+    },
+    format::Format,
+    schema::Node,
+    status::Status,
+    Codec, CodecSupport, DecodeInfo, DecodeOptions, EncodeInfo, EncodeOptions, NodeType,
+};
+
+mod conversion;
+
+pub use conversion::{datatable_to_record_batch, record_batches_to_datatable};
+
+/// A codec for Apache Arrow IPC (also known as Feather V2)
+///
+/// A lossless, fast interchange format for [`Datatable`]s. The conversion
+/// between datatables and Arrow record batches is in the `conversion`
+/// module so that it can also be used for bridging to Arrow-based kernels.
+pub struct ArrowCodec;
+
+#[async_trait]
+impl Codec for ArrowCodec {
+    fn name(&self) -> &str {
+        "arrow"
+    }
+
+    fn status(&self) -> Status {
+        Status::UnderDevelopment
+    }
+
+    fn supports_from_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Arrow => CodecSupport::NoLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_to_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Arrow => CodecSupport::NoLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_from_type(&self, node_type: NodeType) -> CodecSupport {
+        match node_type {
+            NodeType::Datatable => CodecSupport::NoLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_to_type(&self, node_type: NodeType) -> CodecSupport {
+        match node_type {
+            NodeType::Datatable => CodecSupport::NoLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_from_string(&self) -> bool {
+        false
+    }
+
+    fn supports_to_string(&self) -> bool {
+        false
+    }
+
+    async fn from_path(
+        &self,
+        path: &Path,
+        _options: Option<DecodeOptions>,
+    ) -> Result<(Node, DecodeInfo)> {
+        if !path.exists() {
+            bail!("Path `{}` does not exist", path.display());
+        }
+
+        // Try the IPC file format first, falling back to the stream format
+        let batches = if let Ok(reader) = FileReader::try_new(File::open(path)?, None) {
+            reader.collect::<Result<Vec<_>, _>>()?
+        } else {
+            StreamReader::try_new(File::open(path)?, None)?.collect::<Result<Vec<_>, _>>()?
+        };
+
+        let datatable = record_batches_to_datatable(&batches)?;
+
+        Ok((Node::Datatable(datatable), DecodeInfo::none()))
+    }
+
+    async fn to_path(
+        &self,
+        node: &Node,
+        path: &Path,
+        _options: Option<EncodeOptions>,
+    ) -> Result<EncodeInfo> {
+        let Node::Datatable(datatable) = node else {
+            bail!("Unable to encode a `{node}` to Arrow")
+        };
+
+        let batch = datatable_to_record_batch(datatable)?;
+
+        let file = File::create(path)?;
+        let mut writer = FileWriter::try_new(file, batch.schema().as_ref())?;
+        writer.write(&batch)?;
+        writer.finish()?;
+
+        Ok(EncodeInfo::none())
+    }
+}
@@ -0,0 +1,302 @@
+use std::str::FromStr;
+
+use codec::{
+    schema::{
+        shortcuts::{p, t},
+        Article, Author, CreativeWorkType, CreativeWorkTypeOrText, Date, IntegerOrString,
+        Organization, Periodical, Person, PersonOrOrganization, PropertyValueOrString,
+    },
+    Losses,
+};
+
+/// Decode BibTeX content into references
+///
+/// Each entry becomes an [`Article`] reference with its citation key as the
+/// `id`. Fields that have no equivalent in the schema are recorded as
+/// losses.
+pub(super) fn decode(bibtex: &str) -> (Vec<CreativeWorkTypeOrText>, Losses) {
+    let mut references = Vec::new();
+    let mut losses = Losses::none();
+
+    for entry in parse_entries(bibtex) {
+        let Entry {
+            entry_type,
+            key,
+            fields,
+        } = entry;
+
+        match entry_type.as_str() {
+            "comment" | "preamble" | "string" => {
+                losses.add(format!("@{entry_type}"));
+                continue;
+            }
+            "article" => {}
+            _ => losses.add(format!("@{entry_type}")),
+        }
+
+        let mut article = Article {
+            id: Some(key),
+            ..Default::default()
+        };
+
+        let mut year = None;
+        let mut month = None;
+        let mut pages = None;
+
+        for (name, value) in fields {
+            match name.as_str() {
+                "title" => article.title = Some(vec![t(value)]),
+                "author" => {
+                    let authors = value
+                        .split(" and ")
+                        .filter_map(|name| Person::from_str(name.trim()).ok())
+                        .map(Author::Person)
+                        .collect::<Vec<Author>>();
+                    article.authors = (!authors.is_empty()).then_some(authors);
+                }
+                "year" => year = Some(value),
+                "month" => month = Some(value),
+                "abstract" => article.r#abstract = Some(vec![p([t(value)])]),
+                "journal" | "journaltitle" | "booktitle" => {
+                    article.options.is_part_of =
+                        Some(CreativeWorkType::Periodical(Periodical {
+                            name: Some(value),
+                            ..Default::default()
+                        }))
+                }
+                "publisher" => {
+                    article.options.publisher =
+                        Some(PersonOrOrganization::Organization(Organization {
+                            name: Some(value),
+                            ..Default::default()
+                        }))
+                }
+                "pages" => pages = Some(value),
+                "url" => article.options.url = Some(value),
+                "doi" => {
+                    article
+                        .options
+                        .identifiers
+                        .get_or_insert_with(Vec::new)
+                        .push(PropertyValueOrString::String(format!(
+                            "https://doi.org/{value}"
+                        )));
+                }
+                _ => losses.add(format!("@{entry_type}.{name}")),
+            }
+        }
+
+        if let Some(year) = year {
+            let value = match month.and_then(|month| month_number(&month)) {
+                Some(month) => format!("{year}-{month:02}"),
+                None => year,
+            };
+            article.date_published = Some(Date::new(value));
+        }
+
+        if let Some(pages) = pages {
+            let mut parts = pages.splitn(2, ['-', '–']).map(str::trim);
+            article.options.page_start = parts.next().map(page_to_integer_or_string);
+            article.options.page_end = parts
+                .next()
+                .map(|end| end.trim_start_matches('-'))
+                .filter(|end| !end.is_empty())
+                .map(page_to_integer_or_string);
+        }
+
+        references.push(CreativeWorkTypeOrText::CreativeWorkType(
+            CreativeWorkType::Article(article),
+        ));
+    }
+
+    (references, losses)
+}
+
+/// A parsed BibTeX entry
+struct Entry {
+    entry_type: String,
+    key: String,
+    fields: Vec<(String, String)>,
+}
+
+/// Parse the entries of a BibTeX string
+///
+/// A hand rolled parser that handles brace and quote delimited field
+/// values with nested braces. Content outside of entries is ignored,
+/// as are malformed entries.
+fn parse_entries(bibtex: &str) -> Vec<Entry> {
+    let mut entries = Vec::new();
+
+    let mut chars = bibtex.char_indices().peekable();
+    while let Some((start, char)) = chars.next() {
+        if char != '@' {
+            continue;
+        }
+
+        // Entry type up to opening brace or parenthesis
+        let rest = &bibtex[start + 1..];
+        let Some(open) = rest.find(['{', '(']) else {
+            break;
+        };
+        let entry_type = rest[..open].trim().to_lowercase();
+        if entry_type.is_empty() || !entry_type.chars().all(|char| char.is_ascii_alphabetic()) {
+            continue;
+        }
+
+        // Body up to the balanced closing delimiter
+        let body_start = start + 1 + open + 1;
+        let Some(body) = balanced(&bibtex[body_start..]) else {
+            break;
+        };
+
+        // Consume the characters of this entry
+        let end = body_start + body.len();
+        while chars.peek().map_or(false, |(index, ..)| *index <= end) {
+            chars.next();
+        }
+
+        // Citation key up to the first comma
+        let (key, fields) = match body.split_once(',') {
+            Some((key, fields)) => (key.trim().to_string(), fields),
+            None => (body.trim().to_string(), ""),
+        };
+
+        entries.push(Entry {
+            entry_type,
+            key,
+            fields: parse_fields(fields),
+        });
+    }
+
+    entries
+}
+
+/// Get the content up to the brace or parenthesis balancing the one
+/// preceding the string
+fn balanced(rest: &str) -> Option<&str> {
+    let mut depth = 1;
+    for (index, char) in rest.char_indices() {
+        match char {
+            '{' | '(' => depth += 1,
+            '}' | ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&rest[..index]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse the fields of an entry body
+fn parse_fields(body: &str) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+
+    let mut chars = body.char_indices().peekable();
+    while chars.peek().is_some() {
+        // Field name up to equals sign
+        let mut name = String::new();
+        for (.., char) in chars.by_ref() {
+            if char == '=' {
+                break;
+            }
+            name.push(char);
+        }
+        let name = name.trim_matches([' ', '\t', '\n', '\r', ',']).to_lowercase();
+        if name.is_empty() {
+            break;
+        }
+
+        // Field value delimited by braces, quotes, or a comma
+        let mut value = String::new();
+        let mut depth = 0;
+        let mut quoted = false;
+        let mut started = false;
+        for (.., char) in chars.by_ref() {
+            if !started {
+                match char {
+                    ' ' | '\t' | '\n' | '\r' => continue,
+                    '{' => {
+                        started = true;
+                        depth = 1;
+                        continue;
+                    }
+                    '"' => {
+                        started = true;
+                        quoted = true;
+                        continue;
+                    }
+                    _ => {
+                        started = true;
+                    }
+                }
+            } else if quoted {
+                if char == '"' {
+                    break;
+                }
+            } else if depth > 0 {
+                match char {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+                if char == '{' || char == '}' {
+                    continue;
+                }
+            } else if char == ',' {
+                break;
+            }
+            value.push(char);
+        }
+
+        fields.push((name, clean(&value)));
+    }
+
+    fields
+}
+
+/// Clean a field value by removing braces and collapsing whitespace
+fn clean(value: &str) -> String {
+    value
+        .replace(['{', '}'], "")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Convert a page identifier to an integer if possible
+fn page_to_integer_or_string(page: &str) -> IntegerOrString {
+    match page.parse() {
+        Ok(page) => IntegerOrString::Integer(page),
+        Err(..) => IntegerOrString::String(page.to_string()),
+    }
+}
+
+/// Convert a BibTeX month name or number to a month number
+fn month_number(month: &str) -> Option<u32> {
+    if let Ok(month) = month.parse() {
+        return (1..=12).contains(&month).then_some(month);
+    }
+    match month.to_lowercase().get(..3)? {
+        "jan" => Some(1),
+        "feb" => Some(2),
+        "mar" => Some(3),
+        "apr" => Some(4),
+        "may" => Some(5),
+        "jun" => Some(6),
+        "jul" => Some(7),
+        "aug" => Some(8),
+        "sep" => Some(9),
+        "oct" => Some(10),
+        "nov" => Some(11),
+        "dec" => Some(12),
+        _ => None,
+    }
+}
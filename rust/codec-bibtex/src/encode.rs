@@ -0,0 +1,140 @@
+use codec::{
+    common::eyre::{bail, Result},
+    schema::{
+        Article, Author, CreativeWorkType, CreativeWorkTypeOrText, IntegerOrString, Node, Person,
+        PersonOrOrganization,
+    },
+    EncodeInfo, Losses,
+};
+use codec_text_trait::to_text;
+
+/// Encode the references of a [`Node`] to BibTeX
+///
+/// Each reference becomes an `@article` entry (or `@misc` for plain text
+/// references, with the text in the `note` field).
+pub(super) fn encode(node: &Node) -> Result<(String, EncodeInfo)> {
+    let Node::Article(Article { references, .. }) = node else {
+        bail!("Unable to encode a `{node}` to BibTeX")
+    };
+
+    let mut bibtex = String::new();
+    let mut losses = Losses::none();
+
+    for (index, reference) in references.iter().flatten().enumerate() {
+        match reference {
+            CreativeWorkTypeOrText::CreativeWorkType(CreativeWorkType::Article(article)) => {
+                bibtex.push_str(&encode_article(article, index));
+            }
+            CreativeWorkTypeOrText::Text(text) => {
+                bibtex.push_str(&format!(
+                    "@misc{{ref{number},\n  note = {{{text}}}\n}}\n\n",
+                    number = index + 1,
+                    text = text.value
+                ));
+            }
+            _ => losses.add(reference.to_string()),
+        }
+    }
+
+    Ok((
+        bibtex,
+        EncodeInfo {
+            losses,
+            ..Default::default()
+        },
+    ))
+}
+
+/// Encode an [`Article`] reference as an `@article` entry
+fn encode_article(article: &Article, index: usize) -> String {
+    let key = article
+        .id
+        .clone()
+        .unwrap_or_else(|| format!("ref{}", index + 1));
+
+    let mut fields = Vec::new();
+
+    if let Some(authors) = &article.authors {
+        let authors = authors
+            .iter()
+            .filter_map(author_to_name)
+            .collect::<Vec<String>>()
+            .join(" and ");
+        if !authors.is_empty() {
+            fields.push(("author", authors));
+        }
+    }
+
+    if let Some(title) = &article.title {
+        fields.push(("title", to_text(title)));
+    }
+
+    if let Some(CreativeWorkType::Periodical(periodical)) = &article.options.is_part_of {
+        if let Some(name) = &periodical.name {
+            fields.push(("journal", name.clone()));
+        }
+    }
+
+    if let Some(date) = &article.date_published {
+        if let Some(year) = date.value.get(..4) {
+            fields.push(("year", year.to_string()));
+        }
+    }
+
+    if let Some(start) = &article.options.page_start {
+        let mut pages = integer_or_string(start);
+        if let Some(end) = &article.options.page_end {
+            pages.push_str("--");
+            pages.push_str(&integer_or_string(end));
+        }
+        fields.push(("pages", pages));
+    }
+
+    if let Some(PersonOrOrganization::Organization(organization)) = &article.options.publisher {
+        if let Some(name) = &organization.name {
+            fields.push(("publisher", name.clone()));
+        }
+    }
+
+    if let Some(url) = &article.options.url {
+        fields.push(("url", url.clone()));
+    }
+
+    let fields = fields
+        .into_iter()
+        .map(|(name, value)| format!("  {name} = {{{value}}}"))
+        .collect::<Vec<String>>()
+        .join(",\n");
+
+    format!("@article{{{key},\n{fields}\n}}\n\n")
+}
+
+/// Convert an [`Author`] to a BibTeX name
+fn author_to_name(author: &Author) -> Option<String> {
+    match author {
+        Author::Person(Person {
+            given_names,
+            family_names,
+            ..
+        }) => {
+            let family = family_names.as_ref().map(|names| names.join(" "));
+            let given = given_names.as_ref().map(|names| names.join(" "));
+            match (family, given) {
+                (Some(family), Some(given)) => Some(format!("{family}, {given}")),
+                (Some(family), None) => Some(family),
+                (None, Some(given)) => Some(given),
+                (None, None) => None,
+            }
+        }
+        Author::Organization(organization) => organization.name.clone(),
+        _ => None,
+    }
+}
+
+/// Convert an [`IntegerOrString`] to a string
+fn integer_or_string(value: &IntegerOrString) -> String {
+    match value {
+        IntegerOrString::Integer(value) => value.to_string(),
+        IntegerOrString::String(value) => value.clone(),
+    }
+}
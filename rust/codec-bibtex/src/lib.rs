@@ -0,0 +1,86 @@
+use codec::{
+    common::{async_trait::async_trait, eyre::Result},
+    format::Format,
+    schema::{Article, Node},
+    status::Status,
+    Codec, CodecSupport, DecodeInfo, DecodeOptions, EncodeInfo, EncodeOptions, NodeType,
+};
+
+mod decode;
+mod encode;
+
+/// A codec for BibTeX bibliographies
+///
+/// Decodes the entries of a `.bib` file into the `references` of an
+/// [`Article`] and encodes an article's references back to BibTeX. Used
+/// both standalone and when a document points at a `.bib` file for its
+/// bibliography.
+pub struct BibtexCodec;
+
+#[async_trait]
+impl Codec for BibtexCodec {
+    fn name(&self) -> &str {
+        "bibtex"
+    }
+
+    fn status(&self) -> Status {
+        Status::UnderDevelopment
+    }
+
+    fn supports_from_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Bibtex => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_to_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Bibtex => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_from_type(&self, node_type: NodeType) -> CodecSupport {
+        match node_type {
+            NodeType::Article => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_to_type(&self, node_type: NodeType) -> CodecSupport {
+        match node_type {
+            NodeType::Article => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    async fn from_str(
+        &self,
+        input: &str,
+        _options: Option<DecodeOptions>,
+    ) -> Result<(Node, DecodeInfo)> {
+        let (references, losses) = decode::decode(input);
+
+        let article = Article {
+            references: (!references.is_empty()).then_some(references),
+            ..Default::default()
+        };
+
+        Ok((
+            Node::Article(article),
+            DecodeInfo {
+                losses,
+                ..Default::default()
+            },
+        ))
+    }
+
+    async fn to_string(
+        &self,
+        node: &Node,
+        _options: Option<EncodeOptions>,
+    ) -> Result<(String, EncodeInfo)> {
+        encode::encode(node)
+    }
+}
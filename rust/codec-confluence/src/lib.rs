@@ -0,0 +1,157 @@
+use codec::{
+    common::{async_trait::async_trait, eyre::Result},
+    format::Format,
+    schema::Node,
+    status::Status,
+    Codec, CodecSupport, EncodeInfo, EncodeOptions, NodeType,
+};
+use codec_pandoc::{pandoc_to_format, root_to_pandoc};
+
+/// A codec for the Confluence storage format
+///
+/// Encodes a document as the XHTML-based storage format used by the
+/// Confluence REST API, so that rendered reports can be pushed into team
+/// wikis. Encodes to XHTML via Pandoc and then rewrites code blocks and
+/// images as Confluence `<ac:...>` macros. Encoding only.
+pub struct ConfluenceCodec;
+
+#[async_trait]
+impl Codec for ConfluenceCodec {
+    fn name(&self) -> &str {
+        "confluence"
+    }
+
+    fn status(&self) -> Status {
+        Status::UnderDevelopment
+    }
+
+    fn supports_from_format(&self, _format: &Format) -> CodecSupport {
+        CodecSupport::None
+    }
+
+    fn supports_to_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Confluence => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_from_type(&self, _node_type: NodeType) -> CodecSupport {
+        CodecSupport::None
+    }
+
+    fn supports_to_type(&self, _node_type: NodeType) -> CodecSupport {
+        CodecSupport::LowLoss
+    }
+
+    async fn to_string(
+        &self,
+        node: &Node,
+        options: Option<EncodeOptions>,
+    ) -> Result<(String, EncodeInfo)> {
+        let (pandoc, info) = root_to_pandoc(node)?;
+
+        // Disable syntax highlighting so that code blocks are plain
+        // `<pre class="lang"><code>` elements which can be rewritten below
+        let mut args = vec!["--no-highlight".to_string()];
+        if let Some(options) = options {
+            args.extend(options.passthrough_args);
+        }
+
+        let html = pandoc_to_format(&pandoc, None, "html", args).await?;
+
+        Ok((rewrite(&html), info))
+    }
+}
+
+/// Rewrite XHTML into the Confluence storage format
+fn rewrite(html: &str) -> String {
+    rewrite_images(&rewrite_code_blocks(html))
+}
+
+/// Rewrite `<pre class="lang"><code>...</code></pre>` elements as
+/// Confluence code macros
+fn rewrite_code_blocks(html: &str) -> String {
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find("<pre") {
+        let Some(end) = rest[start..].find("</pre>") else {
+            break;
+        };
+
+        output.push_str(&rest[..start]);
+
+        let element = &rest[start..start + end];
+        let language = attribute(element, "class");
+        let code = element
+            .find('>')
+            .map(|open| &element[open + 1..])
+            .unwrap_or_default()
+            .trim_start_matches("<code>")
+            .trim_end_matches("</code>");
+
+        output.push_str("<ac:structured-macro ac:name=\"code\">");
+        if let Some(language) = language {
+            output.push_str("<ac:parameter ac:name=\"language\">");
+            output.push_str(&language);
+            output.push_str("</ac:parameter>");
+        }
+        output.push_str("<ac:plain-text-body><![CDATA[");
+        output.push_str(&unescape(code));
+        output.push_str("]]></ac:plain-text-body></ac:structured-macro>");
+
+        rest = &rest[start + end + 6..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Rewrite `<img src="...">` elements as Confluence image macros
+///
+/// Relative paths become attachment references and absolute URLs become
+/// external image references.
+fn rewrite_images(html: &str) -> String {
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find("<img") {
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+
+        output.push_str(&rest[..start]);
+
+        let element = &rest[start..start + end];
+        if let Some(src) = attribute(element, "src") {
+            if src.starts_with("http://") || src.starts_with("https://") {
+                output.push_str("<ac:image><ri:url ri:value=\"");
+                output.push_str(&src);
+                output.push_str("\"/></ac:image>");
+            } else {
+                let filename = src.rsplit('/').next().unwrap_or(&src);
+                output.push_str("<ac:image><ri:attachment ri:filename=\"");
+                output.push_str(filename);
+                output.push_str("\"/></ac:image>");
+            }
+        }
+
+        rest = &rest[start + end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Get the value of an attribute within an element's opening tag
+fn attribute(element: &str, name: &str) -> Option<String> {
+    let start = element.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = element[start..].find('"')?;
+    Some(element[start..start + end].to_string())
+}
+
+/// Unescape XML character entities in code content
+fn unescape(code: &str) -> String {
+    code.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
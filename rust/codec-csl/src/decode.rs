@@ -0,0 +1,178 @@
+use codec::{
+    common::{
+        eyre::Result,
+        serde_json::{self, Value},
+    },
+    schema::{
+        shortcuts::{p, t},
+        Article, Author, CreativeWorkType, CreativeWorkTypeOrText, Date, IntegerOrString,
+        Periodical, Person, PropertyValueOrString,
+    },
+    Losses,
+};
+
+/// Decode CSL-JSON content into references
+///
+/// Accepts either a JSON array of CSL items or a single item. Each item
+/// becomes an [`Article`] reference with the CSL `id` as its `id`.
+/// Item properties without an equivalent in the schema are recorded as
+/// losses.
+pub(super) fn decode(csl: &str) -> Result<(Vec<CreativeWorkTypeOrText>, Losses)> {
+    let value: Value = serde_json::from_str(csl)?;
+
+    let items = match value {
+        Value::Array(items) => items,
+        item => vec![item],
+    };
+
+    let mut references = Vec::new();
+    let mut losses = Losses::none();
+
+    for item in items {
+        let Value::Object(item) = item else {
+            losses.add("item");
+            continue;
+        };
+
+        let mut article = Article::default();
+
+        for (name, value) in item {
+            match name.as_str() {
+                "id" => {
+                    article.id = match value {
+                        Value::String(id) => Some(id),
+                        Value::Number(id) => Some(id.to_string()),
+                        _ => None,
+                    }
+                }
+                "title" => {
+                    if let Value::String(title) = value {
+                        article.title = Some(vec![t(title)]);
+                    }
+                }
+                "author" => {
+                    let authors = value
+                        .as_array()
+                        .iter()
+                        .flat_map(|authors| authors.iter())
+                        .filter_map(author_from_item)
+                        .collect::<Vec<Author>>();
+                    article.authors = (!authors.is_empty()).then_some(authors);
+                }
+                "issued" => article.date_published = date_from_item(&value),
+                "container-title" => {
+                    if let Value::String(name) = value {
+                        article.options.is_part_of =
+                            Some(CreativeWorkType::Periodical(Periodical {
+                                name: Some(name),
+                                ..Default::default()
+                            }));
+                    }
+                }
+                "page" => {
+                    if let Value::String(page) = value {
+                        let mut parts = page.splitn(2, ['-', '–']).map(str::trim);
+                        article.options.page_start =
+                            parts.next().map(page_to_integer_or_string);
+                        article.options.page_end = parts
+                            .next()
+                            .map(|end| end.trim_start_matches('-'))
+                            .filter(|end| !end.is_empty())
+                            .map(page_to_integer_or_string);
+                    }
+                }
+                "abstract" => {
+                    if let Value::String(text) = value {
+                        article.r#abstract = Some(vec![p([t(text)])]);
+                    }
+                }
+                "URL" => {
+                    if let Value::String(url) = value {
+                        article.options.url = Some(url);
+                    }
+                }
+                "DOI" => {
+                    if let Value::String(doi) = value {
+                        article
+                            .options
+                            .identifiers
+                            .get_or_insert_with(Vec::new)
+                            .push(PropertyValueOrString::String(format!(
+                                "https://doi.org/{doi}"
+                            )));
+                    }
+                }
+                "type" => {}
+                _ => losses.add(name),
+            }
+        }
+
+        references.push(CreativeWorkTypeOrText::CreativeWorkType(
+            CreativeWorkType::Article(article),
+        ));
+    }
+
+    Ok((references, losses))
+}
+
+/// Convert a CSL name item to an [`Author`]
+fn author_from_item(item: &Value) -> Option<Author> {
+    let family = item.get("family").and_then(Value::as_str);
+    let given = item.get("given").and_then(Value::as_str);
+    let literal = item.get("literal").and_then(Value::as_str);
+
+    if family.is_none() && given.is_none() && literal.is_none() {
+        return None;
+    }
+
+    Some(Author::Person(Person {
+        family_names: family
+            .or(literal)
+            .map(|name| name.split_whitespace().map(String::from).collect()),
+        given_names: given.map(|name| name.split_whitespace().map(String::from).collect()),
+        ..Default::default()
+    }))
+}
+
+/// Convert a CSL date item to a [`Date`]
+///
+/// Uses the first of any `date-parts`, falling back to any `raw` or
+/// `literal` value.
+fn date_from_item(item: &Value) -> Option<Date> {
+    if let Some(parts) = item
+        .get("date-parts")
+        .and_then(Value::as_array)
+        .and_then(|parts| parts.first())
+        .and_then(Value::as_array)
+    {
+        let value = parts
+            .iter()
+            .filter_map(Value::as_i64)
+            .enumerate()
+            .map(|(index, part)| {
+                if index == 0 {
+                    part.to_string()
+                } else {
+                    format!("{part:02}")
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("-");
+        if !value.is_empty() {
+            return Some(Date::new(value));
+        }
+    }
+
+    item.get("raw")
+        .or_else(|| item.get("literal"))
+        .and_then(Value::as_str)
+        .map(|value| Date::new(value.to_string()))
+}
+
+/// Convert a page identifier to an integer if possible
+fn page_to_integer_or_string(page: &str) -> IntegerOrString {
+    match page.parse() {
+        Ok(page) => IntegerOrString::Integer(page),
+        Err(..) => IntegerOrString::String(page.to_string()),
+    }
+}
@@ -0,0 +1,163 @@
+use codec::{
+    common::{
+        eyre::{bail, Result},
+        serde_json::{self, json, Map, Value},
+    },
+    schema::{
+        Article, Author, CreativeWorkType, CreativeWorkTypeOrText, IntegerOrString, Node, Person,
+        PropertyValueOrString,
+    },
+    EncodeInfo, EncodeOptions, Losses,
+};
+use codec_text_trait::to_text;
+
+/// Encode the references of a [`Node`] to CSL-JSON
+///
+/// Each reference becomes a CSL item of type `article-journal` (or an
+/// item with only a `title` for plain text references).
+pub(super) fn encode(node: &Node, options: Option<EncodeOptions>) -> Result<(String, EncodeInfo)> {
+    let Node::Article(Article { references, .. }) = node else {
+        bail!("Unable to encode a `{node}` to CSL-JSON")
+    };
+
+    let mut items = Vec::new();
+    let mut losses = Losses::none();
+
+    for (index, reference) in references.iter().flatten().enumerate() {
+        match reference {
+            CreativeWorkTypeOrText::CreativeWorkType(CreativeWorkType::Article(article)) => {
+                items.push(encode_article(article, index));
+            }
+            CreativeWorkTypeOrText::Text(text) => {
+                items.push(json!({
+                    "id": format!("ref{}", index + 1),
+                    "title": text.value
+                }));
+            }
+            _ => losses.add(reference.to_string()),
+        }
+    }
+
+    let json = match options.and_then(|options| options.compact) {
+        Some(true) => serde_json::to_string(&items)?,
+        Some(false) | None => serde_json::to_string_pretty(&items)?,
+    };
+
+    Ok((
+        json,
+        EncodeInfo {
+            losses,
+            ..Default::default()
+        },
+    ))
+}
+
+/// Encode an [`Article`] reference as a CSL item
+fn encode_article(article: &Article, index: usize) -> Value {
+    let mut item = Map::new();
+
+    item.insert(
+        "id".to_string(),
+        Value::String(
+            article
+                .id
+                .clone()
+                .unwrap_or_else(|| format!("ref{}", index + 1)),
+        ),
+    );
+    item.insert(
+        "type".to_string(),
+        Value::String("article-journal".to_string()),
+    );
+
+    if let Some(title) = &article.title {
+        item.insert("title".to_string(), Value::String(to_text(title)));
+    }
+
+    if let Some(authors) = &article.authors {
+        let authors = authors
+            .iter()
+            .filter_map(author_to_item)
+            .collect::<Vec<Value>>();
+        if !authors.is_empty() {
+            item.insert("author".to_string(), Value::Array(authors));
+        }
+    }
+
+    if let Some(date) = &article.date_published {
+        let parts = date
+            .value
+            .split('-')
+            .filter_map(|part| part.parse::<i64>().ok())
+            .map(Value::from)
+            .collect::<Vec<Value>>();
+        if !parts.is_empty() {
+            item.insert("issued".to_string(), json!({ "date-parts": [parts] }));
+        }
+    }
+
+    if let Some(CreativeWorkType::Periodical(periodical)) = &article.options.is_part_of {
+        if let Some(name) = &periodical.name {
+            item.insert("container-title".to_string(), Value::String(name.clone()));
+        }
+    }
+
+    if let Some(start) = &article.options.page_start {
+        let mut page = integer_or_string(start);
+        if let Some(end) = &article.options.page_end {
+            page.push('-');
+            page.push_str(&integer_or_string(end));
+        }
+        item.insert("page".to_string(), Value::String(page));
+    }
+
+    if let Some(url) = &article.options.url {
+        item.insert("URL".to_string(), Value::String(url.clone()));
+    }
+
+    if let Some(doi) = article.options.identifiers.iter().flatten().find_map(
+        |identifier| match identifier {
+            PropertyValueOrString::String(value) => value
+                .strip_prefix("https://doi.org/")
+                .map(|doi| doi.to_string()),
+            _ => None,
+        },
+    ) {
+        item.insert("DOI".to_string(), Value::String(doi));
+    }
+
+    Value::Object(item)
+}
+
+/// Convert an [`Author`] to a CSL name item
+fn author_to_item(author: &Author) -> Option<Value> {
+    match author {
+        Author::Person(Person {
+            given_names,
+            family_names,
+            ..
+        }) => {
+            let mut item = Map::new();
+            if let Some(names) = family_names {
+                item.insert("family".to_string(), Value::String(names.join(" ")));
+            }
+            if let Some(names) = given_names {
+                item.insert("given".to_string(), Value::String(names.join(" ")));
+            }
+            (!item.is_empty()).then_some(Value::Object(item))
+        }
+        Author::Organization(organization) => organization
+            .name
+            .clone()
+            .map(|name| json!({ "literal": name })),
+        _ => None,
+    }
+}
+
+/// Convert an [`IntegerOrString`] to a string
+fn integer_or_string(value: &IntegerOrString) -> String {
+    match value {
+        IntegerOrString::Integer(value) => value.to_string(),
+        IntegerOrString::String(value) => value.clone(),
+    }
+}
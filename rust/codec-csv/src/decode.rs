@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use csv::ReaderBuilder;
+
+use codec::{
+    common::eyre::{bail, eyre, Result},
+    format::Format,
+    schema::{Datatable, DatatableColumn, Node, Primitive},
+    DecodeInfo, DecodeOptions,
+};
+
+/// The type of a column, as specified in the `column_types` decode option
+enum ColumnType {
+    Boolean,
+    Integer,
+    Number,
+    String,
+}
+
+/// Decode delimited text to a Stencila [`Node`]
+///
+/// Decodes to a [`Datatable`] with the type of each value inferred from its
+/// text, unless an explicit type for the column is specified in options.
+pub(super) fn decode(csv: &str, options: Option<DecodeOptions>) -> Result<(Node, DecodeInfo)> {
+    let options = options.unwrap_or_default();
+
+    let delimiter = options.delimiter.unwrap_or(match options.format {
+        Some(Format::Tsv) => '\t',
+        _ => ',',
+    });
+    let quote = options.quote.unwrap_or('"');
+    let header = options.header.unwrap_or(true);
+    let decimal = options.decimal_separator.unwrap_or('.');
+    let column_types = column_types(&options.column_types)?;
+
+    let mut reader = ReaderBuilder::new()
+        .delimiter(ascii(delimiter, "delimiter")?)
+        .quote(ascii(quote, "quote")?)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(csv.as_bytes());
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for record in reader.records() {
+        rows.push(record?.iter().map(String::from).collect());
+    }
+
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or_default();
+
+    let names: Vec<String> = if header {
+        let mut names = rows.first().cloned().unwrap_or_default();
+        while names.len() < width {
+            names.push(format!("Column {}", names.len() + 1));
+        }
+        names
+    } else {
+        (1..=width).map(|index| format!("Column {index}")).collect()
+    };
+
+    let columns = names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let column_type = column_types
+                .get(name)
+                .or_else(|| column_types.get(&(index + 1).to_string()));
+            let values = rows
+                .iter()
+                .skip(header as usize)
+                .map(|row| {
+                    row.get(index).map_or_else(
+                        || Primitive::Null(Default::default()),
+                        |text| decode_value(text, column_type, decimal),
+                    )
+                })
+                .collect();
+            DatatableColumn::new(name.clone(), values)
+        })
+        .collect();
+
+    Ok((
+        Node::Datatable(Datatable::new(columns)),
+        DecodeInfo::none(),
+    ))
+}
+
+/// Parse the `column_types` decode option into a map of column name (or
+/// one-based index) to [`ColumnType`]
+fn column_types(entries: &[String]) -> Result<HashMap<String, ColumnType>> {
+    let mut types = HashMap::new();
+    for entry in entries {
+        let Some((name, column_type)) = entry.split_once('=') else {
+            bail!("Invalid column type `{entry}`: expected the form `name=type`");
+        };
+        let column_type = match column_type.trim().to_lowercase().as_str() {
+            "boolean" => ColumnType::Boolean,
+            "integer" => ColumnType::Integer,
+            "number" => ColumnType::Number,
+            "string" => ColumnType::String,
+            _ => bail!(
+                "Unknown column type `{column_type}`: expected one of `boolean`, `integer`, `number`, or `string`"
+            ),
+        };
+        types.insert(name.trim().to_string(), column_type);
+    }
+    Ok(types)
+}
+
+/// Ensure that a delimiter or quote character is ASCII
+fn ascii(char: char, name: &str) -> Result<u8> {
+    u8::try_from(char).map_err(|..| eyre!("The {name} must be an ASCII character"))
+}
+
+/// Decode the text of a cell to a [`Primitive`] value
+///
+/// If the column has an explicit type, values that can not be parsed as that
+/// type fall back to a string (rather than corrupting them to a default).
+fn decode_value(text: &str, column_type: Option<&ColumnType>, decimal: char) -> Primitive {
+    if text.is_empty() {
+        return Primitive::Null(Default::default());
+    }
+
+    let string = || Primitive::String(text.to_string());
+
+    match column_type {
+        Some(ColumnType::Boolean) => decode_boolean(text).map_or_else(string, Primitive::Boolean),
+        Some(ColumnType::Integer) => decode_number(text, decimal)
+            .parse()
+            .map_or_else(|..| string(), Primitive::Integer),
+        Some(ColumnType::Number) => decode_number(text, decimal)
+            .parse()
+            .map_or_else(|..| string(), Primitive::Number),
+        Some(ColumnType::String) => string(),
+        None => {
+            if let Some(boolean) = decode_boolean(text) {
+                return Primitive::Boolean(boolean);
+            }
+            let number = decode_number(text, decimal);
+            if let Ok(integer) = number.parse() {
+                Primitive::Integer(integer)
+            } else if let Ok(number) = number.parse() {
+                Primitive::Number(number)
+            } else {
+                string()
+            }
+        }
+    }
+}
+
+/// Decode the text of a cell as a boolean
+fn decode_boolean(text: &str) -> Option<bool> {
+    match text.to_lowercase().as_str() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Normalize the text of a cell for parsing as a number
+///
+/// When the decimal separator is not a period, thousands separators (periods
+/// and spaces) are removed and the decimal separator replaced with a period.
+fn decode_number(text: &str, decimal: char) -> String {
+    if decimal == '.' {
+        return text.to_string();
+    }
+
+    text.chars()
+        .filter(|&char| char != '.' && char != ' ' && char != '\u{a0}')
+        .map(|char| if char == decimal { '.' } else { char })
+        .collect()
+}
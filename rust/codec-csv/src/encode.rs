@@ -0,0 +1,56 @@
+use csv::WriterBuilder;
+
+use codec::{
+    common::{
+        eyre::{bail, Result},
+        serde_json,
+    },
+    format::Format,
+    schema::{Node, Primitive},
+    EncodeInfo, EncodeOptions,
+};
+
+/// Encode a Stencila [`Node`] to delimited text
+///
+/// Only [`Datatable`] nodes can be encoded; the column names become the
+/// first row.
+pub(super) fn encode(node: &Node, options: Option<EncodeOptions>) -> Result<(String, EncodeInfo)> {
+    let Node::Datatable(datatable) = node else {
+        bail!("Unable to encode a `{node}` to CSV")
+    };
+
+    let delimiter = match options.and_then(|options| options.format) {
+        Some(Format::Tsv) => b'\t',
+        _ => b',',
+    };
+
+    let mut writer = WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(Vec::new());
+
+    writer.write_record(datatable.columns.iter().map(|column| &column.name))?;
+
+    let rows = datatable
+        .columns
+        .iter()
+        .map(|column| column.values.len())
+        .max()
+        .unwrap_or_default();
+    for index in 0..rows {
+        writer.write_record(datatable.columns.iter().map(
+            |column| match column.values.get(index) {
+                Some(Primitive::Null(..)) | None => String::new(),
+                Some(Primitive::Boolean(value)) => value.to_string(),
+                Some(Primitive::Integer(value)) => value.to_string(),
+                Some(Primitive::UnsignedInteger(value)) => value.to_string(),
+                Some(Primitive::Number(value)) => value.to_string(),
+                Some(Primitive::String(value)) => value.clone(),
+                Some(value) => serde_json::to_string(value).unwrap_or_default(),
+            },
+        ))?;
+    }
+
+    let csv = String::from_utf8(writer.into_inner()?)?;
+
+    Ok((csv, EncodeInfo::none()))
+}
@@ -0,0 +1,103 @@
+use codec::{common::tokio, schema::Primitive};
+use common_dev::pretty_assertions::assert_eq;
+
+use super::*;
+
+/// Decode with default options: comma delimited with a header row and
+/// inferred types
+#[tokio::test]
+async fn defaults() -> Result<()> {
+    let codec = CsvCodec {};
+
+    let (node, ..) = codec
+        .from_str("name,count,fraction\na,1,0.5\nb,,1.5\n", None)
+        .await?;
+
+    let Node::Datatable(datatable) = node else {
+        unreachable!()
+    };
+    assert_eq!(datatable.columns.len(), 3);
+    assert_eq!(datatable.columns[0].name, "name");
+    assert_eq!(
+        datatable.columns[0].values[0],
+        Primitive::String("a".to_string())
+    );
+    assert_eq!(datatable.columns[1].values[0], Primitive::Integer(1));
+    assert_eq!(
+        datatable.columns[1].values[1],
+        Primitive::Null(Default::default())
+    );
+    assert_eq!(datatable.columns[2].values[1], Primitive::Number(1.5));
+
+    Ok(())
+}
+
+/// Decode a European style CSV: semicolon delimited with comma as the
+/// decimal separator
+#[tokio::test]
+async fn dialect() -> Result<()> {
+    let codec = CsvCodec {};
+
+    let (node, ..) = codec
+        .from_str(
+            "name;amount\na;1.234,56\n",
+            Some(DecodeOptions {
+                delimiter: Some(';'),
+                decimal_separator: Some(','),
+                ..Default::default()
+            }),
+        )
+        .await?;
+
+    let Node::Datatable(datatable) = node else {
+        unreachable!()
+    };
+    assert_eq!(datatable.columns[1].values[0], Primitive::Number(1234.56));
+
+    Ok(())
+}
+
+/// Decode with no header row and explicit column types
+#[tokio::test]
+async fn no_header_column_types() -> Result<()> {
+    let codec = CsvCodec {};
+
+    let (node, ..) = codec
+        .from_str(
+            "001,1\n002,x\n",
+            Some(DecodeOptions {
+                header: Some(false),
+                column_types: vec!["1=string".to_string(), "2=integer".to_string()],
+                ..Default::default()
+            }),
+        )
+        .await?;
+
+    let Node::Datatable(datatable) = node else {
+        unreachable!()
+    };
+    assert_eq!(datatable.columns[0].name, "Column 1");
+    assert_eq!(
+        datatable.columns[0].values[0],
+        Primitive::String("001".to_string())
+    );
+    assert_eq!(datatable.columns[1].values[0], Primitive::Integer(1));
+    assert_eq!(
+        datatable.columns[1].values[1],
+        Primitive::String("x".to_string())
+    );
+
+    Ok(())
+}
+
+/// Round-trip a datatable through encode and decode
+#[tokio::test]
+async fn round_trip() -> Result<()> {
+    let codec = CsvCodec {};
+
+    let (node, ..) = codec.from_str("a,b\n1,true\n2,false\n", None).await?;
+    let (csv, ..) = codec.to_string(&node, None).await?;
+    assert_eq!(csv, "a,b\n1,true\n2,false\n");
+
+    Ok(())
+}
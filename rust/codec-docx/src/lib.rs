@@ -59,15 +59,16 @@ impl Codec for DocxCodec {
         path: &Path,
         options: Option<DecodeOptions>,
     ) -> Result<(Node, DecodeInfo)> {
-        let pandoc = pandoc_from_format(
-            "",
-            Some(path),
-            PANDOC_FORMAT,
-            options
+        // Keep tracked changes so that they are decoded to suggestion nodes
+        // (passthrough args come after so users can override)
+        let mut args = vec!["--track-changes=all".to_string()];
+        args.append(
+            &mut options
                 .map(|options| options.passthrough_args)
                 .unwrap_or_default(),
-        )
-        .await?;
+        );
+
+        let pandoc = pandoc_from_format("", Some(path), PANDOC_FORMAT, args).await?;
         root_from_pandoc(pandoc)
     }
 
@@ -1,13 +1,17 @@
 //! Provides the `DomCodec` trait for generating HTML for the
 //! browser DOM for Stencila Schema nodes
 
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use html_escape::{encode_safe, encode_single_quoted_attribute};
 
 use common::{
-    inflector::Inflector, itertools::Itertools, serde::Serialize, serde_json,
+    inflector::Inflector,
+    itertools::Itertools,
+    serde::Serialize,
+    serde_json,
     smart_default::SmartDefault,
+    smol_str::{SmolStr, ToSmolStr},
 };
 use node_id::NodeId;
 use node_type::NodeType;
@@ -116,7 +120,16 @@ pub struct DomEncodeContext {
     node_types: Vec<NodeType>,
 
     /// The names of the current stack of HTML elements
-    elements: Vec<String>,
+    ///
+    /// Uses `SmolStr` to avoid allocating for the many small, mostly
+    /// repeated, element names pushed during encoding.
+    elements: Vec<SmolStr>,
+
+    /// Custom element names memoized by node type
+    ///
+    /// Avoids repeatedly generating the kebab-cased element name for
+    /// each node of the same type in large documents.
+    node_elems: HashMap<NodeType, SmolStr>,
 
     /// The levels and ids of the current stack of `Heading` nodes
     headings: Vec<(i64, NodeId)>,
@@ -142,6 +155,14 @@ pub struct DomEncodeContext {
     /// The maximum number of rows of a datatable to encode
     #[default = 1000]
     pub max_datatable_rows: usize,
+
+    /// The number of rows in each `<tbody>` page of an encoded datatable
+    ///
+    /// Rows are encoded in pages of this size, with pages after the first
+    /// `hidden`, so that large datatables paginate rather than rendering
+    /// all rows on first paint.
+    #[default = 100]
+    pub datatable_page_rows: usize,
 }
 
 impl DomEncodeContext {
@@ -160,7 +181,7 @@ impl DomEncodeContext {
         self.content.push_str(name);
         self.content.push('>');
 
-        self.elements.push(name.to_string());
+        self.elements.push(name.to_smolstr());
 
         self
     }
@@ -183,7 +204,7 @@ impl DomEncodeContext {
         }
         self.content.push('>');
 
-        self.elements.push(name.to_string());
+        self.elements.push(name.to_smolstr());
 
         self
     }
@@ -200,7 +221,7 @@ impl DomEncodeContext {
         let ancestors = self
             .node_types
             .iter()
-            .map(|node_type| node_type.to_string())
+            .map(|node_type| <&'static str>::from(node_type))
             .join(".");
 
         self.enter_elem_attrs(
@@ -214,7 +235,13 @@ impl DomEncodeContext {
 
     /// Enter a node with the default, custom element for the node type
     pub fn enter_node(&mut self, node_type: NodeType, node_id: NodeId) -> &mut Self {
-        let name = ["stencila-", &node_type.to_string().to_kebab_case()].concat();
+        let name = self
+            .node_elems
+            .entry(node_type)
+            .or_insert_with(|| {
+                SmolStr::new(["stencila-", &node_type.to_string().to_kebab_case()].concat())
+            })
+            .clone();
         self.enter_node_elem(&name, node_type, node_id)
     }
 
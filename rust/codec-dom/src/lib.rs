@@ -1,3 +1,8 @@
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
 use lightningcss::stylesheet::{ParserOptions, PrinterOptions, StyleSheet};
 
 use codec::{
@@ -64,7 +69,7 @@ impl Codec for DomCodec {
         let dest_path = options.as_ref().and_then(|options| options.to_path.clone());
 
         // Encode to DOM HTML
-        let mut context = DomEncodeContext::new(standalone, source_path, dest_path);
+        let mut context = DomEncodeContext::new(standalone, source_path.clone(), dest_path);
         node.to_dom(&mut context);
 
         // Add the root attribute to the root node (the first opening tag)
@@ -138,6 +143,8 @@ impl Codec for DomCodec {
                 })
                 .unwrap_or_default();
 
+            let theme_css = theme_css(theme, source_path.as_deref());
+
             let alternates = options
                 .as_ref()
                 .and_then(|options| options.alternates.clone())
@@ -164,7 +171,7 @@ impl Codec for DomCodec {
     <link rel="icon" type="image/png" href="/~static/images/favicon.png" />
     <link rel="preconnect" href="https://fonts.googleapis.com" />
     <link href="https://fonts.googleapis.com/css2?family=IBM+Plex+Mono:ital,wght@0,100;0,200;0,300;0,400;0,500;0,600;0,700;1,100;1,200;1,300;1,400;1,500;1,600;1,700&family=Inter:ital,opsz,wght@0,14..32,100..900;1,14..32,100..900&display=swap" rel="stylesheet" />
-    <link rel="stylesheet" type="text/css" href="/~static/themes/{theme}.css" />
+    {theme_css}
     <link rel="stylesheet" type="text/css" href="/~static/views/dynamic.css" />
     <script type="module" src="/~static/views/dynamic.js"></script>
   </head>
@@ -188,6 +195,32 @@ impl Codec for DomCodec {
     }
 }
 
+/// Generate the `<head>` element for the theme
+///
+/// If the theme resolves to a local CSS file — either the path of the file
+/// itself, or the name of a file in a `themes` directory beside the document —
+/// its CSS is embedded so that the encoded document does not rely on the file
+/// being served. Otherwise, the theme is assumed to be one of the built-in
+/// themes (e.g. `default`, `stencila`, `latex`, `tufte`) and a stylesheet
+/// link to it is generated.
+fn theme_css(theme: &str, source_path: Option<&Path>) -> String {
+    let mut candidates = vec![PathBuf::from(theme)];
+    if let Some(dir) = source_path.and_then(Path::parent) {
+        candidates.push(dir.join(theme));
+        candidates.push(dir.join("themes").join(theme).with_extension("css"));
+    }
+
+    for candidate in candidates {
+        if candidate.extension().is_some_and(|ext| ext == "css") && candidate.is_file() {
+            if let Ok(css) = read_to_string(&candidate) {
+                return ["<style>", &normalize_css(&css), "</style>"].concat();
+            }
+        }
+    }
+
+    format!(r#"<link rel="stylesheet" type="text/css" href="/~static/themes/{theme}.css" />"#)
+}
+
 /// Indent HTML
 ///
 /// Originally based on https://gist.github.com/lwilli/14fb3178bd9adac3a64edfbc11f42e0d
@@ -0,0 +1,143 @@
+use codec::{
+    common::{
+        async_trait::async_trait,
+        eyre::{bail, Result},
+        serde_json::{self, Value},
+    },
+    format::Format,
+    schema::{Datatable, DatatableColumn, Node, Primitive},
+    status::Status,
+    Codec, CodecSupport, DecodeInfo, DecodeOptions, NodeType,
+};
+
+/// A codec for GeoJSON
+///
+/// Decodes a GeoJSON `FeatureCollection` (or single `Feature` or geometry)
+/// to a [`Datatable`] with a row for each feature: a column for each
+/// feature property, plus `geometry type` and `geometry` columns, so that
+/// spatial data can be rendered and summarized like any other tabular
+/// output (e.g. as an interactive map by the DOM codec).
+pub struct GeoJsonCodec;
+
+#[async_trait]
+impl Codec for GeoJsonCodec {
+    fn name(&self) -> &str {
+        "geojson"
+    }
+
+    fn status(&self) -> Status {
+        Status::UnderDevelopment
+    }
+
+    fn supports_from_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::GeoJson => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_to_format(&self, _format: &Format) -> CodecSupport {
+        CodecSupport::None
+    }
+
+    fn supports_from_type(&self, node_type: NodeType) -> CodecSupport {
+        match node_type {
+            NodeType::Datatable => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_to_type(&self, _node_type: NodeType) -> CodecSupport {
+        CodecSupport::None
+    }
+
+    async fn from_str(
+        &self,
+        input: &str,
+        _options: Option<DecodeOptions>,
+    ) -> Result<(Node, DecodeInfo)> {
+        let value: Value = serde_json::from_str(input)?;
+
+        let features = match value.get("type").and_then(Value::as_str) {
+            Some("FeatureCollection") => value
+                .get("features")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default(),
+            Some("Feature") => vec![value],
+            Some(..) => vec![serde_json::json!({
+                "type": "Feature",
+                "properties": {},
+                "geometry": value
+            })],
+            None => bail!("Input does not appear to be GeoJSON: missing `type` property"),
+        };
+
+        // Collect the union of property names across all features, in order
+        // of first appearance
+        let mut names: Vec<String> = Vec::new();
+        for feature in &features {
+            if let Some(properties) = feature.get("properties").and_then(Value::as_object) {
+                for name in properties.keys() {
+                    if !names.contains(name) {
+                        names.push(name.clone());
+                    }
+                }
+            }
+        }
+
+        let mut columns: Vec<DatatableColumn> = names
+            .iter()
+            .map(|name| {
+                let values = features
+                    .iter()
+                    .map(|feature| {
+                        feature
+                            .get("properties")
+                            .and_then(|properties| properties.get(name))
+                            .map(primitive_from_value)
+                            .unwrap_or_default()
+                    })
+                    .collect();
+                DatatableColumn::new(name.clone(), values)
+            })
+            .collect();
+
+        columns.push(DatatableColumn::new(
+            "geometry type".to_string(),
+            features
+                .iter()
+                .map(|feature| {
+                    feature
+                        .get("geometry")
+                        .and_then(|geometry| geometry.get("type"))
+                        .map(primitive_from_value)
+                        .unwrap_or_default()
+                })
+                .collect(),
+        ));
+
+        columns.push(DatatableColumn::new(
+            "geometry".to_string(),
+            features
+                .iter()
+                .map(|feature| {
+                    feature
+                        .get("geometry")
+                        .map(primitive_from_value)
+                        .unwrap_or_default()
+                })
+                .collect(),
+        ));
+
+        Ok((
+            Node::Datatable(Datatable::new(columns)),
+            DecodeInfo::none(),
+        ))
+    }
+}
+
+/// Convert a JSON [`Value`] to a [`Primitive`]
+fn primitive_from_value(value: &Value) -> Primitive {
+    serde_json::from_value(value.clone()).unwrap_or_default()
+}
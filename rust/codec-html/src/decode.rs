@@ -2,7 +2,8 @@ use codec::{
     common::eyre::{bail, Result},
     schema::{
         shortcuts::{em, h1, h2, h3, h4, h5, h6, p, sec, stg, stk, sub, sup, t, u},
-        Article, Block, ImageObject, Inline, Link, Node, StyledBlock, StyledInline,
+        Article, Block, ImageObject, Inline, Link, Node, StyledBlock, StyledInline, Table,
+        TableCell, TableCellOptions, TableCellType, TableRow, TableRowType,
     },
     DecodeInfo, DecodeOptions,
 };
@@ -56,6 +57,7 @@ fn decode_blocks(parser: &Parser, nodes: &RawChildren) -> Vec<Block> {
                 "div" => decode_div(parser, tag),
                 "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => decode_h(parser, tag, &name),
                 "p" => decode_p(parser, tag),
+                "table" => decode_table(parser, tag),
                 // Inlines where block is expected
                 "a" => p([decode_a(parser, tag)]),
                 "img" => p([decode_img(parser, tag)]),
@@ -123,6 +125,135 @@ fn decode_p(parser: &Parser, tag: &HTMLTag) -> Block {
     p(decode_inlines(parser, tag.children().top()))
 }
 
+/// Decode a <table> element into a [`Table`]
+///
+/// Handles <thead>, <tbody> and <tfoot> sections (setting the row type of
+/// their rows), <th> versus <td> cells, `rowspan`/`colspan` attributes,
+/// and a <caption> if present.
+fn decode_table(parser: &Parser, tag: &HTMLTag) -> Block {
+    let mut caption = None;
+    let mut rows = Vec::new();
+
+    for child in tag
+        .children()
+        .top()
+        .iter()
+        .flat_map(|handle| handle.get(parser))
+    {
+        let Some(tag) = child.as_tag() else { continue };
+        match tag.name().as_utf8_str().as_ref() {
+            "caption" => caption = Some(decode_blocks(parser, tag.children().top())),
+            "thead" => decode_table_rows(parser, tag, Some(TableRowType::HeaderRow), &mut rows),
+            "tbody" => decode_table_rows(parser, tag, None, &mut rows),
+            "tfoot" => decode_table_rows(parser, tag, Some(TableRowType::FooterRow), &mut rows),
+            "tr" => rows.push(decode_table_row(parser, tag, None)),
+            _ => continue,
+        }
+    }
+
+    Block::Table(Table {
+        caption,
+        rows,
+        ..Default::default()
+    })
+}
+
+/// Decode the <tr> elements of a table section into [`TableRow`]s
+fn decode_table_rows(
+    parser: &Parser,
+    tag: &HTMLTag,
+    row_type: Option<TableRowType>,
+    rows: &mut Vec<TableRow>,
+) {
+    for child in tag
+        .children()
+        .top()
+        .iter()
+        .flat_map(|handle| handle.get(parser))
+    {
+        if let Some(tag) = child.as_tag() {
+            if tag.name().as_utf8_str() == "tr" {
+                rows.push(decode_table_row(parser, tag, row_type.clone()));
+            }
+        }
+    }
+}
+
+/// Decode a <tr> element into a [`TableRow`]
+fn decode_table_row(parser: &Parser, tag: &HTMLTag, row_type: Option<TableRowType>) -> TableRow {
+    let mut cells = Vec::new();
+
+    for child in tag
+        .children()
+        .top()
+        .iter()
+        .flat_map(|handle| handle.get(parser))
+    {
+        if let Some(tag) = child.as_tag() {
+            let name = tag.name().as_utf8_str();
+            if name == "td" || name == "th" {
+                cells.push(decode_table_cell(parser, tag, name == "th"));
+            }
+        }
+    }
+
+    TableRow {
+        cells,
+        row_type,
+        ..Default::default()
+    }
+}
+
+/// Decode a <td> or <th> element into a [`TableCell`]
+fn decode_table_cell(parser: &Parser, tag: &HTMLTag, header: bool) -> TableCell {
+    let attrs = tag.attributes();
+
+    let span = |name| {
+        attrs
+            .get(name)
+            .flatten()
+            .and_then(|bytes| bytes.as_utf8_str().parse().ok())
+            .filter(|span| *span != 1)
+    };
+    let column_span = span("colspan");
+    let row_span = span("rowspan");
+
+    let mut content = decode_blocks(parser, tag.children().top());
+
+    // There is no alignment property in the schema so represent any `align`
+    // attribute, or `style`, by wrapping the content in a `StyledBlock`
+    // (as is done for <div> elements)
+    let style = attrs
+        .get("align")
+        .flatten()
+        .map(|align| format!("text-align: {}", align.as_utf8_str()))
+        .or_else(|| {
+            attrs
+                .get("style")
+                .flatten()
+                .map(|style| style.as_utf8_str().to_string())
+        });
+    if let Some(style) = style {
+        content = vec![Block::StyledBlock(StyledBlock {
+            code: style.into(),
+            style_language: Some("css".to_string()),
+            content,
+            ..Default::default()
+        })];
+    }
+
+    TableCell {
+        cell_type: header.then_some(TableCellType::HeaderCell),
+        content,
+        options: Box::new(TableCellOptions {
+            column_span,
+            row_span,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
 /// Decode inline elements
 fn decode_inlines(parser: &Parser, nodes: &RawChildren) -> Vec<Inline> {
     let mut inlines = Vec::new();
@@ -5,7 +5,8 @@ use codec::{
         shortcuts::{em, mi, p, qb, qi, stg, stk, sub, sup, t, u},
         Admonition, Article, AudioObject, AudioObjectOptions, Block, CodeExpression, CodeInline,
         Cord, Date, DateTime, Duration, Heading, ImageObject, ImageObjectOptions, Inline, Link,
-        MediaObject, MediaObjectOptions, Note, NoteType, Parameter, Section, StyledInline,
+        MediaObject, MediaObjectOptions, Note, NoteType, Parameter, Section, SectionType,
+        StyledInline,
         ThematicBreak, Time, Timestamp, VideoObject, VideoObjectOptions,
     },
     Losses,
@@ -44,6 +45,9 @@ fn decode_blocks<'a, 'input: 'a, I: Iterator<Item = Node<'a, 'input>>>(
             "p" => decode_p(&child_path, &child, losses),
             "disp-quote" => decode_disp_quote(&child_path, &child, losses, depth),
             "sec" => decode_sec(&child_path, &child, losses, depth + 1),
+            "supplementary-material" => {
+                decode_supplementary_material(&child_path, &child, losses, depth)
+            }
             "title" => decode_title(&child_path, &child, losses, depth),
             _ => {
                 record_node_lost(path, &child, losses);
@@ -128,6 +132,66 @@ fn decode_sec(path: &str, node: &Node, losses: &mut Losses, depth: u8) -> Block
     })
 }
 
+/// Decode a `<supplementary-material>` to a [`Block::Section`] with type
+/// `SupplementaryMaterials`
+///
+/// There is no dedicated node type for supplementary material so the label
+/// and caption are decoded to content blocks, and linked files (via the
+/// `xlink:href` of the element itself, or of child `<media>` or `<graphic>`
+/// elements) to paragraphs containing a link to the file. Using a section of
+/// this type means that citations within it are counted and numbered
+/// separately when so configured.
+fn decode_supplementary_material(path: &str, node: &Node, losses: &mut Losses, depth: u8) -> Block {
+    record_attrs_lost(path, node, ["href"], losses);
+
+    let mut content = Vec::new();
+    let mut hrefs = Vec::new();
+
+    if let Some(href) = node.attribute((XLINK, "href")) {
+        hrefs.push(href.to_string());
+    }
+
+    for child in node.children() {
+        let tag = child.tag_name().name();
+        let child_path = extend_path(path, tag);
+        match tag {
+            "label" => content.push(p([stg([t(child.text().unwrap_or_default())])])),
+            "caption" => content.append(&mut decode_blocks(
+                &child_path,
+                child.children(),
+                losses,
+                depth,
+            )),
+            "media" | "graphic" => {
+                if let Some(href) = child.attribute((XLINK, "href")) {
+                    hrefs.push(href.to_string());
+                } else {
+                    record_node_lost(path, &child, losses);
+                }
+            }
+            _ => {
+                if child.is_element() {
+                    record_node_lost(path, &child, losses);
+                }
+            }
+        }
+    }
+
+    for href in hrefs {
+        content.push(p([Inline::Link(Link {
+            content: vec![t(&href)],
+            target: href,
+            ..Default::default()
+        })]));
+    }
+
+    Block::Section(Section {
+        section_type: Some(SectionType::SupplementaryMaterials),
+        content,
+        ..Default::default()
+    })
+}
+
 /// Decode a `<title>` to a [`Block::Heading`]
 fn decode_title(path: &str, node: &Node, losses: &mut Losses, depth: u8) -> Block {
     record_attrs_lost(path, node, [], losses);
@@ -66,3 +66,30 @@ async fn spans() -> Result<()> {
 
     Ok(())
 }
+
+/// Decoding of `<supplementary-material>` to a section with linked files
+#[tokio::test]
+async fn supplementary_material() -> Result<()> {
+    use codec::schema::{Block, SectionType};
+
+    let codec = JatsCodec {};
+
+    let jats = r#"<article xmlns:xlink="http://www.w3.org/1999/xlink"><body><supplementary-material xlink:href="supplement-1.pdf"><label>Supplement 1</label><caption><p>Supplementary methods.</p></caption><media xlink:href="data.csv"/></supplementary-material></body></article>"#;
+
+    let (node, ..) = codec.from_str(jats, None).await?;
+    let Node::Article(article) = node else {
+        unreachable!()
+    };
+    let Some(Block::Section(section)) = article.content.first() else {
+        unreachable!()
+    };
+
+    assert_eq!(
+        section.section_type,
+        Some(SectionType::SupplementaryMaterials)
+    );
+    // Label, caption paragraph, and a link paragraph for each of the two files
+    assert_eq!(section.content.len(), 4);
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use std::{
     fs::{self, File},
-    io::{Cursor, Read, Write},
+    io::{self, BufWriter, Cursor, Read, Write},
     path::Path,
 };
 
@@ -8,7 +8,7 @@ use codec::{
     common::{
         async_trait::async_trait,
         eyre::{bail, Result},
-        serde_json::{Map, Value},
+        serde_json::{self, Map, Value},
         zip::{self, write::FileOptions, ZipArchive},
     },
     format::Format,
@@ -159,17 +159,18 @@ pub fn to_path(node: &Node, path: &Path, options: Option<EncodeOptions>) -> Resu
     // Implement `to_path, rather than `to_bytes`, so that, if encoding to `json.zip`,
     // the single file in the Zip archive can have the name minus `.zip`
 
-    let mut options = options.unwrap_or_default();
-    options.standalone = Some(true);
-    let options = Some(options);
-
-    let (string, ..) = to_string(node, options.clone())?;
+    let options = options.unwrap_or_default();
+    let compact = options.compact;
+    let format = options.format.clone();
 
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
 
-    if let Some(Format::JsonZip) = options.and_then(|options| options.format) {
+    // Stream the node to the file, rather than building an intermediate
+    // value or string, so that memory stays bounded when encoding large
+    // documents (e.g. those containing large `Datatable`s)
+    if let Some(Format::JsonZip) = format {
         let zip_file = File::create(path)?;
         let mut zip = zip::ZipWriter::new(&zip_file);
 
@@ -182,15 +183,92 @@ pub fn to_path(node: &Node, path: &Path, options: Option<EncodeOptions>) -> Resu
 
         let options = FileOptions::default().unix_permissions(0o755);
         zip.start_file(filename, options)?;
-        zip.write_all(string.as_bytes())?;
+        write_node(&mut zip, node, compact)?;
         zip.finish()?;
     } else {
-        fs::write(path, string)?;
+        let mut file = BufWriter::new(File::create(path)?);
+        write_node(&mut file, node, compact)?;
+        file.flush()?;
     };
 
     Ok(EncodeInfo::none())
 }
 
+/**
+ * Write a node to a writer as standalone JSON, optionally compactly
+ *
+ * Serializes the node directly to the writer so that the whole document
+ * never needs to be buffered in memory. The standalone `$schema` and
+ * `@context` properties are injected after the opening brace of the root
+ * object by the [`StandaloneWriter`].
+ */
+fn write_node<W: Write>(writer: W, node: &Node, compact: Option<bool>) -> Result<()> {
+    let prefix = match node {
+        // Primitives do not serialize to an object with a `type` so get no
+        // standalone properties
+        Node::Null(..)
+        | Node::Boolean(..)
+        | Node::Integer(..)
+        | Node::UnsignedInteger(..)
+        | Node::Number(..)
+        | Node::String(..)
+        | Node::Cord(..)
+        | Node::Array(..)
+        | Node::Object(..) => None,
+        _ => {
+            let r#type = node.node_type();
+            Some(match compact {
+                Some(true) => format!(
+                    "\"$schema\":\"https://stencila.org/v{STENCILA_VERSION}/{type}.schema.json\",\"@context\":\"https://stencila.org/v{STENCILA_VERSION}/context.jsonld\","
+                ),
+                Some(false) | None => format!(
+                    "\n  \"$schema\": \"https://stencila.org/v{STENCILA_VERSION}/{type}.schema.json\",\n  \"@context\": \"https://stencila.org/v{STENCILA_VERSION}/context.jsonld\","
+                ),
+            })
+        }
+    };
+    let mut writer = StandaloneWriter {
+        inner: writer,
+        prefix,
+    };
+
+    match compact {
+        Some(true) => serde_json::to_writer(&mut writer, node)?,
+        Some(false) | None => serde_json::to_writer_pretty(&mut writer, node)?,
+    }
+
+    Ok(())
+}
+
+/**
+ * A writer that injects standalone properties after the opening brace of
+ * the root JSON object
+ *
+ * If the root of the JSON is not an object (e.g. the node is a primitive)
+ * then nothing is injected.
+ */
+struct StandaloneWriter<W: Write> {
+    inner: W,
+    prefix: Option<String>,
+}
+
+impl<W: Write> Write for StandaloneWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(prefix) = self.prefix.take() {
+            if buf.first() == Some(&b'{') {
+                self.inner.write_all(b"{")?;
+                self.inner.write_all(prefix.as_bytes())?;
+                return Ok(1);
+            }
+        }
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
 /**
  * Encode a node to a JSON string
  */
@@ -199,7 +277,7 @@ pub fn to_string(node: &Node, options: Option<EncodeOptions>) -> Result<(String,
         standalone,
         compact,
         ..
-    } = options.unwrap_or_default();
+    } = options.clone().unwrap_or_default();
 
     if !standalone.unwrap_or_default() {
         return Ok((
@@ -211,6 +289,23 @@ pub fn to_string(node: &Node, options: Option<EncodeOptions>) -> Result<(String,
         ));
     }
 
+    let value = to_value(node, options)?;
+
+    Ok((
+        match compact {
+            Some(true) => value.to_json(),
+            Some(false) | None => value.to_json_pretty(),
+        }?,
+        EncodeInfo::none(),
+    ))
+}
+
+/**
+ * Encode a node to a JSON value
+ */
+pub fn to_value(node: &Node, options: Option<EncodeOptions>) -> Result<Value> {
+    let EncodeOptions { standalone, .. } = options.unwrap_or_default();
+
     let value = node.to_json_value()?;
 
     let value = if let (Some(true), Some(r#type)) = (
@@ -246,11 +341,5 @@ pub fn to_string(node: &Node, options: Option<EncodeOptions>) -> Result<(String,
         value
     };
 
-    Ok((
-        match compact {
-            Some(true) => value.to_json(),
-            Some(false) | None => value.to_json_pretty(),
-        }?,
-        EncodeInfo::none(),
-    ))
+    Ok(value)
 }
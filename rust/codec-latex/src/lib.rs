@@ -1,13 +1,21 @@
 use codec::{
-    common::{async_trait::async_trait, eyre::Result},
+    common::{async_trait::async_trait, eyre::Result, tokio::fs::read_to_string},
     format::Format,
     schema::Node,
     status::Status,
     Codec, CodecSupport, DecodeInfo, DecodeOptions, EncodeInfo, EncodeOptions, NodeType,
 };
-use codec_pandoc::{pandoc_from_format, pandoc_to_format, root_from_pandoc, root_to_pandoc};
+use codec_pandoc::{
+    pandoc_from_format, pandoc_to_format, root_from_pandoc, root_to_pandoc, root_to_pandoc_render,
+};
 
 /// A codec for LaTeX
+///
+/// In addition to ordinary LaTeX documents, can encode to Beamer slides
+/// (use the `Beamer` format in `EncodeOptions`): sections become frames and
+/// the outputs of code chunks, rather than their code, are encoded (as
+/// figures for image outputs) so that talks can be generated from
+/// executable documents.
 pub struct LatexCodec;
 
 const PANDOC_FORMAT: &str = "latex";
@@ -32,6 +40,7 @@ impl Codec for LatexCodec {
     fn supports_to_format(&self, format: &Format) -> CodecSupport {
         match format {
             Format::Latex => CodecSupport::LowLoss,
+            Format::Beamer => CodecSupport::HighLoss,
             _ => CodecSupport::None,
         }
     }
@@ -49,15 +58,18 @@ impl Codec for LatexCodec {
         input: &str,
         options: Option<DecodeOptions>,
     ) -> Result<(Node, DecodeInfo)> {
-        let pandoc = pandoc_from_format(
-            input,
-            None,
-            PANDOC_FORMAT,
-            options
-                .map(|options| options.passthrough_args)
-                .unwrap_or_default(),
-        )
-        .await?;
+        let options = options.unwrap_or_default();
+
+        // Prepend any macro definitions (e.g. a preamble with `\newcommand`s)
+        // so that custom commands are expanded by the reader, rather than
+        // being left as raw fragments
+        let input = match &options.macros {
+            Some(path) => [read_to_string(path).await?.as_str(), "\n", input].concat(),
+            None => input.to_string(),
+        };
+
+        let pandoc =
+            pandoc_from_format(&input, None, PANDOC_FORMAT, options.passthrough_args).await?;
         root_from_pandoc(pandoc)
     }
 
@@ -66,14 +78,20 @@ impl Codec for LatexCodec {
         node: &Node,
         options: Option<EncodeOptions>,
     ) -> Result<(String, EncodeInfo)> {
-        let (pandoc, info) = root_to_pandoc(node)?;
+        let options = options.unwrap_or_default();
+        let beamer = matches!(options.format, Some(Format::Beamer));
+
+        let (pandoc, info) = if beamer {
+            root_to_pandoc_render(node)?
+        } else {
+            root_to_pandoc(node)?
+        };
+
         let output = pandoc_to_format(
             &pandoc,
             None,
-            PANDOC_FORMAT,
-            options
-                .map(|options| options.passthrough_args)
-                .unwrap_or_default(),
+            if beamer { "beamer" } else { PANDOC_FORMAT },
+            options.passthrough_args,
         )
         .await?;
         Ok((output, info))
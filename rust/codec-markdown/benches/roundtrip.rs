@@ -0,0 +1,35 @@
+use codec::{common::tokio::runtime::Runtime, Codec};
+use codec_markdown::MarkdownCodec;
+use common_dev::{
+    bench::large_article,
+    criterion::{criterion_group, criterion_main, Criterion},
+};
+
+/// Benchmark encoding and decoding of a large article
+fn roundtrip(criterion: &mut Criterion) {
+    let runtime = Runtime::new().expect("unable to create runtime");
+
+    let node = large_article(100);
+    let (markdown, ..) = runtime
+        .block_on(MarkdownCodec {}.to_string(&node, None))
+        .expect("unable to encode");
+
+    criterion.bench_function("encode", |bencher| {
+        bencher.iter(|| {
+            runtime
+                .block_on(MarkdownCodec {}.to_string(&node, None))
+                .expect("unable to encode")
+        })
+    });
+
+    criterion.bench_function("decode", |bencher| {
+        bencher.iter(|| {
+            runtime
+                .block_on(MarkdownCodec {}.from_str(&markdown, None))
+                .expect("unable to decode")
+        })
+    });
+}
+
+criterion_group!(benches, roundtrip);
+criterion_main!(benches);
@@ -1567,6 +1567,33 @@ fn myst_to_block(code: &mdast::Code, context: &mut Context) -> Option<Block> {
             content: decode_blocks(&value, context),
             ..Default::default()
         }),
+        "grid" | "card" | "tab-set" | "tab-item" | "glossary" | "margin" => {
+            // Layout directives are decoded to styled blocks with the
+            // directive name, arguments and options as the code so that
+            // they can be encoded back to the original directive
+            let mut code = name.to_string();
+            if let Some(args) = args {
+                code.push(' ');
+                code.push_str(args);
+            }
+            let mut pairs: Vec<(&&str, &&str)> = options.iter().collect();
+            pairs.sort();
+            for (key, value) in pairs {
+                code.push_str(" :");
+                code.push_str(key);
+                code.push(':');
+                if !value.is_empty() {
+                    code.push(' ');
+                    code.push_str(value);
+                }
+            }
+
+            Block::StyledBlock(StyledBlock {
+                code: code.into(),
+                content: decode_blocks(&value, context),
+                ..Default::default()
+            })
+        }
         _ => {
             // Fallback to code block that will preserve
             let mut lang = lang.to_string();
@@ -1599,6 +1626,15 @@ fn code_to_block(code: mdast::Code, context: &mut Context) -> Block {
             .unwrap_or_default();
     let is_raw = meta.starts_with("raw") || lang.as_deref() == Some("raw");
 
+    if matches!(context.format, Format::Rmd)
+        && lang
+            .as_ref()
+            .map(|lang| lang.starts_with('{'))
+            .unwrap_or_default()
+    {
+        return rmd_chunk_to_block(lang.unwrap_or_default(), meta, value, context);
+    }
+
     if is_exec {
         let lang = lang.and_then(|lang| {
             let lang = lang
@@ -1693,6 +1729,142 @@ fn code_to_block(code: mdast::Code, context: &mut Context) -> Block {
     }
 }
 
+/// Decode an R Markdown code chunk to a [`Block::CodeChunk`]
+///
+/// Parses the knitr chunk header (e.g. `{r label, echo=FALSE, fig.cap="A caption"}`)
+/// for the engine, label, and chunk options. Options with an equivalent
+/// [`CodeChunk`] property (`echo`, `include`, `eval`, `fig.cap`, `label`) are
+/// decoded onto it; others (e.g. `fig.width`, `warning`, `cache`) are
+/// recorded as losses.
+fn rmd_chunk_to_block(lang: String, meta: String, code: String, context: &mut Context) -> Block {
+    let header = [lang.as_str(), meta.as_str()].join(" ");
+    let header = header
+        .trim()
+        .trim_start_matches('{')
+        .trim_end_matches('}')
+        .trim();
+
+    let mut programming_language = None;
+    let mut execution_mode = None;
+    let mut is_invisible = None;
+    let mut label_automatically = None;
+    let mut label = None;
+    let mut label_type = None;
+    let mut caption = None;
+
+    for (index, part) in split_chunk_options(header).into_iter().enumerate() {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+
+        if index == 0 && !part.contains('=') {
+            // First item is the engine, optionally followed by a label
+            let mut words = part.split_whitespace();
+            if let Some(engine) = words.next() {
+                programming_language = Some(engine.to_string());
+            }
+            if let Some(word) = words.next() {
+                label_automatically = Some(false);
+                label = Some(word.to_string());
+            }
+            continue;
+        }
+
+        let Some((name, value)) = part.split_once('=') else {
+            // A bare item after the engine is a label
+            if label.is_none() {
+                label_automatically = Some(false);
+                label = Some(part.to_string());
+            } else {
+                context.lost(&["CodeChunk.", part].concat());
+            }
+            continue;
+        };
+        let name = name.trim();
+        let value = unquote_chunk_option(value);
+
+        match name {
+            "echo" | "include" => {
+                if value.eq_ignore_ascii_case("false") {
+                    is_invisible = Some(true);
+                } else {
+                    context.lost(&["CodeChunk.", name].concat());
+                }
+            }
+            "eval" => {
+                if value.eq_ignore_ascii_case("false") {
+                    execution_mode = Some(ExecutionMode::Locked);
+                } else {
+                    context.lost(&["CodeChunk.", name].concat());
+                }
+            }
+            "fig.cap" => {
+                label_type = Some(LabelType::FigureLabel);
+                caption = Some(decode_blocks(value, context));
+            }
+            "label" => {
+                label_automatically = Some(false);
+                label = Some(value.to_string());
+            }
+            _ => {
+                context.lost(&["CodeChunk.", name].concat());
+            }
+        }
+    }
+
+    Block::CodeChunk(CodeChunk {
+        code: code.into(),
+        programming_language,
+        execution_mode,
+        is_invisible,
+        label_automatically,
+        label_type,
+        label,
+        caption,
+        ..Default::default()
+    })
+}
+
+/// Split a knitr chunk header into comma separated options, respecting
+/// quotes and parentheses (e.g. in `fig.cap="A, B"` or `fig.dim=c(5, 3)`)
+fn split_chunk_options(header: &str) -> Vec<&str> {
+    let mut options = Vec::new();
+    let mut start = 0;
+    let mut quote = None;
+    let mut depth = 0usize;
+    for (index, char) in header.char_indices() {
+        match (quote, char) {
+            (Some(current), ..) if current == char => quote = None,
+            (Some(..), ..) => {}
+            (None, '"' | '\'') => quote = Some(char),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                options.push(&header[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    options.push(&header[start..]);
+    options
+}
+
+/// Trim whitespace and any surrounding quotes from a knitr chunk option value
+fn unquote_chunk_option(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .or_else(|| {
+            value
+                .strip_prefix('\'')
+                .and_then(|value| value.strip_suffix('\''))
+        })
+        .unwrap_or(value)
+}
+
 fn mds_to_quote_block_or_admonition(mds: Vec<mdast::Node>, context: &mut Context) -> Block {
     let mut content = mds_to_blocks(mds, context);
 
@@ -1730,7 +1902,14 @@ fn mds_to_quote_block_or_admonition(mds: Vec<mdast::Node>, context: &mut Context
         .parse_peek(first_string.as_str());
 
     if let Ok((rest, (admonition_type, fold, title, ..))) = parsed {
-        if let Ok(admonition_type) = admonition_type.parse::<AdmonitionType>() {
+        // GitHub's `CAUTION` alert has no direct equivalent so is mapped to
+        // its closest, `Danger` (which encodes back to `CAUTION`)
+        let admonition_type = admonition_type.parse::<AdmonitionType>().ok().or_else(|| {
+            admonition_type
+                .eq_ignore_ascii_case("caution")
+                .then_some(AdmonitionType::Danger)
+        });
+        if let Some(admonition_type) = admonition_type {
             let is_folded = fold.and_then(|symbol| match symbol {
                 "-" => Some(false),
                 "+" => Some(true),
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, ops::Range};
+use std::{collections::HashMap, ops::Range, path::Path};
 
 use markdown::{mdast, unist::Position};
 use winnow::{
@@ -328,6 +328,7 @@ pub(super) fn inlines(input: &str) -> Vec<(Inline, Range<usize>)> {
             // Nested in another alt to avoid going over max size of tuple
             alt((insert_inline, delete_inline, replace_inline)),
             alt((edit_with, edit_end)),
+            wikilink,
             string,
             character,
         ))
@@ -909,12 +910,87 @@ fn edit_end(input: &mut Located<&str>) -> PResult<Inline> {
         .parse_next(input)
 }
 
+/// Parse an Obsidian-style wikilink into a `Link`, or an embed into a media object
+///
+/// Handles `[[Page Name]]`, `[[page#Heading|Alias]]` and media embeds such as
+/// `![[figure.png]]`. Targets without an extension are resolved to sibling
+/// Markdown documents (`.md` is appended) so that links continue to work when
+/// a vault is published as a site. Embeds of notes, rather than media, decode
+/// to a link to the note.
+///
+/// Note that the edit parsers above also use double square brackets but all
+/// require a keyword (e.g. `[[insert `) so are tried first.
+fn wikilink(input: &mut Located<&str>) -> PResult<Inline> {
+    (
+        opt('!'),
+        delimited(
+            "[[",
+            take_while(1.., |chr: char| chr != ']' && chr != '\n'),
+            "]]",
+        ),
+    )
+        .map(|(embed, inner): (Option<char>, &str)| {
+            let (target, label) = match inner.split_once('|') {
+                Some((target, label)) => (target.trim(), Some(label.trim())),
+                None => (inner.trim(), None),
+            };
+
+            let (page, heading) = match target.split_once('#') {
+                Some((page, heading)) => (page, Some(heading)),
+                None => (target, None),
+            };
+
+            let extension = Path::new(page).extension().and_then(|ext| ext.to_str());
+
+            let mut url = if page.is_empty() || extension.is_some() {
+                page.to_string()
+            } else {
+                [page, ".md"].concat()
+            };
+            if let Some(heading) = heading {
+                url.push('#');
+                url.push_str(&heading.replace(' ', "-").to_lowercase());
+            }
+
+            if embed.is_some() {
+                match extension.map(|ext| ext.to_lowercase()).as_deref() {
+                    Some("png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "bmp") => {
+                        return Inline::ImageObject(ImageObject {
+                            content_url: url,
+                            ..Default::default()
+                        })
+                    }
+                    Some("mp3" | "wav" | "ogg" | "flac" | "m4a") => {
+                        return Inline::AudioObject(AudioObject {
+                            content_url: url,
+                            ..Default::default()
+                        })
+                    }
+                    Some("mp4" | "webm" | "mov" | "mkv") => {
+                        return Inline::VideoObject(VideoObject {
+                            content_url: url,
+                            ..Default::default()
+                        })
+                    }
+                    _ => {}
+                }
+            }
+
+            Inline::Link(Link {
+                target: url,
+                content: vec![Inline::Text(Text::from(label.unwrap_or(target)))],
+                ..Default::default()
+            })
+        })
+        .parse_next(input)
+}
+
 /// Accumulate characters into a `Text` node
 ///
 /// Will greedily take as many characters as possible, excluding those that appear at the
-/// start of other inline parsers e.g. '$', '['
+/// start of other inline parsers e.g. '$', '[', and '!' which may start an embed
 fn string(input: &mut Located<&str>) -> PResult<Inline> {
-    const CHARS: &str = "~@#$^&[]{`<>";
+    const CHARS: &str = "~@#$^&[]{`<>!";
     take_while(1.., |chr: char| !CHARS.contains(chr))
         .map(|val: &str| Inline::Text(Text::new(val.into())))
         .parse_next(input)
@@ -1026,6 +1102,49 @@ mod tests {
         assert!(matches!(inlines[1].0, Inline::Underline(..)));
     }
 
+    #[test]
+    fn test_wikilink() {
+        assert_eq!(
+            wikilink(&mut Located::new("[[Page Name]]")).unwrap(),
+            Inline::Link(Link {
+                target: "Page Name.md".into(),
+                content: vec![Inline::Text(Text::from("Page Name"))],
+                ..Default::default()
+            })
+        );
+
+        assert_eq!(
+            wikilink(&mut Located::new("[[page#My Heading|Alias]]")).unwrap(),
+            Inline::Link(Link {
+                target: "page.md#my-heading".into(),
+                content: vec![Inline::Text(Text::from("Alias"))],
+                ..Default::default()
+            })
+        );
+
+        assert_eq!(
+            wikilink(&mut Located::new("![[figure.png]]")).unwrap(),
+            Inline::ImageObject(ImageObject {
+                content_url: "figure.png".into(),
+                ..Default::default()
+            })
+        );
+
+        // Embedded notes decode to a link to the note
+        assert_eq!(
+            wikilink(&mut Located::new("![[Other Note]]")).unwrap(),
+            Inline::Link(Link {
+                target: "Other Note.md".into(),
+                content: vec![Inline::Text(Text::from("Other Note"))],
+                ..Default::default()
+            })
+        );
+
+        let is = inlines("before [[Page Name]] after");
+        assert_eq!(is.len(), 3);
+        assert!(matches!(is[1].0, Inline::Link(..)));
+    }
+
     #[test]
     fn test_instruction_inline() {
         instruction_inline(&mut Located::new("[[create something]]")).unwrap();
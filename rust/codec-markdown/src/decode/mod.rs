@@ -1,4 +1,4 @@
-use std::{collections::HashMap, ops::Range};
+use std::{collections::HashMap, ops::Range, str::FromStr};
 
 use markdown::{
     mdast::{self, Root},
@@ -17,7 +17,8 @@ use codec::{
     },
     format::Format,
     schema::{
-        Article, Block, Inline, Node, NodeId, NodeType, Null, Prompt, VisitorMut, WalkControl,
+        Article, Block, Heading, Inline, Node, NodeId, NodeType, Null, Person, Prompt, RawBlock,
+        Text, VisitorMut, WalkControl,
     },
     DecodeInfo, DecodeOptions, Losses, Mapping,
 };
@@ -36,6 +37,15 @@ pub fn decode(content: &str, options: Option<DecodeOptions>) -> Result<(Node, De
         .and_then(|options| options.format.clone())
         .unwrap_or(Format::Smd); // Default to Stencila Markdown
 
+    // Decode only the structure of the document if the `coarse` option is used
+    if options
+        .as_ref()
+        .and_then(|options| options.coarse)
+        .unwrap_or_default()
+    {
+        return Ok(decode_coarse(content, format));
+    }
+
     // Check the content and return early if any messages and in strict mode
     let messages = check::check(content, &format);
     if !messages.is_empty() {
@@ -92,6 +102,79 @@ pub fn decode(content: &str, options: Option<DecodeOptions>) -> Result<(Node, De
     Ok((node, info))
 }
 
+/// Decode only the coarse structure of a Markdown string
+///
+/// Decodes the YAML frontmatter and ATX headings only, collecting the content
+/// between headings into [`RawBlock`]s without parsing it. This is much faster
+/// than a full decode for large documents and intended for purposes, such as
+/// outlining and search indexing, that only need the structure and metadata
+/// of a document.
+fn decode_coarse(md: &str, format: Format) -> (Node, DecodeInfo) {
+    static HEADING_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"^(#{1,6})\s+(.+?)(?:\s+#+)?\s*$").expect("invalid regex"));
+
+    let mut context = Context::new(format.clone());
+
+    // Split off any YAML frontmatter
+    let mut rest = md;
+    if let Some(after) = md.strip_prefix("---\n").or_else(|| md.strip_prefix("---\r\n")) {
+        if let Some(end) = after.find("\n---") {
+            context.yaml = Some(after[..end].to_string());
+            rest = match after[end + 4..].split_once('\n') {
+                Some((.., after_delimiter)) => after_delimiter,
+                None => "",
+            };
+        }
+    }
+
+    let mut content = Vec::new();
+    let mut chunk = String::new();
+    let mut within_fence = false;
+    for line in rest.lines() {
+        // Track fenced blocks so that lines starting with `#` within them
+        // (e.g. comments in code) are not treated as headings
+        if line.starts_with("```") || line.starts_with("~~~") {
+            within_fence = !within_fence;
+        }
+
+        if !within_fence {
+            if let Some(captures) = HEADING_REGEX.captures(line) {
+                if !chunk.trim().is_empty() {
+                    content.push(Block::RawBlock(RawBlock::new(
+                        format.to_string(),
+                        chunk.as_str().into(),
+                    )));
+                }
+                chunk.clear();
+
+                content.push(Block::Heading(Heading::new(
+                    captures[1].len() as i64,
+                    vec![Inline::Text(Text::from(&captures[2]))],
+                )));
+                continue;
+            }
+        }
+
+        chunk.push_str(line);
+        chunk.push('\n');
+    }
+    if !chunk.trim().is_empty() {
+        content.push(Block::RawBlock(RawBlock::new(
+            format.to_string(),
+            chunk.as_str().into(),
+        )));
+    }
+
+    // Decode frontmatter metadata as in a full decode
+    let node = if let Some(Node::Article(rest)) = context.frontmatter() {
+        Node::Article(Article { content, ..rest })
+    } else {
+        Node::Article(Article::new(content))
+    };
+
+    (node, DecodeInfo::none())
+}
+
 /// Decode a Markdown string to blocks
 ///
 /// Because this is parsing a standalone fragment of Markdown, and the `to_mdast` function,
@@ -399,15 +482,54 @@ impl Context {
             }
         };
 
-        // Parse title and abstract as Markdown (need to do here before deserializing to node
-        // and remove from value so does not cause an error when deserializing)
+        // Normalize common frontmatter conventions so that deserialization
+        // succeeds and metadata is not lost
+        if let Some(object) = value.as_object_mut() {
+            // Allow the singular `author` as an alias for `authors`
+            if !object.contains_key("authors") {
+                if let Some(author) = object.remove("author") {
+                    let authors = if author.is_array() {
+                        author
+                    } else {
+                        json!([author])
+                    };
+                    object.insert("authors".into(), authors);
+                }
+            }
+
+            // Parse authors that are strings e.g. "Jane Doe <jane@example.org>"
+            if let Some(serde_json::Value::Array(authors)) = object.get_mut("authors") {
+                for author in authors.iter_mut() {
+                    if let Some(string) = author.as_str() {
+                        if let Ok(person) = Person::from_str(string) {
+                            *author = serde_json::to_value(person).unwrap_or_default();
+                        }
+                    }
+                }
+            }
+
+            // Split keywords that are a comma separated string
+            if let Some(keywords) = object.get("keywords").and_then(|keywords| keywords.as_str()) {
+                let keywords = keywords
+                    .split(',')
+                    .map(|keyword| keyword.trim())
+                    .collect::<Vec<_>>();
+                object.insert("keywords".into(), json!(keywords));
+            }
+        }
+
+        // Parse title and abstract that are strings as Markdown (need to do here before
+        // deserializing to node, and remove from value so they do not cause an error when
+        // deserializing). Structured values are left to be deserialized with the node.
         let (title, abs) = if let Some(object) = value.as_object_mut() {
-            let title = object
-                .remove("title")
+            let title = matches!(object.get("title"), Some(serde_json::Value::String(..)))
+                .then(|| object.remove("title"))
+                .flatten()
                 .and_then(|value| value.as_str().map(String::from))
                 .map(|title| decode_inlines(&title, self));
-            let abs = object
-                .remove("abstract")
+            let abs = matches!(object.get("abstract"), Some(serde_json::Value::String(..)))
+                .then(|| object.remove("abstract"))
+                .flatten()
                 .and_then(|value| value.as_str().map(String::from))
                 .map(|abs| decode_blocks(&abs, self));
             (title, abs)
@@ -421,10 +543,14 @@ impl Context {
             return None;
         };
 
-        // Set title and abstract if node is Article
+        // Set title and abstract if node is Article and they were parsed as Markdown
         if let Node::Article(article) = &mut node {
-            article.title = title;
-            article.r#abstract = abs;
+            if title.is_some() {
+                article.title = title;
+            }
+            if abs.is_some() {
+                article.r#abstract = abs;
+            }
         }
 
         Some(node)
@@ -435,8 +561,10 @@ impl VisitorMut for Context {
     /// Apply footnote content to inline notes
     fn visit_inline(&mut self, inline: &mut Inline) -> WalkControl {
         if let Inline::Note(note) = inline {
-            if let Some(id) = note.id.take() {
-                if let Some(content) = self.footnotes.remove(&id) {
+            // Retain the id so that the footnote identifier is stable
+            // when encoding back to Markdown
+            if let Some(id) = &note.id {
+                if let Some(content) = self.footnotes.remove(id) {
                     note.content = content;
                 }
             }
@@ -0,0 +1,69 @@
+use codec::{
+    common::{eyre::Result, tokio},
+    schema::{Block, Node},
+    Codec, DecodeOptions,
+};
+use codec_markdown::MarkdownCodec;
+
+/// Test that the `coarse` option decodes only frontmatter and headings,
+/// collecting other content into raw blocks
+#[tokio::test]
+async fn coarse() -> Result<()> {
+    let codec = MarkdownCodec {};
+
+    let md = r#"---
+title: The title
+---
+
+Some intro text.
+
+# First
+
+A paragraph with *emphasis*.
+
+```python
+# A comment, not a heading
+```
+
+## Second ##
+
+More text.
+"#;
+
+    let (node, ..) = codec
+        .from_str(
+            md,
+            Some(DecodeOptions {
+                coarse: Some(true),
+                ..Default::default()
+            }),
+        )
+        .await?;
+
+    let Node::Article(article) = node else {
+        unreachable!("Expected an article")
+    };
+
+    // Frontmatter metadata is decoded
+    assert!(article.title.is_some());
+
+    // Headings are decoded; everything between them, including the comment
+    // within the code fence, is collected into raw blocks
+    assert_eq!(article.content.len(), 5);
+    assert!(matches!(article.content[0], Block::RawBlock(..)));
+    let Block::Heading(first) = &article.content[1] else {
+        unreachable!("Expected a heading")
+    };
+    assert_eq!(first.level, 1);
+    let Block::RawBlock(raw) = &article.content[2] else {
+        unreachable!("Expected a raw block")
+    };
+    assert!(raw.content.contains("# A comment, not a heading"));
+    let Block::Heading(second) = &article.content[3] else {
+        unreachable!("Expected a heading")
+    };
+    assert_eq!(second.level, 2);
+    assert!(matches!(article.content[4], Block::RawBlock(..)));
+
+    Ok(())
+}
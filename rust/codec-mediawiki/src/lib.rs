@@ -0,0 +1,85 @@
+use codec::{
+    common::{async_trait::async_trait, eyre::Result},
+    format::Format,
+    schema::Node,
+    status::Status,
+    Codec, CodecSupport, DecodeInfo, DecodeOptions, EncodeInfo, EncodeOptions, NodeType,
+};
+use codec_pandoc::{pandoc_from_format, pandoc_to_format, root_from_pandoc, root_to_pandoc};
+
+/// A codec for MediaWiki wikitext
+///
+/// Templates and other constructs without an equivalent in the schema are
+/// passed through as raw blocks so that pages can be round-tripped back
+/// to a wiki.
+pub struct MediawikiCodec;
+
+const PANDOC_FORMAT: &str = "mediawiki";
+
+#[async_trait]
+impl Codec for MediawikiCodec {
+    fn name(&self) -> &str {
+        "mediawiki"
+    }
+
+    fn status(&self) -> Status {
+        Status::UnderDevelopment
+    }
+
+    fn supports_from_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Mediawiki => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_to_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Mediawiki => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_from_type(&self, _node_type: NodeType) -> CodecSupport {
+        CodecSupport::LowLoss
+    }
+
+    fn supports_to_type(&self, _node_type: NodeType) -> CodecSupport {
+        CodecSupport::LowLoss
+    }
+
+    async fn from_str(
+        &self,
+        input: &str,
+        options: Option<DecodeOptions>,
+    ) -> Result<(Node, DecodeInfo)> {
+        let pandoc = pandoc_from_format(
+            input,
+            None,
+            PANDOC_FORMAT,
+            options
+                .map(|options| options.passthrough_args)
+                .unwrap_or_default(),
+        )
+        .await?;
+        root_from_pandoc(pandoc)
+    }
+
+    async fn to_string(
+        &self,
+        node: &Node,
+        options: Option<EncodeOptions>,
+    ) -> Result<(String, EncodeInfo)> {
+        let (pandoc, info) = root_to_pandoc(node)?;
+        let output = pandoc_to_format(
+            &pandoc,
+            None,
+            PANDOC_FORMAT,
+            options
+                .map(|options| options.passthrough_args)
+                .unwrap_or_default(),
+        )
+        .await?;
+        Ok((output, info))
+    }
+}
@@ -0,0 +1,212 @@
+use std::{fs::File, io::Read, path::Path};
+
+use roxmltree::{Document, Node as XmlNode};
+
+use codec::{
+    common::{
+        eyre::{bail, Result},
+        zip::ZipArchive,
+    },
+    schema::{Collection, CreativeWorkType, Datatable, DatatableColumn, Node, Primitive},
+    DecodeInfo, DecodeOptions,
+};
+
+/// The maximum number of times a repeated cell or row will be expanded
+///
+/// Spreadsheet applications pad sheets out to their maximum dimensions
+/// using repeat counts so these need to be capped to avoid generating
+/// millions of empty cells.
+const MAX_REPEAT: usize = 1000;
+
+/// Decode an ODS file to a Stencila [`Node`]
+///
+/// Reads the `content.xml` entry of the ODS Zip archive and decodes each
+/// sheet to a [`Datatable`] named after the sheet, treating the first row
+/// as column names. A single sheet becomes a `Datatable` node; multiple
+/// sheets become a [`Collection`] with one `Datatable` per sheet. The
+/// `sheets` option can be used to select sheets by name.
+pub(super) fn decode_path(
+    path: &Path,
+    options: Option<DecodeOptions>,
+) -> Result<(Node, DecodeInfo)> {
+    if !path.exists() {
+        bail!("Path `{}` does not exist", path.display());
+    }
+
+    let sheets = options.map(|options| options.sheets).unwrap_or_default();
+
+    let file = File::open(path)?;
+    let mut zip = ZipArchive::new(file)?;
+    let mut content = String::new();
+    zip.by_name("content.xml")?.read_to_string(&mut content)?;
+
+    let dom = Document::parse(&content)?;
+
+    let mut datatables = Vec::new();
+    for table in dom.descendants().filter(|node| node.has_tag_name("table")) {
+        let Some(name) = attribute(&table, "name") else {
+            continue;
+        };
+        if !sheets.is_empty() && !sheets.iter().any(|sheet| sheet == name) {
+            continue;
+        }
+        datatables.push(decode_sheet(&table, name));
+    }
+
+    if datatables.is_empty() {
+        if sheets.is_empty() {
+            bail!("Spreadsheet does not have any sheets")
+        } else {
+            bail!("Spreadsheet does not have any sheets matching the `sheets` option")
+        }
+    }
+
+    let node = if datatables.len() == 1 {
+        Node::Datatable(datatables.swap_remove(0))
+    } else {
+        Node::Collection(Collection::new(
+            datatables
+                .into_iter()
+                .map(CreativeWorkType::Datatable)
+                .collect(),
+        ))
+    };
+
+    Ok((node, DecodeInfo::none()))
+}
+
+/// Decode a `<table:table>` element to a [`Datatable`] named after the sheet
+fn decode_sheet(table: &XmlNode, name: &str) -> Datatable {
+    let mut rows = decode_table(table);
+
+    // Trim trailing empty rows
+    while rows
+        .last()
+        .map_or(false, |row| row.iter().all(is_null))
+    {
+        rows.pop();
+    }
+
+    let names = rows.first().cloned().unwrap_or_default();
+    let columns = names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let values = rows
+                .iter()
+                .skip(1)
+                .map(|row| row.get(index).cloned().unwrap_or_default())
+                .collect();
+            DatatableColumn::new(primitive_to_string(name), values)
+        })
+        .collect();
+
+    let mut datatable = Datatable::new(columns);
+    datatable.options.name = Some(name.to_string());
+    datatable
+}
+
+/// Decode a `<table:table>` element to rows of [`Primitive`] values
+fn decode_table(table: &XmlNode) -> Vec<Vec<Primitive>> {
+    let mut rows = Vec::new();
+    for row in table
+        .children()
+        .filter(|node| node.has_tag_name("table-row"))
+    {
+        let mut cells = Vec::new();
+        for cell in row.children().filter(|node| {
+            node.has_tag_name("table-cell") || node.has_tag_name("covered-table-cell")
+        }) {
+            let value = decode_cell(&cell);
+            // Expand repeated cells, including empty ones, so that interior
+            // blanks keep later values in the correct column (trailing empty
+            // cells, used to pad out sheets, are trimmed below)
+            let repeat: usize = attribute(&cell, "number-columns-repeated")
+                .and_then(|repeat| repeat.parse().ok())
+                .unwrap_or(1)
+                .min(MAX_REPEAT);
+            for _ in 0..repeat {
+                cells.push(value.clone());
+            }
+        }
+
+        // Trim trailing empty cells
+        while cells.last().map_or(false, is_null) {
+            cells.pop();
+        }
+
+        // Expand repeated rows, including empty ones, so that interior blank
+        // rows keep later rows in the correct position (trailing empty rows,
+        // used to pad out sheets, are trimmed when decoding the sheet)
+        let repeat: usize = attribute(&row, "number-rows-repeated")
+            .and_then(|repeat| repeat.parse().ok())
+            .unwrap_or(1)
+            .min(MAX_REPEAT);
+        for _ in 0..repeat {
+            rows.push(cells.clone());
+        }
+    }
+    rows
+}
+
+/// Decode a `<table:table-cell>` element to a [`Primitive`] value
+fn decode_cell(cell: &XmlNode) -> Primitive {
+    match attribute(cell, "value-type") {
+        Some("float") | Some("currency") | Some("percentage") => {
+            let value: f64 = attribute(cell, "value")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or_default();
+            if value.fract() == 0.0 && value.abs() < (i64::MAX as f64) {
+                Primitive::Integer(value as i64)
+            } else {
+                Primitive::Number(value)
+            }
+        }
+        Some("boolean") => Primitive::Boolean(attribute(cell, "boolean-value") == Some("true")),
+        Some("date") => Primitive::String(
+            attribute(cell, "date-value")
+                .map(String::from)
+                .unwrap_or_default(),
+        ),
+        Some("time") => Primitive::String(
+            attribute(cell, "time-value")
+                .map(String::from)
+                .unwrap_or_default(),
+        ),
+        _ => {
+            let text = cell
+                .descendants()
+                .filter_map(|node| node.text())
+                .collect::<Vec<&str>>()
+                .join("");
+            if text.is_empty() {
+                Primitive::Null(Default::default())
+            } else {
+                Primitive::String(text)
+            }
+        }
+    }
+}
+
+/// Get an attribute of an element by its local name (ignoring namespace prefix)
+fn attribute<'a>(node: &'a XmlNode, name: &str) -> Option<&'a str> {
+    node.attributes()
+        .find(|attribute| attribute.name() == name)
+        .map(|attribute| attribute.value())
+}
+
+/// Is a primitive a null value?
+fn is_null(value: &Primitive) -> bool {
+    matches!(value, Primitive::Null(..))
+}
+
+/// Convert a primitive to a string for use as a column name
+fn primitive_to_string(value: &Primitive) -> String {
+    match value {
+        Primitive::String(value) => value.clone(),
+        Primitive::Integer(value) => value.to_string(),
+        Primitive::Number(value) => value.to_string(),
+        Primitive::Boolean(value) => value.to_string(),
+        _ => String::new(),
+    }
+}
@@ -0,0 +1,183 @@
+use std::{fs, fs::File, io::Write, path::Path};
+
+use codec::{
+    common::{
+        eyre::{bail, Result},
+        serde_json,
+        zip::{write::FileOptions, CompressionMethod, ZipWriter},
+    },
+    schema::{CreativeWorkType, Datatable, Node, Primitive},
+    EncodeInfo, EncodeOptions,
+};
+
+/// The media type of OpenDocument Spreadsheets
+const MEDIA_TYPE: &str = "application/vnd.oasis.opendocument.spreadsheet";
+
+/// Encode a Stencila [`Node`] to an ODS file
+///
+/// A [`Datatable`] node becomes a single sheet with the column names as the
+/// first row. A [`Collection`] of datatables becomes a workbook with one
+/// sheet per datatable, named after the datatable.
+pub(super) fn encode_path(
+    node: &Node,
+    path: &Path,
+    _options: Option<EncodeOptions>,
+) -> Result<EncodeInfo> {
+    let sheets: Vec<(String, &Datatable)> = match node {
+        Node::Datatable(datatable) => vec![(sheet_name(datatable, 0), datatable)],
+        Node::Collection(collection) => collection
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                CreativeWorkType::Datatable(datatable) => Some(datatable),
+                _ => None,
+            })
+            .enumerate()
+            .map(|(index, datatable)| (sheet_name(datatable, index), datatable))
+            .collect(),
+        _ => bail!("Unable to encode a `{node}` to ODS"),
+    };
+    if sheets.is_empty() {
+        bail!("Unable to encode a `{node}` to ODS: no datatables")
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file = File::create(path)?;
+    let mut zip = ZipWriter::new(file);
+
+    // The `mimetype` entry must be first and uncompressed
+    zip.start_file(
+        "mimetype",
+        FileOptions::default().compression_method(CompressionMethod::Stored),
+    )?;
+    zip.write_all(MEDIA_TYPE.as_bytes())?;
+
+    zip.start_file("META-INF/manifest.xml", FileOptions::default())?;
+    zip.write_all(manifest().as_bytes())?;
+
+    zip.start_file("content.xml", FileOptions::default())?;
+    zip.write_all(content(&sheets).as_bytes())?;
+
+    zip.finish()?;
+
+    Ok(EncodeInfo::none())
+}
+
+/// Generate the `META-INF/manifest.xml` entry
+fn manifest() -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.2">
+ <manifest:file-entry manifest:full-path="/" manifest:media-type="{MEDIA_TYPE}"/>
+ <manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>
+</manifest:manifest>
+"#
+    )
+}
+
+/// Get the name for a sheet, defaulting to `Sheet<number>`
+fn sheet_name(datatable: &Datatable, index: usize) -> String {
+    datatable
+        .options
+        .name
+        .clone()
+        .unwrap_or_else(|| format!("Sheet{}", index + 1))
+}
+
+/// Generate the `content.xml` entry for one or more named sheets
+fn content(sheets: &[(String, &Datatable)]) -> String {
+    let mut tables = String::new();
+    for (name, datatable) in sheets {
+        tables.push_str(&format!(
+            "   <table:table table:name=\"{}\">\n",
+            escape(name)
+        ));
+        tables.push_str(&table(datatable));
+        tables.push_str("   </table:table>\n");
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" office:version="1.2">
+ <office:body>
+  <office:spreadsheet>
+{tables}  </office:spreadsheet>
+ </office:body>
+</office:document-content>
+"#
+    )
+}
+
+/// Generate the rows of a `<table:table>` element for a [`Datatable`]
+fn table(datatable: &Datatable) -> String {
+    let rows_count = datatable
+        .columns
+        .iter()
+        .map(|column| column.values.len())
+        .max()
+        .unwrap_or_default();
+
+    let mut rows = String::new();
+
+    // Header row of column names
+    rows.push_str("   <table:table-row>\n");
+    for column in &datatable.columns {
+        rows.push_str(&string_cell(&column.name));
+    }
+    rows.push_str("   </table:table-row>\n");
+
+    // Value rows
+    for index in 0..rows_count {
+        rows.push_str("   <table:table-row>\n");
+        for column in &datatable.columns {
+            rows.push_str(&match column.values.get(index) {
+                Some(Primitive::Integer(value)) => number_cell(*value as f64),
+                Some(Primitive::UnsignedInteger(value)) => number_cell(*value as f64),
+                Some(Primitive::Number(value)) => number_cell(*value),
+                Some(Primitive::Boolean(value)) => boolean_cell(*value),
+                Some(Primitive::String(value)) => string_cell(value),
+                Some(value) => {
+                    string_cell(&serde_json::to_string(value).unwrap_or_default())
+                }
+                None => "    <table:table-cell/>\n".to_string(),
+            });
+        }
+        rows.push_str("   </table:table-row>\n");
+    }
+
+    rows
+}
+
+/// Generate a cell with a float value
+fn number_cell(value: f64) -> String {
+    format!(
+        "    <table:table-cell office:value-type=\"float\" office:value=\"{value}\"><text:p>{value}</text:p></table:table-cell>\n"
+    )
+}
+
+/// Generate a cell with a boolean value
+fn boolean_cell(value: bool) -> String {
+    format!(
+        "    <table:table-cell office:value-type=\"boolean\" office:boolean-value=\"{value}\"><text:p>{value}</text:p></table:table-cell>\n"
+    )
+}
+
+/// Generate a cell with a string value
+fn string_cell(value: &str) -> String {
+    format!(
+        "    <table:table-cell office:value-type=\"string\"><text:p>{}</text:p></table:table-cell>\n",
+        escape(value)
+    )
+}
+
+/// Escape a string for use in XML text content or attribute values
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
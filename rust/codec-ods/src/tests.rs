@@ -0,0 +1,119 @@
+use std::{fs::File, io::Write};
+
+use codec::{
+    common::{
+        tempfile,
+        tokio,
+        zip::{write::FileOptions, ZipWriter},
+    },
+    schema::{Datatable, DatatableColumn, Primitive},
+};
+use common_dev::pretty_assertions::assert_eq;
+
+use super::*;
+
+/// Decode a sheet with repeated cells and rows
+///
+/// Interior repeats must be expanded so that later values stay in the
+/// correct column and row, while trailing repeats (used by applications
+/// to pad sheets out to their maximum dimensions) are trimmed.
+#[tokio::test]
+async fn repeats() -> Result<()> {
+    const CONTENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" office:version="1.2">
+ <office:body>
+  <office:spreadsheet>
+   <table:table table:name="Sheet1">
+    <table:table-row>
+     <table:table-cell office:value-type="string"><text:p>a</text:p></table:table-cell>
+     <table:table-cell office:value-type="string"><text:p>b</text:p></table:table-cell>
+     <table:table-cell office:value-type="string"><text:p>c</text:p></table:table-cell>
+     <table:table-cell office:value-type="string"><text:p>d</text:p></table:table-cell>
+    </table:table-row>
+    <table:table-row>
+     <table:table-cell office:value-type="float" office:value="1"/>
+     <table:table-cell table:number-columns-repeated="2"/>
+     <table:table-cell office:value-type="float" office:value="4"/>
+    </table:table-row>
+    <table:table-row table:number-rows-repeated="2"/>
+    <table:table-row>
+     <table:table-cell office:value-type="float" office:value="5"/>
+     <table:table-cell table:number-columns-repeated="1000"/>
+    </table:table-row>
+    <table:table-row table:number-rows-repeated="100000"/>
+   </table:table>
+  </office:spreadsheet>
+ </office:body>
+</office:document-content>
+"#;
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("repeats.ods");
+
+    let file = File::create(&path)?;
+    let mut zip = ZipWriter::new(file);
+    zip.start_file("content.xml", FileOptions::default())?;
+    zip.write_all(CONTENT.as_bytes())?;
+    zip.finish()?;
+
+    let codec = OdsCodec {};
+    let (node, ..) = codec.from_path(&path, None).await?;
+
+    let Node::Datatable(datatable) = node else {
+        unreachable!()
+    };
+
+    let null = || Primitive::Null(Default::default());
+
+    assert_eq!(datatable.columns.len(), 4);
+    assert_eq!(
+        datatable.columns[0].values,
+        vec![Primitive::Integer(1), null(), null(), Primitive::Integer(5)]
+    );
+    assert_eq!(
+        datatable.columns[1].values,
+        vec![null(), null(), null(), null()]
+    );
+    assert_eq!(
+        datatable.columns[3].values,
+        vec![Primitive::Integer(4), null(), null(), null()]
+    );
+
+    Ok(())
+}
+
+/// Round-trip a datatable through an ODS file
+#[tokio::test]
+async fn round_trip() -> Result<()> {
+    let codec = OdsCodec {};
+
+    let mut datatable = Datatable::new(vec![
+        DatatableColumn::new(
+            "name".to_string(),
+            vec![
+                Primitive::String("a".to_string()),
+                Primitive::String("b".to_string()),
+            ],
+        ),
+        DatatableColumn::new(
+            "count".to_string(),
+            vec![Primitive::Integer(1), Primitive::Integer(2)],
+        ),
+        DatatableColumn::new(
+            "flag".to_string(),
+            vec![Primitive::Boolean(true), Primitive::Boolean(false)],
+        ),
+    ]);
+    datatable.options.name = Some("Data".to_string());
+    let node = Node::Datatable(datatable);
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("round_trip.ods");
+
+    codec.to_path(&node, &path, None).await?;
+    let (decoded, ..) = codec.from_path(&path, None).await?;
+
+    assert_eq!(decoded, node);
+
+    Ok(())
+}
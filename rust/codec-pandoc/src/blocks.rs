@@ -2,7 +2,7 @@ use std::{any::type_name_of_val, str::FromStr};
 
 use pandoc_types::definition::{self as pandoc};
 
-use codec::schema::*;
+use codec::{common::tracing, schema::*};
 use codec_text_trait::to_text;
 
 use crate::{
@@ -439,8 +439,29 @@ fn figure_from_pandoc(
 
 fn code_block_to_pandoc(
     code_block: &CodeBlock,
-    _context: &mut PandocEncodeContext,
+    context: &mut PandocEncodeContext,
 ) -> pandoc::Block {
+    // Render diagram code blocks to an SVG file so that they are visible in
+    // formats that do not support client-side rendering (e.g. DOCX, PDF)
+    let lang = code_block
+        .programming_language
+        .as_deref()
+        .unwrap_or_default();
+    if matches!(lang, "dot" | "graphviz" | "mermaid") {
+        let svg = if lang == "mermaid" {
+            images::mermaid_to_svg(&code_block.code)
+        } else {
+            images::dot_to_svg(&code_block.code)
+        };
+        match svg {
+            Ok(path) => {
+                let image = Inline::ImageObject(ImageObject::new(path.to_string_lossy().into()));
+                return pandoc::Block::Plain(inlines_to_pandoc(&[image], context));
+            }
+            Err(error) => tracing::warn!("While rendering `{lang}` code block: {error}"),
+        }
+    }
+
     let classes = code_block
         .programming_language
         .as_ref()
@@ -502,9 +523,14 @@ fn code_block_from_pandoc(
 
 fn code_chunk_to_pandoc(
     code_chunk: &CodeChunk,
-    _context: &mut PandocEncodeContext,
+    context: &mut PandocEncodeContext,
 ) -> pandoc::Block {
-    // TODO: Handle outputs
+    // When rendering, encode the outputs of the chunk, rather than its code
+    if context.render {
+        if let Some(outputs) = &code_chunk.outputs {
+            return code_chunk_outputs_to_pandoc(code_chunk, outputs, context);
+        }
+    }
 
     // If no outputs, then encode as a code block
 
@@ -536,6 +562,62 @@ fn code_chunk_to_pandoc(
     pandoc::Block::CodeBlock(attrs, code_chunk.code.to_string())
 }
 
+/// Encode the outputs of a `CodeChunk` to a Pandoc block
+///
+/// Image outputs are encoded within a figure, using the chunk's label and
+/// caption, and other outputs as paragraphs.
+fn code_chunk_outputs_to_pandoc(
+    code_chunk: &CodeChunk,
+    outputs: &[Node],
+    context: &mut PandocEncodeContext,
+) -> pandoc::Block {
+    let attrs = if let Some(label) = &code_chunk.label {
+        attrs_attributes(vec![("label".into(), label.into())])
+    } else {
+        attrs_empty()
+    };
+
+    context.paragraph_to_plain = true;
+
+    let caption = code_chunk
+        .caption
+        .as_ref()
+        .map(|blocks| blocks_to_pandoc(blocks, context))
+        .unwrap_or_default();
+
+    let mut blocks = Vec::new();
+    for output in outputs {
+        match output {
+            Node::ImageObject(image) => {
+                let inlines =
+                    inlines_to_pandoc(&[Inline::ImageObject(image.clone())], context);
+                blocks.push(pandoc::Block::Plain(inlines));
+            }
+            _ => blocks.push(pandoc::Block::Para(vec![pandoc::Inline::Str(to_text(
+                output,
+            ))])),
+        }
+    }
+
+    context.paragraph_to_plain = false;
+
+    let has_image = outputs
+        .iter()
+        .any(|output| matches!(output, Node::ImageObject(..)));
+    if has_image || code_chunk.label_type.is_some() || code_chunk.caption.is_some() {
+        pandoc::Block::Figure(
+            attrs,
+            pandoc::Caption {
+                short: None,
+                long: caption,
+            },
+            blocks,
+        )
+    } else {
+        pandoc::Block::Div(attrs, blocks)
+    }
+}
+
 fn math_block_to_pandoc(
     math_block: &MathBlock,
     context: &mut PandocEncodeContext,
@@ -1,14 +1,16 @@
 use pandoc_types::definition::{self as pandoc, Attr, Target};
 
 use codec::{
-    common::{once_cell::sync::Lazy, regex::Regex, serde_json},
+    common::{once_cell::sync::Lazy, regex::Regex, serde_json, tracing},
     schema::*,
 };
 use codec_text_trait::to_text;
 
+use std::str::FromStr;
+
 use crate::{
     blocks::{blocks_from_pandoc, blocks_to_pandoc},
-    shared::{attrs_classes, attrs_empty, PandocDecodeContext, PandocEncodeContext},
+    shared::{attrs_classes, attrs_empty, get_attr, PandocDecodeContext, PandocEncodeContext},
 };
 
 pub(super) fn inlines_to_pandoc(
@@ -49,6 +51,9 @@ pub(super) fn inlines_from_pandoc(
         }
     }
 
+    // Remove any empty text nodes e.g. those left by removed comment markers
+    inlines.retain(|inline| !matches!(inline, Inline::Text(Text { value, .. }) if value.is_empty()));
+
     inlines
 }
 
@@ -101,11 +106,20 @@ fn inline_to_pandoc(
         Inline::StyledInline(styled) => styled_inline_to_pandoc(styled, context),
         Inline::Parameter(parameter) => parameter_to_pandoc(parameter, context),
 
+        // Suggestions: encoded as spans with the classes that Pandoc's `docx`
+        // writer (and others) recognize as tracked changes
+        Inline::InsertInline(insert) => pandoc::Inline::Span(
+            attrs_classes(vec!["insertion".into()]),
+            inlines_to_pandoc(&insert.content, context),
+        ),
+        Inline::DeleteInline(delete) => pandoc::Inline::Span(
+            attrs_classes(vec!["deletion".into()]),
+            inlines_to_pandoc(&delete.content, context),
+        ),
+
         // Inline types currently ignored: record loss and encode an empty span
         // TODO: implement these or remove from schema's `Inline` enum
         Inline::Button(..)
-        | Inline::DeleteInline(..)
-        | Inline::InsertInline(..)
         | Inline::InstructionInline(..)
         | Inline::ModifyInline(..)
         | Inline::ReplaceInline(..)
@@ -159,6 +173,26 @@ fn audio_to_pandoc(audio: &AudioObject, context: &mut PandocEncodeContext) -> pa
 }
 
 fn image_to_pandoc(image: &ImageObject, context: &mut PandocEncodeContext) -> pandoc::Inline {
+    // Render Mermaid diagrams (which have the diagram code in `content_url`)
+    // to an SVG file so that they are visible in formats that do not support
+    // client-side rendering (e.g. DOCX, PDF)
+    if image.media_type.as_deref() == Some("text/vnd.mermaid") {
+        match images::mermaid_to_svg(&image.content_url) {
+            Ok(path) => {
+                return media_object_to_pandoc(
+                    &path.to_string_lossy(),
+                    &image.title,
+                    &image.caption,
+                    context,
+                )
+            }
+            Err(error) => {
+                tracing::warn!("While rendering Mermaid diagram: {error}");
+                context.losses.add("ImageObject.contentUrl");
+            }
+        }
+    }
+
     media_object_to_pandoc(&image.content_url, &image.title, &image.caption, context)
 }
 
@@ -344,6 +378,37 @@ fn styled_inline_from_pandoc(
     inlines: Vec<pandoc::Inline>,
     context: &mut PandocDecodeContext,
 ) -> Inline {
+    // Spans that Pandoc produces for tracked changes (e.g. when reading DOCX
+    // with `--track-changes=all`) are decoded to suggestion nodes so that
+    // collaborator edits can be accepted or rejected with existing tooling
+    if attrs.classes.iter().any(|class| class == "insertion") {
+        return Inline::InsertInline(InsertInline::new(inlines_from_pandoc(inlines, context)));
+    }
+    if attrs.classes.iter().any(|class| class == "deletion") {
+        return Inline::DeleteInline(DeleteInline::new(inlines_from_pandoc(inlines, context)));
+    }
+
+    // Spans for Word comments are collected into the context, to be attached
+    // to the root node, and the markers removed from the inline flow
+    if attrs.classes.iter().any(|class| class == "comment-start") {
+        let comment = Comment {
+            id: get_attr(&attrs, "id"),
+            authors: get_attr(&attrs, "author")
+                .and_then(|author| Person::from_str(&author).ok())
+                .map(|person| vec![Author::Person(person)]),
+            date_published: get_attr(&attrs, "date").map(Date::new),
+            content: vec![Block::Paragraph(Paragraph::new(inlines_from_pandoc(
+                inlines, context,
+            )))],
+            ..Default::default()
+        };
+        context.comments.push(comment);
+        return Inline::Text(Text::new("".into()));
+    }
+    if attrs.classes.iter().any(|class| class == "comment-end") {
+        return Inline::Text(Text::new("".into()));
+    }
+
     Inline::StyledInline(StyledInline::new(
         attrs.classes.join(" ").into(),
         inlines_from_pandoc(inlines, context),
@@ -8,8 +8,11 @@ use codec::{
     DecodeInfo, EncodeInfo,
 };
 
+use codec_text_trait::to_text;
+
 use crate::{
     blocks::{blocks_from_pandoc, blocks_to_pandoc},
+    inlines::inlines_to_pandoc,
     meta::{
         inlines_from_meta_inlines, inlines_to_meta_inlines, string_from_meta_value,
         string_to_meta_value,
@@ -30,6 +33,24 @@ pub fn root_to_pandoc(root: &Node) -> Result<(pandoc::Pandoc, EncodeInfo)> {
     ))
 }
 
+/// As for [`root_to_pandoc`] but encoding the outputs of executable nodes,
+/// rather than their code (e.g. for Beamer slides)
+pub fn root_to_pandoc_render(root: &Node) -> Result<(pandoc::Pandoc, EncodeInfo)> {
+    let mut context = PandocEncodeContext {
+        render: true,
+        ..Default::default()
+    };
+    let pandoc = node_to_pandoc(root, &mut context)?;
+
+    Ok((
+        pandoc,
+        EncodeInfo {
+            losses: context.losses,
+            ..Default::default()
+        },
+    ))
+}
+
 pub fn root_from_pandoc(pandoc: pandoc::Pandoc) -> Result<(Node, DecodeInfo)> {
     let mut context = PandocDecodeContext::default();
     let node = node_from_pandoc(pandoc, &mut context)?;
@@ -87,7 +108,54 @@ fn article_to_pandoc(
         }
     }
 
-    let blocks = blocks_to_pandoc(&article.content, context);
+    let mut blocks = blocks_to_pandoc(&article.content, context);
+
+    // Write any comments back as comment spans, recognized by the `docx`
+    // writer, in a trailing paragraph. The range of the document that a
+    // comment was originally anchored to is not stored, so it is not restored.
+    if let Some(comments) = &article.options.comments {
+        for (index, comment) in comments.iter().enumerate() {
+            let content = comment
+                .content
+                .first()
+                .and_then(|block| match block {
+                    Block::Paragraph(paragraph) => {
+                        Some(inlines_to_pandoc(&paragraph.content, context))
+                    }
+                    _ => None,
+                })
+                .unwrap_or_default();
+
+            let id = comment.id.clone().unwrap_or_else(|| index.to_string());
+
+            let mut attributes = vec![("id".to_string(), id.clone())];
+            if let Some(Author::Person(person)) = comment.authors.iter().flatten().next() {
+                attributes.push(("author".to_string(), to_text(person)));
+            }
+            if let Some(date) = &comment.date_published {
+                attributes.push(("date".to_string(), date.value.to_string()));
+            }
+
+            blocks.push(pandoc::Block::Para(vec![
+                pandoc::Inline::Span(
+                    pandoc::Attr {
+                        classes: vec!["comment-start".to_string()],
+                        attributes,
+                        ..Default::default()
+                    },
+                    content,
+                ),
+                pandoc::Inline::Span(
+                    pandoc::Attr {
+                        classes: vec!["comment-end".to_string()],
+                        attributes: vec![("id".to_string(), id)],
+                        ..Default::default()
+                    },
+                    Vec::new(),
+                ),
+            ]));
+        }
+    }
 
     Ok(pandoc::Pandoc { meta, blocks })
 }
@@ -122,12 +190,19 @@ fn article_from_pandoc(pandoc: pandoc::Pandoc, context: &mut PandocDecodeContext
 
     let content = blocks_from_pandoc(pandoc.blocks, context);
 
-    Article {
+    let mut article = Article {
         title,
         date_published,
         content,
         keywords,
         r#abstract,
         ..Default::default()
+    };
+
+    // Attach any comments collected while decoding (e.g. Word comments)
+    if !context.comments.is_empty() {
+        article.options.comments = Some(std::mem::take(&mut context.comments));
     }
+
+    article
 }
@@ -1,4 +1,4 @@
-use codec::Losses;
+use codec::{schema::Comment, Losses};
 use pandoc_types::definition as pandoc;
 
 /// The context for encoding to Pandoc AST
@@ -9,12 +9,20 @@ pub(super) struct PandocEncodeContext {
     /// Encode paragraphs as Pandoc `Plain` blocks in places
     /// like figure and table captions.
     pub paragraph_to_plain: bool,
+
+    /// Encode the outputs of executable nodes, rather than their code
+    /// (e.g. when encoding to Beamer slides)
+    pub render: bool,
 }
 
 /// The context for decoding from Pandoc AST
 #[derive(Default)]
 pub(super) struct PandocDecodeContext {
     pub losses: Losses,
+
+    /// Comments collected from `comment-start` spans (e.g. Word comments)
+    /// to be attached to the root node
+    pub comments: Vec<Comment>,
 }
 
 /// Create an empty Pandoc `Attr` tuple
@@ -0,0 +1,237 @@
+use std::{path::Path, process::Stdio};
+
+use codec::{
+    common::{
+        eyre::{bail, Result},
+        tokio::process::Command,
+    },
+    schema::{
+        shortcuts::{h, p, t, tbl, td, th, tr},
+        Article, Block, CreativeWorkTypeOrText, Node, Text,
+    },
+    DecodeInfo, DecodeOptions, Losses,
+};
+
+/// Decode a PDF file to a Stencila [`Node`]
+///
+/// Uses the `pdftotext` binary (part of Poppler) to extract the text of the
+/// PDF and then applies heuristics to recover the structure of the article:
+/// title, headings, paragraphs, simple tables, and references. Only works
+/// well for born-digital PDFs with an embedded text layer.
+pub(super) async fn decode_path(
+    path: &Path,
+    _options: Option<DecodeOptions>,
+) -> Result<(Node, DecodeInfo)> {
+    if !path.exists() {
+        bail!("File does not exists: {}", path.to_string_lossy())
+    }
+
+    let output = Command::new("pdftotext")
+        .args(["-layout", "-enc", "UTF-8"])
+        .arg(path)
+        .arg("-")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        let error = String::from_utf8(output.stderr)?;
+        bail!("While extracting text from PDF using `pdftotext`: {error}")
+    }
+
+    let text = String::from_utf8(output.stdout)?;
+    Ok(decode_text(&text))
+}
+
+/// Decode text extracted from a PDF into an [`Article`]
+fn decode_text(text: &str) -> (Node, DecodeInfo) {
+    let mut title: Option<String> = None;
+    let mut content: Vec<Block> = Vec::new();
+    let mut references: Vec<CreativeWorkTypeOrText> = Vec::new();
+    let mut in_references = false;
+
+    for (index, lines) in blocks(text).into_iter().enumerate() {
+        // Treat the first, short, block as the title of the article
+        if index == 0 && lines.len() <= 3 && heading_level(lines[0]).is_none() {
+            title = Some(join(&lines));
+            continue;
+        }
+
+        if let Some(table) = table_from_lines(&lines) {
+            content.push(table);
+            continue;
+        }
+
+        if lines.len() == 1 {
+            if let Some(level) = heading_level(lines[0]) {
+                let text = lines[0].trim().to_string();
+                in_references = matches!(
+                    trim_numbering(&text).to_lowercase().as_str(),
+                    "references" | "bibliography"
+                );
+                content.push(h(level, [t(text)]));
+                continue;
+            }
+        }
+
+        if in_references {
+            references.push(CreativeWorkTypeOrText::Text(Text::from(join(&lines))));
+            continue;
+        }
+
+        content.push(p([t(join(&lines))]));
+    }
+
+    let mut article = Article::new(content);
+    if let Some(title) = title {
+        article.title = Some(vec![t(title)]);
+    }
+    if !references.is_empty() {
+        article.references = Some(references);
+    }
+
+    (
+        Node::Article(article),
+        DecodeInfo {
+            // Non-textual content (e.g. figures, math) and inline formatting
+            // are not recovered by text extraction
+            losses: Losses::todo(),
+            ..Default::default()
+        },
+    )
+}
+
+/// Split extracted text into blocks of consecutive non-empty lines
+///
+/// Form feeds (page breaks) are treated as blank lines.
+fn blocks(text: &str) -> Vec<Vec<&str>> {
+    let mut blocks = Vec::new();
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let trimmed = line.trim_end().trim_end_matches('\u{c}');
+        if trimmed.trim().is_empty() {
+            if !lines.is_empty() {
+                blocks.push(std::mem::take(&mut lines));
+            }
+        } else {
+            lines.push(trimmed);
+        }
+    }
+    if !lines.is_empty() {
+        blocks.push(lines);
+    }
+    blocks
+}
+
+/// Join the lines of a block into a single string with collapsed whitespace
+fn join(lines: &[&str]) -> String {
+    lines
+        .iter()
+        .flat_map(|line| line.split_whitespace())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Get the heading level of a line, if it appears to be a heading
+///
+/// A line is considered a heading if it is short, does not end in sentence
+/// punctuation, and is either numbered (e.g. `2.`, `3.1`), all uppercase,
+/// or a common section title. The level is derived from the depth of any
+/// numbering.
+fn heading_level(line: &str) -> Option<i64> {
+    let line = line.trim();
+    if line.len() > 80 || line.ends_with(['.', ':', ';', ',']) {
+        return None;
+    }
+
+    // Numbered headings e.g. "2 Methods", "3.1. Sampling", "A.2 Details"
+    if let Some((first, rest)) = line.split_once(' ') {
+        let first = first.trim_end_matches('.');
+        let parts: Vec<&str> = first.split('.').collect();
+        if !rest.trim().is_empty()
+            && parts.iter().all(|part| {
+                part.chars().all(|char| char.is_ascii_digit())
+                    || (part.len() == 1 && part.chars().all(|char| char.is_ascii_uppercase()))
+            })
+        {
+            return Some((parts.len() as i64).min(6));
+        }
+    }
+
+    // All uppercase headings e.g. "INTRODUCTION"
+    let mut letters = line.chars().filter(|char| char.is_alphabetic()).peekable();
+    if letters.peek().is_some() && letters.all(|char| char.is_uppercase()) {
+        return Some(1);
+    }
+
+    // Common un-numbered section titles
+    matches!(
+        line.to_lowercase().as_str(),
+        "abstract"
+            | "introduction"
+            | "background"
+            | "methods"
+            | "materials and methods"
+            | "results"
+            | "discussion"
+            | "conclusion"
+            | "conclusions"
+            | "acknowledgements"
+            | "acknowledgments"
+            | "references"
+            | "bibliography"
+            | "appendix"
+    )
+    .then_some(1)
+}
+
+/// Strip any leading numbering from a heading
+fn trim_numbering(text: &str) -> &str {
+    match text.split_once(' ') {
+        Some((first, rest))
+            if first
+                .chars()
+                .all(|char| char.is_ascii_digit() || char == '.') =>
+        {
+            rest.trim()
+        }
+        _ => text,
+    }
+}
+
+/// Attempt to decode a block of lines as a [`Table`]
+///
+/// The `-layout` option of `pdftotext` preserves the column alignment of
+/// tables so rows appear as lines with cells separated by runs of two or
+/// more spaces. Requires at least two rows with the same number of cells.
+fn table_from_lines(lines: &[&str]) -> Option<Block> {
+    if lines.len() < 2 {
+        return None;
+    }
+
+    let rows: Vec<Vec<&str>> = lines
+        .iter()
+        .map(|line| {
+            line.split("  ")
+                .map(str::trim)
+                .filter(|cell| !cell.is_empty())
+                .collect()
+        })
+        .collect();
+
+    let columns = rows.first().map(Vec::len)?;
+    if columns < 2 || rows.iter().any(|cells| cells.len() != columns) {
+        return None;
+    }
+
+    Some(tbl(rows.into_iter().enumerate().map(|(index, cells)| {
+        tr(cells.into_iter().map(|cell| {
+            if index == 0 {
+                th([t(cell)])
+            } else {
+                td([t(cell)])
+            }
+        }))
+    })))
+}
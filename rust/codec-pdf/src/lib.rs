@@ -7,7 +7,9 @@ use codec::{
     status::Status,
     Codec, CodecSupport, DecodeInfo, DecodeOptions, EncodeInfo, EncodeOptions, NodeType,
 };
-use codec_pandoc::{pandoc_from_format, pandoc_to_format, root_from_pandoc, root_to_pandoc};
+use codec_pandoc::{pandoc_to_format, root_to_pandoc};
+
+mod decode;
 
 /// A codec for PDF
 pub struct PdfCodec;
@@ -24,8 +26,11 @@ impl Codec for PdfCodec {
         Status::UnderDevelopment
     }
 
-    fn supports_from_format(&self, _format: &Format) -> CodecSupport {
-        CodecSupport::None
+    fn supports_from_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Pdf => CodecSupport::HighLoss,
+            _ => CodecSupport::None,
+        }
     }
 
     fn supports_to_format(&self, format: &Format) -> CodecSupport {
@@ -35,8 +40,11 @@ impl Codec for PdfCodec {
         }
     }
 
-    fn supports_from_type(&self, _node_type: NodeType) -> CodecSupport {
-        CodecSupport::None
+    fn supports_from_type(&self, node_type: NodeType) -> CodecSupport {
+        match node_type {
+            NodeType::Article => CodecSupport::HighLoss,
+            _ => CodecSupport::None,
+        }
     }
 
     fn supports_to_type(&self, _node_type: NodeType) -> CodecSupport {
@@ -56,16 +64,7 @@ impl Codec for PdfCodec {
         path: &Path,
         options: Option<DecodeOptions>,
     ) -> Result<(Node, DecodeInfo)> {
-        let pandoc = pandoc_from_format(
-            "",
-            Some(path),
-            PANDOC_FORMAT,
-            options
-                .map(|options| options.passthrough_args)
-                .unwrap_or_default(),
-        )
-        .await?;
-        root_from_pandoc(pandoc)
+        decode::decode_path(path, options).await
     }
 
     async fn to_path(
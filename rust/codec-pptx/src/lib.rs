@@ -0,0 +1,76 @@
+use std::path::Path;
+
+use codec::{
+    common::{async_trait::async_trait, eyre::Result},
+    format::Format,
+    schema::Node,
+    status::Status,
+    Codec, CodecSupport, EncodeInfo, EncodeOptions, NodeType,
+};
+use codec_pandoc::{pandoc_to_format, root_to_pandoc};
+
+/// A codec for Microsoft PowerPoint PPTX
+///
+/// Encodes a document as a slide deck with slides delimited by headings
+/// and thematic breaks (as per Pandoc's slide show conventions). Encoding
+/// only; there is no support for decoding PPTX.
+pub struct PptxCodec;
+
+const PANDOC_FORMAT: &str = "pptx";
+
+#[async_trait]
+impl Codec for PptxCodec {
+    fn name(&self) -> &str {
+        "pptx"
+    }
+
+    fn status(&self) -> Status {
+        Status::UnderDevelopment
+    }
+
+    fn supports_from_format(&self, _format: &Format) -> CodecSupport {
+        CodecSupport::None
+    }
+
+    fn supports_to_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Pptx => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_from_type(&self, _node_type: NodeType) -> CodecSupport {
+        CodecSupport::None
+    }
+
+    fn supports_to_type(&self, _node_type: NodeType) -> CodecSupport {
+        CodecSupport::LowLoss
+    }
+
+    fn supports_from_string(&self) -> bool {
+        false
+    }
+
+    fn supports_to_string(&self) -> bool {
+        false
+    }
+
+    async fn to_path(
+        &self,
+        node: &Node,
+        path: &Path,
+        options: Option<EncodeOptions>,
+    ) -> Result<EncodeInfo> {
+        let (pandoc, info) = root_to_pandoc(node)?;
+        pandoc_to_format(
+            &pandoc,
+            Some(path),
+            PANDOC_FORMAT,
+            options
+                .map(|options| options.passthrough_args)
+                .unwrap_or_default(),
+        )
+        .await?;
+        Ok(info)
+    }
+}
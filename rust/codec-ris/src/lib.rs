@@ -0,0 +1,309 @@
+use std::str::FromStr;
+
+use codec::{
+    common::{
+        async_trait::async_trait,
+        eyre::{bail, Result},
+    },
+    format::Format,
+    schema::{
+        shortcuts::{p, t},
+        Article, Author, CreativeWorkType, CreativeWorkTypeOrText, Date, IntegerOrString, Node,
+        Periodical, Person, PropertyValueOrString,
+    },
+    status::Status,
+    Codec, CodecSupport, DecodeInfo, DecodeOptions, EncodeInfo, EncodeOptions, Losses, NodeType,
+};
+use codec_text_trait::to_text;
+
+/// A codec for the RIS reference format
+///
+/// Decodes the records of a RIS file (as exported by EndNote, Scopus and
+/// other reference managers) into the `references` of an [`Article`] and
+/// encodes an article's references back to RIS.
+pub struct RisCodec;
+
+#[async_trait]
+impl Codec for RisCodec {
+    fn name(&self) -> &str {
+        "ris"
+    }
+
+    fn status(&self) -> Status {
+        Status::UnderDevelopment
+    }
+
+    fn supports_from_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Ris => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_to_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Ris => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_from_type(&self, node_type: NodeType) -> CodecSupport {
+        match node_type {
+            NodeType::Article => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_to_type(&self, node_type: NodeType) -> CodecSupport {
+        match node_type {
+            NodeType::Article => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    async fn from_str(
+        &self,
+        input: &str,
+        _options: Option<DecodeOptions>,
+    ) -> Result<(Node, DecodeInfo)> {
+        let (references, losses) = decode(input);
+
+        let article = Article {
+            references: (!references.is_empty()).then_some(references),
+            ..Default::default()
+        };
+
+        Ok((
+            Node::Article(article),
+            DecodeInfo {
+                losses,
+                ..Default::default()
+            },
+        ))
+    }
+
+    async fn to_string(
+        &self,
+        node: &Node,
+        _options: Option<EncodeOptions>,
+    ) -> Result<(String, EncodeInfo)> {
+        encode(node)
+    }
+}
+
+/// Decode RIS content into references
+///
+/// Records start at a `TY` tag and end at an `ER` tag. Tags that have no
+/// equivalent in the schema are recorded as losses.
+fn decode(ris: &str) -> (Vec<CreativeWorkTypeOrText>, Losses) {
+    let mut references = Vec::new();
+    let mut losses = Losses::none();
+
+    let mut article: Option<Article> = None;
+    for line in ris.lines() {
+        let Some((tag, value)) = line.split_once("  - ").or_else(|| line.split_once('-')) else {
+            continue;
+        };
+        let tag = tag.trim();
+        let value = value.trim().to_string();
+
+        if tag == "TY" {
+            article = Some(Article::default());
+            continue;
+        }
+
+        if tag == "ER" {
+            if let Some(article) = article.take() {
+                references.push(CreativeWorkTypeOrText::CreativeWorkType(
+                    CreativeWorkType::Article(article),
+                ));
+            }
+            continue;
+        }
+
+        let Some(current) = article.as_mut() else {
+            continue;
+        };
+
+        match tag {
+            "ID" => current.id = Some(value),
+            "TI" | "T1" => current.title = Some(vec![t(value)]),
+            "AU" | "A1" => {
+                if let Ok(person) = Person::from_str(&value) {
+                    current
+                        .authors
+                        .get_or_insert_with(Vec::new)
+                        .push(Author::Person(person));
+                }
+            }
+            "PY" | "Y1" => {
+                let value = value
+                    .split('/')
+                    .filter(|part| !part.trim().is_empty())
+                    .collect::<Vec<&str>>()
+                    .join("-");
+                if !value.is_empty() {
+                    current.date_published = Some(Date::new(value));
+                }
+            }
+            "JO" | "JF" | "T2" => {
+                current.options.is_part_of = Some(CreativeWorkType::Periodical(Periodical {
+                    name: Some(value),
+                    ..Default::default()
+                }))
+            }
+            "SP" => current.options.page_start = Some(page_to_integer_or_string(&value)),
+            "EP" => current.options.page_end = Some(page_to_integer_or_string(&value)),
+            "AB" | "N2" => current.r#abstract = Some(vec![p([t(value)])]),
+            "UR" => current.options.url = Some(value),
+            "DO" => {
+                current
+                    .options
+                    .identifiers
+                    .get_or_insert_with(Vec::new)
+                    .push(PropertyValueOrString::String(format!(
+                        "https://doi.org/{value}"
+                    )));
+            }
+            _ => losses.add(tag.to_string()),
+        }
+    }
+
+    // A record without a final ER tag
+    if let Some(article) = article {
+        references.push(CreativeWorkTypeOrText::CreativeWorkType(
+            CreativeWorkType::Article(article),
+        ));
+    }
+
+    (references, losses)
+}
+
+/// Encode the references of a [`Node`] to RIS
+fn encode(node: &Node) -> Result<(String, EncodeInfo)> {
+    let Node::Article(Article { references, .. }) = node else {
+        bail!("Unable to encode a `{node}` to RIS")
+    };
+
+    let mut ris = String::new();
+    let mut losses = Losses::none();
+
+    for reference in references.iter().flatten() {
+        match reference {
+            CreativeWorkTypeOrText::CreativeWorkType(CreativeWorkType::Article(article)) => {
+                ris.push_str(&encode_article(article));
+            }
+            CreativeWorkTypeOrText::Text(text) => {
+                ris.push_str(&format!("TY  - GEN\nTI  - {}\nER  - \n\n", text.value));
+            }
+            _ => losses.add(reference.to_string()),
+        }
+    }
+
+    Ok((
+        ris,
+        EncodeInfo {
+            losses,
+            ..Default::default()
+        },
+    ))
+}
+
+/// Encode an [`Article`] reference as a RIS record
+fn encode_article(article: &Article) -> String {
+    let mut record = String::from("TY  - JOUR\n");
+
+    let mut tag = |tag: &str, value: String| {
+        record.push_str(tag);
+        record.push_str("  - ");
+        record.push_str(&value);
+        record.push('\n');
+    };
+
+    if let Some(id) = &article.id {
+        tag("ID", id.clone());
+    }
+
+    if let Some(title) = &article.title {
+        tag("TI", to_text(title));
+    }
+
+    for author in article.authors.iter().flatten() {
+        match author {
+            Author::Person(Person {
+                given_names,
+                family_names,
+                ..
+            }) => {
+                let family = family_names.as_ref().map(|names| names.join(" "));
+                let given = given_names.as_ref().map(|names| names.join(" "));
+                match (family, given) {
+                    (Some(family), Some(given)) => tag("AU", format!("{family}, {given}")),
+                    (Some(name), None) | (None, Some(name)) => tag("AU", name),
+                    (None, None) => {}
+                }
+            }
+            Author::Organization(organization) => {
+                if let Some(name) = &organization.name {
+                    tag("AU", name.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(date) = &article.date_published {
+        tag("PY", date.value.replace('-', "/"));
+    }
+
+    if let Some(CreativeWorkType::Periodical(periodical)) = &article.options.is_part_of {
+        if let Some(name) = &periodical.name {
+            tag("JO", name.clone());
+        }
+    }
+
+    if let Some(start) = &article.options.page_start {
+        tag("SP", integer_or_string(start));
+    }
+    if let Some(end) = &article.options.page_end {
+        tag("EP", integer_or_string(end));
+    }
+
+    if let Some(url) = &article.options.url {
+        tag("UR", url.clone());
+    }
+
+    if let Some(doi) = article
+        .options
+        .identifiers
+        .iter()
+        .flatten()
+        .find_map(|identifier| match identifier {
+            PropertyValueOrString::String(value) => value
+                .strip_prefix("https://doi.org/")
+                .map(|doi| doi.to_string()),
+            _ => None,
+        })
+    {
+        tag("DO", doi);
+    }
+
+    record.push_str("ER  - \n\n");
+    record
+}
+
+/// Convert a page identifier to an integer if possible
+fn page_to_integer_or_string(page: &str) -> IntegerOrString {
+    match page.parse() {
+        Ok(page) => IntegerOrString::Integer(page),
+        Err(..) => IntegerOrString::String(page.to_string()),
+    }
+}
+
+/// Convert an [`IntegerOrString`] to a string
+fn integer_or_string(value: &IntegerOrString) -> String {
+    match value {
+        IntegerOrString::Integer(value) => value.to_string(),
+        IntegerOrString::String(value) => value.clone(),
+    }
+}
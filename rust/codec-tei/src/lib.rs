@@ -0,0 +1,65 @@
+use codec::{
+    common::{async_trait::async_trait, eyre::Result},
+    format::Format,
+    schema::Node,
+    status::Status,
+    Codec, CodecSupport, EncodeInfo, EncodeOptions, NodeType,
+};
+use codec_pandoc::{pandoc_to_format, root_to_pandoc};
+
+/// A codec for TEI (Text Encoding Initiative) XML
+///
+/// Covers the TEI Lite subset produced by Pandoc: the header, `<div>`s
+/// derived from headings, figures, and bibliographic data. Encoding only;
+/// there is currently no support for decoding TEI.
+pub struct TeiCodec;
+
+const PANDOC_FORMAT: &str = "tei";
+
+#[async_trait]
+impl Codec for TeiCodec {
+    fn name(&self) -> &str {
+        "tei"
+    }
+
+    fn status(&self) -> Status {
+        Status::UnderDevelopment
+    }
+
+    fn supports_from_format(&self, _format: &Format) -> CodecSupport {
+        CodecSupport::None
+    }
+
+    fn supports_to_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Tei => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_from_type(&self, _node_type: NodeType) -> CodecSupport {
+        CodecSupport::None
+    }
+
+    fn supports_to_type(&self, _node_type: NodeType) -> CodecSupport {
+        CodecSupport::LowLoss
+    }
+
+    async fn to_string(
+        &self,
+        node: &Node,
+        options: Option<EncodeOptions>,
+    ) -> Result<(String, EncodeInfo)> {
+        let (pandoc, info) = root_to_pandoc(node)?;
+        let output = pandoc_to_format(
+            &pandoc,
+            None,
+            PANDOC_FORMAT,
+            options
+                .map(|options| options.passthrough_args)
+                .unwrap_or_default(),
+        )
+        .await?;
+        Ok((output, info))
+    }
+}
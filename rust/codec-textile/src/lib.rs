@@ -0,0 +1,63 @@
+use codec::{
+    common::{async_trait::async_trait, eyre::Result},
+    format::Format,
+    schema::Node,
+    status::Status,
+    Codec, CodecSupport, DecodeInfo, DecodeOptions, NodeType,
+};
+use codec_pandoc::{pandoc_from_format, root_from_pandoc};
+
+/// A codec for Textile markup
+///
+/// Decoding only, for migrating content from older Redmine and Jira wikis
+/// into Stencila documents.
+pub struct TextileCodec;
+
+const PANDOC_FORMAT: &str = "textile";
+
+#[async_trait]
+impl Codec for TextileCodec {
+    fn name(&self) -> &str {
+        "textile"
+    }
+
+    fn status(&self) -> Status {
+        Status::UnderDevelopment
+    }
+
+    fn supports_from_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Textile => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_to_format(&self, _format: &Format) -> CodecSupport {
+        CodecSupport::None
+    }
+
+    fn supports_from_type(&self, _node_type: NodeType) -> CodecSupport {
+        CodecSupport::LowLoss
+    }
+
+    fn supports_to_type(&self, _node_type: NodeType) -> CodecSupport {
+        CodecSupport::None
+    }
+
+    async fn from_str(
+        &self,
+        input: &str,
+        options: Option<DecodeOptions>,
+    ) -> Result<(Node, DecodeInfo)> {
+        let pandoc = pandoc_from_format(
+            input,
+            None,
+            PANDOC_FORMAT,
+            options
+                .map(|options| options.passthrough_args)
+                .unwrap_or_default(),
+        )
+        .await?;
+        root_from_pandoc(pandoc)
+    }
+}
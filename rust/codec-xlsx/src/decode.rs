@@ -0,0 +1,262 @@
+use std::{collections::HashMap, fs::File, io::Read, path::Path};
+
+use roxmltree::{Document, Node as XmlNode};
+
+use codec::{
+    common::{
+        eyre::{bail, Result},
+        zip::ZipArchive,
+    },
+    schema::{Collection, CreativeWorkType, Datatable, DatatableColumn, Node, Primitive},
+    DecodeInfo, DecodeOptions,
+};
+
+/// Decode an XLSX file to a Stencila [`Node`]
+///
+/// Reads the workbook, relationships, shared strings, and worksheet entries
+/// of the XLSX Zip archive and decodes each sheet to a [`Datatable`] named
+/// after the sheet, treating the first row as column names. A single sheet
+/// becomes a `Datatable` node; multiple sheets become a [`Collection`] with
+/// one `Datatable` per sheet. The `sheets` option can be used to select
+/// sheets by name.
+pub(super) fn decode_path(
+    path: &Path,
+    options: Option<DecodeOptions>,
+) -> Result<(Node, DecodeInfo)> {
+    if !path.exists() {
+        bail!("Path `{}` does not exist", path.display());
+    }
+
+    let sheets = options.map(|options| options.sheets).unwrap_or_default();
+
+    let file = File::open(path)?;
+    let mut zip = ZipArchive::new(file)?;
+
+    // The name and relationship id of each sheet, in workbook order
+    let workbook = read_entry(&mut zip, "xl/workbook.xml")?;
+    let workbook = Document::parse(&workbook)?;
+    let sheet_ids: Vec<(String, String)> = workbook
+        .descendants()
+        .filter(|node| node.has_tag_name("sheet"))
+        .filter_map(|sheet| {
+            let name = attribute(&sheet, "name")?;
+            let id = attribute(&sheet, "id")?;
+            Some((name.to_string(), id.to_string()))
+        })
+        .collect();
+
+    // The target path of each relationship id
+    let rels = read_entry(&mut zip, "xl/_rels/workbook.xml.rels")?;
+    let rels = Document::parse(&rels)?;
+    let targets: HashMap<String, String> = rels
+        .descendants()
+        .filter(|node| node.has_tag_name("Relationship"))
+        .filter_map(|rel| {
+            let id = attribute(&rel, "Id")?;
+            let target = attribute(&rel, "Target")?;
+            Some((id.to_string(), target.to_string()))
+        })
+        .collect();
+
+    // Shared strings, referred to by index from cells
+    let strings: Vec<String> = match read_entry(&mut zip, "xl/sharedStrings.xml") {
+        Ok(shared) => Document::parse(&shared)?
+            .descendants()
+            .filter(|node| node.has_tag_name("si"))
+            .map(|si| text(&si))
+            .collect(),
+        Err(..) => Vec::new(),
+    };
+
+    let mut datatables = Vec::new();
+    for (name, id) in sheet_ids {
+        if !sheets.is_empty() && !sheets.iter().any(|sheet| sheet == &name) {
+            continue;
+        }
+
+        let Some(target) = targets.get(&id) else {
+            continue;
+        };
+        let target = match target.strip_prefix('/') {
+            Some(target) => target.to_string(),
+            None => format!("xl/{target}"),
+        };
+
+        let sheet = read_entry(&mut zip, &target)?;
+        let sheet = Document::parse(&sheet)?;
+        datatables.push(decode_sheet(&sheet, &name, &strings));
+    }
+
+    if datatables.is_empty() {
+        if sheets.is_empty() {
+            bail!("Workbook does not have any sheets")
+        } else {
+            bail!("Workbook does not have any sheets matching the `sheets` option")
+        }
+    }
+
+    let node = if datatables.len() == 1 {
+        Node::Datatable(datatables.swap_remove(0))
+    } else {
+        Node::Collection(Collection::new(
+            datatables
+                .into_iter()
+                .map(CreativeWorkType::Datatable)
+                .collect(),
+        ))
+    };
+
+    Ok((node, DecodeInfo::none()))
+}
+
+/// Read an entry of the XLSX Zip archive to a string
+fn read_entry(zip: &mut ZipArchive<File>, name: &str) -> Result<String> {
+    let mut content = String::new();
+    zip.by_name(name)?.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/// Decode a worksheet to a [`Datatable`] named after the sheet
+fn decode_sheet(sheet: &Document, name: &str, strings: &[String]) -> Datatable {
+    let mut rows: Vec<Vec<Primitive>> = Vec::new();
+    for row in sheet.descendants().filter(|node| node.has_tag_name("row")) {
+        // Pad up to the row indicated by the row reference so that empty
+        // rows, which are not in the XML, are not skipped over
+        if let Some(index) = attribute(&row, "r").and_then(|r| r.parse::<usize>().ok()) {
+            while rows.len() + 1 < index {
+                rows.push(Vec::new());
+            }
+        }
+
+        let mut cells: Vec<Primitive> = Vec::new();
+        for cell in row.children().filter(|node| node.has_tag_name("c")) {
+            // Pad up to the column indicated by the cell reference so that
+            // empty cells, which are not in the XML, are not skipped over
+            if let Some(index) = attribute(&cell, "r").and_then(column_index) {
+                while cells.len() < index {
+                    cells.push(Primitive::Null(Default::default()));
+                }
+            }
+            cells.push(decode_cell(&cell, strings));
+        }
+
+        // Trim trailing empty cells
+        while cells.last().map_or(false, is_null) {
+            cells.pop();
+        }
+
+        rows.push(cells);
+    }
+
+    // Trim trailing empty rows
+    while rows.last().map_or(false, |row| row.iter().all(is_null)) {
+        rows.pop();
+    }
+
+    let names = rows.first().cloned().unwrap_or_default();
+    let columns = names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let values = rows
+                .iter()
+                .skip(1)
+                .map(|row| {
+                    row.get(index)
+                        .cloned()
+                        .unwrap_or(Primitive::Null(Default::default()))
+                })
+                .collect();
+            DatatableColumn::new(primitive_to_string(name), values)
+        })
+        .collect();
+
+    let mut datatable = Datatable::new(columns);
+    datatable.options.name = Some(name.to_string());
+    datatable
+}
+
+/// Decode a `<c>` (cell) element to a [`Primitive`] value
+fn decode_cell(cell: &XmlNode, strings: &[String]) -> Primitive {
+    let value = cell
+        .children()
+        .find(|node| node.has_tag_name("v"))
+        .and_then(|value| value.text())
+        .unwrap_or_default();
+
+    match attribute(cell, "t").unwrap_or("n") {
+        // Shared string
+        "s" => value
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| strings.get(index))
+            .map(|string| Primitive::String(string.clone()))
+            .unwrap_or(Primitive::Null(Default::default())),
+        // Boolean
+        "b" => Primitive::Boolean(value == "1"),
+        // Inline string
+        "inlineStr" => {
+            let string = text(cell);
+            if string.is_empty() {
+                Primitive::Null(Default::default())
+            } else {
+                Primitive::String(string)
+            }
+        }
+        // Formula string or error
+        "str" | "e" => Primitive::String(value.to_string()),
+        // Number (the default)
+        _ => match value.parse::<f64>() {
+            Ok(number) if number.fract() == 0.0 && number.abs() < (i64::MAX as f64) => {
+                Primitive::Integer(number as i64)
+            }
+            Ok(number) => Primitive::Number(number),
+            Err(..) => Primitive::Null(Default::default()),
+        },
+    }
+}
+
+/// Get the zero-based column index from a cell reference e.g. `B2` -> 1
+fn column_index(reference: &str) -> Option<usize> {
+    let mut index = 0usize;
+    let mut letters = false;
+    for char in reference.chars() {
+        if !char.is_ascii_alphabetic() {
+            break;
+        }
+        index = index * 26 + (char.to_ascii_uppercase() as usize - 'A' as usize + 1);
+        letters = true;
+    }
+    letters.then(|| index - 1)
+}
+
+/// Get the concatenated text content of an element's descendants
+fn text(node: &XmlNode) -> String {
+    node.descendants()
+        .filter_map(|node| node.text())
+        .collect::<Vec<&str>>()
+        .join("")
+}
+
+/// Get an attribute of an element by its local name (ignoring namespace prefix)
+fn attribute<'a>(node: &'a XmlNode, name: &str) -> Option<&'a str> {
+    node.attributes()
+        .find(|attribute| attribute.name() == name)
+        .map(|attribute| attribute.value())
+}
+
+/// Is a primitive a null value?
+fn is_null(value: &Primitive) -> bool {
+    matches!(value, Primitive::Null(..))
+}
+
+/// Convert a primitive to a string for use as a column name
+fn primitive_to_string(value: &Primitive) -> String {
+    match value {
+        Primitive::String(value) => value.clone(),
+        Primitive::Integer(value) => value.to_string(),
+        Primitive::Number(value) => value.to_string(),
+        Primitive::Boolean(value) => value.to_string(),
+        _ => String::new(),
+    }
+}
@@ -0,0 +1,217 @@
+use std::{fs, fs::File, io::Write, path::Path};
+
+use codec::{
+    common::{
+        eyre::{bail, Result},
+        serde_json,
+        zip::{write::FileOptions, ZipWriter},
+    },
+    schema::{CreativeWorkType, Datatable, Node, Primitive},
+    EncodeInfo, EncodeOptions,
+};
+
+/// Encode a Stencila [`Node`] to an XLSX file
+///
+/// A [`Datatable`] node becomes a single sheet with the column names as the
+/// first row. A [`Collection`] of datatables becomes a workbook with one
+/// sheet per datatable, named after the datatable.
+pub(super) fn encode_path(
+    node: &Node,
+    path: &Path,
+    _options: Option<EncodeOptions>,
+) -> Result<EncodeInfo> {
+    let sheets: Vec<(String, &Datatable)> = match node {
+        Node::Datatable(datatable) => vec![(sheet_name(datatable, 0), datatable)],
+        Node::Collection(collection) => collection
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                CreativeWorkType::Datatable(datatable) => Some(datatable),
+                _ => None,
+            })
+            .enumerate()
+            .map(|(index, datatable)| (sheet_name(datatable, index), datatable))
+            .collect(),
+        _ => bail!("Unable to encode a `{node}` to XLSX"),
+    };
+    if sheets.is_empty() {
+        bail!("Unable to encode a `{node}` to XLSX: no datatables")
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file = File::create(path)?;
+    let mut zip = ZipWriter::new(file);
+
+    zip.start_file("[Content_Types].xml", FileOptions::default())?;
+    zip.write_all(content_types(sheets.len()).as_bytes())?;
+
+    zip.start_file("_rels/.rels", FileOptions::default())?;
+    zip.write_all(RELS.as_bytes())?;
+
+    zip.start_file("xl/workbook.xml", FileOptions::default())?;
+    zip.write_all(workbook(&sheets).as_bytes())?;
+
+    zip.start_file("xl/_rels/workbook.xml.rels", FileOptions::default())?;
+    zip.write_all(workbook_rels(sheets.len()).as_bytes())?;
+
+    for (index, (.., datatable)) in sheets.iter().enumerate() {
+        zip.start_file(
+            format!("xl/worksheets/sheet{}.xml", index + 1),
+            FileOptions::default(),
+        )?;
+        zip.write_all(worksheet(datatable).as_bytes())?;
+    }
+
+    zip.finish()?;
+
+    Ok(EncodeInfo::none())
+}
+
+/// The `_rels/.rels` entry relating the package to the workbook
+const RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+ <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>
+</Relationships>
+"#;
+
+/// Get the name for a sheet, defaulting to `Sheet<number>`
+fn sheet_name(datatable: &Datatable, index: usize) -> String {
+    datatable
+        .options
+        .name
+        .clone()
+        .unwrap_or_else(|| format!("Sheet{}", index + 1))
+}
+
+/// Generate the `[Content_Types].xml` entry
+fn content_types(sheets: usize) -> String {
+    let mut overrides = String::new();
+    for index in 1..=sheets {
+        overrides.push_str(&format!(
+            " <Override PartName=\"/xl/worksheets/sheet{index}.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml\"/>\n"
+        ));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+ <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+ <Default Extension="xml" ContentType="application/xml"/>
+ <Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>
+{overrides}</Types>
+"#
+    )
+}
+
+/// Generate the `xl/workbook.xml` entry listing the sheets
+fn workbook(sheets: &[(String, &Datatable)]) -> String {
+    let mut entries = String::new();
+    for (index, (name, ..)) in sheets.iter().enumerate() {
+        let index = index + 1;
+        entries.push_str(&format!(
+            "  <sheet name=\"{}\" sheetId=\"{index}\" r:id=\"rId{index}\"/>\n",
+            escape(name)
+        ));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+ <sheets>
+{entries} </sheets>
+</workbook>
+"#
+    )
+}
+
+/// Generate the `xl/_rels/workbook.xml.rels` entry relating sheets to worksheets
+fn workbook_rels(sheets: usize) -> String {
+    let mut entries = String::new();
+    for index in 1..=sheets {
+        entries.push_str(&format!(
+            " <Relationship Id=\"rId{index}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet\" Target=\"worksheets/sheet{index}.xml\"/>\n"
+        ));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+{entries}</Relationships>
+"#
+    )
+}
+
+/// Generate a `xl/worksheets/sheet<number>.xml` entry for a [`Datatable`]
+///
+/// Strings are written as inline strings so that a shared strings entry is
+/// not needed.
+fn worksheet(datatable: &Datatable) -> String {
+    let rows_count = datatable
+        .columns
+        .iter()
+        .map(|column| column.values.len())
+        .max()
+        .unwrap_or_default();
+
+    let mut rows = String::new();
+
+    // Header row of column names
+    rows.push_str("  <row>\n");
+    for column in &datatable.columns {
+        rows.push_str(&string_cell(&column.name));
+    }
+    rows.push_str("  </row>\n");
+
+    // Value rows
+    for index in 0..rows_count {
+        rows.push_str("  <row>\n");
+        for column in &datatable.columns {
+            rows.push_str(&match column.values.get(index) {
+                Some(Primitive::Integer(value)) => number_cell(&value.to_string()),
+                Some(Primitive::UnsignedInteger(value)) => number_cell(&value.to_string()),
+                Some(Primitive::Number(value)) => number_cell(&value.to_string()),
+                Some(Primitive::Boolean(value)) => boolean_cell(*value),
+                Some(Primitive::String(value)) => string_cell(value),
+                Some(value) => string_cell(&serde_json::to_string(value).unwrap_or_default()),
+                None => "   <c/>\n".to_string(),
+            });
+        }
+        rows.push_str("  </row>\n");
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+ <sheetData>
+{rows} </sheetData>
+</worksheet>
+"#
+    )
+}
+
+/// Generate a cell with a number value
+fn number_cell(value: &str) -> String {
+    format!("   <c><v>{value}</v></c>\n")
+}
+
+/// Generate a cell with a boolean value
+fn boolean_cell(value: bool) -> String {
+    format!("   <c t=\"b\"><v>{}</v></c>\n", value as u8)
+}
+
+/// Generate a cell with an inline string value
+fn string_cell(value: &str) -> String {
+    format!("   <c t=\"inlineStr\"><is><t>{}</t></is></c>\n", escape(value))
+}
+
+/// Escape a string for use in XML text content or attribute values
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use codec::{
+    common::{async_trait::async_trait, eyre::Result},
+    format::Format,
+    schema::Node,
+    status::Status,
+    Codec, CodecSupport, DecodeInfo, DecodeOptions, EncodeInfo, EncodeOptions, NodeType,
+};
+
+mod decode;
+mod encode;
+
+#[cfg(test)]
+mod tests;
+
+/// A codec for Microsoft Excel workbooks
+///
+/// Decodes each sheet of a workbook to a [`Datatable`] (with the first row
+/// treated as column names). A workbook with a single sheet becomes a
+/// `Datatable` node; one with several sheets becomes a [`Collection`] with
+/// one named `Datatable` per sheet. The `sheets` option can be used to
+/// select sheets by name. Encodes a `Datatable` or `Collection` back to a
+/// workbook.
+pub struct XlsxCodec;
+
+#[async_trait]
+impl Codec for XlsxCodec {
+    fn name(&self) -> &str {
+        "xlsx"
+    }
+
+    fn status(&self) -> Status {
+        Status::UnderDevelopment
+    }
+
+    fn supports_from_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Xlsx => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_to_format(&self, format: &Format) -> CodecSupport {
+        match format {
+            Format::Xlsx => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_from_type(&self, node_type: NodeType) -> CodecSupport {
+        match node_type {
+            NodeType::Datatable | NodeType::Collection => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_to_type(&self, node_type: NodeType) -> CodecSupport {
+        match node_type {
+            NodeType::Datatable | NodeType::Collection => CodecSupport::LowLoss,
+            _ => CodecSupport::None,
+        }
+    }
+
+    fn supports_from_string(&self) -> bool {
+        false
+    }
+
+    fn supports_to_string(&self) -> bool {
+        false
+    }
+
+    async fn from_path(
+        &self,
+        path: &Path,
+        options: Option<DecodeOptions>,
+    ) -> Result<(Node, DecodeInfo)> {
+        decode::decode_path(path, options)
+    }
+
+    async fn to_path(
+        &self,
+        node: &Node,
+        path: &Path,
+        options: Option<EncodeOptions>,
+    ) -> Result<EncodeInfo> {
+        encode::encode_path(node, path, options)
+    }
+}
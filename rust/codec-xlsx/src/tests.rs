@@ -0,0 +1,126 @@
+use std::{fs::File, io::Write};
+
+use codec::{
+    common::{
+        tempfile,
+        tokio,
+        zip::{write::FileOptions, ZipWriter},
+    },
+    schema::{Datatable, DatatableColumn, Primitive},
+};
+use common_dev::pretty_assertions::assert_eq;
+
+use super::*;
+
+/// Decode a worksheet with omitted rows and cells
+///
+/// Excel omits entirely empty rows and cells from the XML so these must be
+/// padded using the `r` references of rows and cells to keep later values
+/// in the correct row and column.
+#[tokio::test]
+async fn gaps() -> Result<()> {
+    const WORKBOOK: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+ <sheets>
+  <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
+ </sheets>
+</workbook>
+"#;
+
+    const RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+ <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>
+</Relationships>
+"#;
+
+    const SHEET: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+ <sheetData>
+  <row r="1">
+   <c r="A1" t="inlineStr"><is><t>a</t></is></c>
+   <c r="B1" t="inlineStr"><is><t>b</t></is></c>
+   <c r="C1" t="inlineStr"><is><t>c</t></is></c>
+  </row>
+  <row r="2">
+   <c r="A2"><v>1</v></c>
+   <c r="C2"><v>3</v></c>
+  </row>
+  <row r="4">
+   <c r="A4"><v>4</v></c>
+  </row>
+ </sheetData>
+</worksheet>
+"#;
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("gaps.xlsx");
+
+    let file = File::create(&path)?;
+    let mut zip = ZipWriter::new(file);
+    zip.start_file("xl/workbook.xml", FileOptions::default())?;
+    zip.write_all(WORKBOOK.as_bytes())?;
+    zip.start_file("xl/_rels/workbook.xml.rels", FileOptions::default())?;
+    zip.write_all(RELS.as_bytes())?;
+    zip.start_file("xl/worksheets/sheet1.xml", FileOptions::default())?;
+    zip.write_all(SHEET.as_bytes())?;
+    zip.finish()?;
+
+    let codec = XlsxCodec {};
+    let (node, ..) = codec.from_path(&path, None).await?;
+
+    let Node::Datatable(datatable) = node else {
+        unreachable!()
+    };
+
+    let null = || Primitive::Null(Default::default());
+
+    assert_eq!(datatable.columns.len(), 3);
+    assert_eq!(datatable.columns[0].name, "a");
+    assert_eq!(
+        datatable.columns[0].values,
+        vec![Primitive::Integer(1), null(), Primitive::Integer(4)]
+    );
+    assert_eq!(datatable.columns[1].values, vec![null(), null(), null()]);
+    assert_eq!(
+        datatable.columns[2].values,
+        vec![Primitive::Integer(3), null(), null()]
+    );
+
+    Ok(())
+}
+
+/// Round-trip a datatable through an XLSX file
+#[tokio::test]
+async fn round_trip() -> Result<()> {
+    let codec = XlsxCodec {};
+
+    let mut datatable = Datatable::new(vec![
+        DatatableColumn::new(
+            "name".to_string(),
+            vec![
+                Primitive::String("a".to_string()),
+                Primitive::String("b".to_string()),
+            ],
+        ),
+        DatatableColumn::new(
+            "count".to_string(),
+            vec![Primitive::Integer(1), Primitive::Integer(2)],
+        ),
+        DatatableColumn::new(
+            "flag".to_string(),
+            vec![Primitive::Boolean(true), Primitive::Boolean(false)],
+        ),
+    ]);
+    datatable.options.name = Some("Data".to_string());
+    let node = Node::Datatable(datatable);
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("round_trip.xlsx");
+
+    codec.to_path(&node, &path, None).await?;
+    let (decoded, ..) = codec.from_path(&path, None).await?;
+
+    assert_eq!(decoded, node);
+
+    Ok(())
+}
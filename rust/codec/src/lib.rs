@@ -370,6 +370,59 @@ pub struct DecodeOptions {
     /// Decode in strict mode for the format
     pub strict: Option<bool>,
 
+    /// Decode only the coarse structure of the document
+    ///
+    /// Supported by some codecs (e.g. Markdown) to decode only headings,
+    /// block boundaries, and metadata, skipping inline parsing and large
+    /// data. Much faster for large documents and useful for purposes, such
+    /// as outlining and search indexing, that only need document structure.
+    pub coarse: Option<bool>,
+
+    /// The path of a file of macro definitions to read before decoding
+    ///
+    /// Used by some codecs (e.g. LaTeX) to expand custom commands defined in
+    /// a preamble or macros file, rather than leaving them as raw fragments.
+    pub macros: Option<PathBuf>,
+
+    /// The delimiter between fields when decoding tabular data
+    ///
+    /// Used by the CSV codec. Defaults to a comma for CSV and a tab for TSV.
+    pub delimiter: Option<char>,
+
+    /// The quote character used around fields when decoding tabular data
+    ///
+    /// Used by the CSV codec. Defaults to a double quote.
+    pub quote: Option<char>,
+
+    /// Whether the first row of tabular data is a header row of column names
+    ///
+    /// Used by the CSV codec. Defaults to `true`; when `false` column names
+    /// are generated (e.g. "Column 1").
+    pub header: Option<bool>,
+
+    /// The types of columns when decoding tabular data
+    ///
+    /// Used by the CSV codec. Each entry should have the form `name=type`
+    /// (or `index=type` when there is no header row) where type is one of
+    /// `boolean`, `integer`, `number`, or `string`. The types of columns
+    /// not listed are inferred from their values.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub column_types: Vec<String>,
+
+    /// The decimal separator used when parsing numbers from tabular data
+    ///
+    /// Used by the CSV codec for locale-aware number parsing (e.g. use `,`
+    /// for data where `1.234,56` means one thousand, two hundred and
+    /// thirty-four point five six). Defaults to a period.
+    pub decimal_separator: Option<char>,
+
+    /// The names of the sheets to decode from a workbook
+    ///
+    /// Used by spreadsheet codecs (e.g. XLSX, ODS). When empty, all sheets
+    /// are decoded.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sheets: Vec<String>,
+
     /// The response to take when there are losses in the decoding
     #[default(_code = "LossesResponse::Warn")]
     pub losses: LossesResponse,
@@ -403,6 +456,14 @@ pub struct EncodeOptions {
     /// This option is only supported by some formats.
     pub render: Option<bool>,
 
+    /// Whether to pre-render math to MathML
+    ///
+    /// When this option is `true`, the TeX code of `MathBlock` and `MathInline`
+    /// nodes that do not already have MathML is transpiled to MathML before
+    /// encoding. This avoids the need for client-side rendering of math
+    /// (e.g. using KaTeX or MathJax) in published documents.
+    pub render_math: Option<bool>,
+
     /// Whether to encode as a standalone document
     ///
     /// Unless specified otherwise, this is the default when encoding to a file
@@ -428,6 +489,9 @@ pub struct EncodeOptions {
     /// The theme to use when encoding
     ///
     /// Use this option to specify the theme form HTML and HTML-based formats (e.g. PDF).
+    /// May be the name of a built-in theme (e.g. `default`, `stencila`, `latex`,
+    /// `tufte`), the path of a local CSS file, or the name of a CSS file in a
+    /// `themes` directory beside the document.
     pub theme: Option<String>,
 
     /// The path of the document being encoded from
@@ -3,6 +3,7 @@ use std::path::{Path, PathBuf};
 use codec::{
     common::{
         eyre::{bail, eyre, Result},
+        futures::{stream, StreamExt, TryStreamExt},
         reqwest::Client,
         tracing,
     },
@@ -16,15 +17,22 @@ pub use codec::{
 use node_strip::{StripNode, StripTargets};
 
 pub mod cli;
+mod math;
 
 /// Get a list of all codecs
 pub fn list() -> Vec<Box<dyn Codec>> {
-    let codecs = vec![
-        Box::new(codec_cbor::CborCodec) as Box<dyn Codec>,
+    let mut codecs = vec![
+        Box::new(codec_arrow::ArrowCodec) as Box<dyn Codec>,
+        Box::new(codec_bibtex::BibtexCodec),
+        Box::new(codec_cbor::CborCodec),
+        Box::new(codec_confluence::ConfluenceCodec),
+        Box::new(codec_csl::CslCodec),
+        Box::new(codec_csv::CsvCodec),
         Box::new(codec_debug::DebugCodec),
         Box::new(codec_docx::DocxCodec),
         Box::new(codec_dom::DomCodec),
         Box::new(codec_directory::DirectoryCodec),
+        Box::new(codec_geojson::GeoJsonCodec),
         Box::new(codec_html::HtmlCodec),
         Box::new(codec_ipynb::IpynbCodec),
         Box::new(codec_jats::JatsCodec),
@@ -33,17 +41,24 @@ pub fn list() -> Vec<Box<dyn Codec>> {
         Box::new(codec_jsonld::JsonLdCodec),
         Box::new(codec_latex::LatexCodec),
         Box::new(codec_markdown::MarkdownCodec),
+        Box::new(codec_mediawiki::MediawikiCodec),
+        Box::new(codec_ods::OdsCodec),
         Box::new(codec_odt::OdtCodec),
         Box::new(codec_pandoc::PandocCodec),
         Box::new(codec_pdf::PdfCodec),
+        Box::new(codec_pptx::PptxCodec),
+        Box::new(codec_ris::RisCodec),
         Box::<codec_swb::SwbCodec>::default(),
+        Box::new(codec_tei::TeiCodec),
         Box::new(codec_text::TextCodec),
+        Box::new(codec_textile::TextileCodec),
+        Box::new(codec_typst::TypstCodec),
+        Box::new(codec_xlsx::XlsxCodec),
         Box::new(codec_yaml::YamlCodec),
     ];
 
-    // TODO: make plugins a dependency and append codecs to list
-    //let provided_by_plugins = &mut plugins::codecs::list();
-    //codecs.append(provided_by_plugins);
+    // Add codecs provided by installed and enabled plugins
+    codecs.append(&mut plugins::codecs::list_installed());
 
     codecs
 }
@@ -208,6 +223,35 @@ pub async fn from_path_with_info(
         .await
 }
 
+/// Decode Stencila Schema nodes from several file system paths concurrently
+///
+/// Decodes up to `max_concurrency` (defaulting to the available parallelism)
+/// paths at a time, so that directory-level operations such as batch
+/// conversion and site publishing are not limited by decoding files one at a
+/// time. The returned nodes are in the same order as `paths`, regardless of
+/// the order in which decoding completes.
+#[tracing::instrument(skip(paths))]
+pub async fn from_paths(
+    paths: &[PathBuf],
+    options: Option<DecodeOptions>,
+    max_concurrency: Option<usize>,
+) -> Result<Vec<Node>> {
+    let max_concurrency = max_concurrency.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|parallelism| parallelism.get())
+            .unwrap_or(1)
+    });
+
+    stream::iter(paths)
+        .map(|path| {
+            let options = options.clone();
+            async move { from_path(path, options).await }
+        })
+        .buffered(max_concurrency.max(1))
+        .try_collect()
+        .await
+}
+
 /// Decode a Stencila Schema node from `stdin`
 #[tracing::instrument]
 pub async fn from_stdin(options: Option<DecodeOptions>) -> Result<Node> {
@@ -259,18 +303,11 @@ pub async fn to_string_with_info(
         ..options.unwrap_or_default()
     });
 
-    if let Some(EncodeOptions {
-        strip_scopes,
-        strip_types,
-        strip_props,
-        ..
-    }) = options.clone()
+    if let Some(node) = options
+        .as_ref()
+        .and_then(|options| transformed(node, options))
     {
-        if !(strip_scopes.is_empty() && strip_types.is_empty() && strip_props.is_empty()) {
-            let mut node = node.clone();
-            node.strip(&StripTargets::new(strip_scopes, strip_types, strip_props));
-            return codec.to_string(&node, options).await;
-        }
+        return codec.to_string(&node, options).await;
     }
 
     codec.to_string(node, options).await
@@ -311,23 +348,48 @@ pub async fn to_path_with_info(
         ..options.unwrap_or_default()
     });
 
-    if let Some(EncodeOptions {
-        strip_scopes,
-        strip_types,
-        strip_props,
-        ..
-    }) = options.clone()
+    if let Some(node) = options
+        .as_ref()
+        .and_then(|options| transformed(node, options))
     {
-        if !(strip_scopes.is_empty() && strip_types.is_empty() && strip_props.is_empty()) {
-            let mut node = node.clone();
-            node.strip(&StripTargets::new(strip_scopes, strip_types, strip_props));
-            return codec.to_path(&node, path, options).await;
-        }
+        return codec.to_path(&node, path, options).await;
     }
 
     codec.to_path(node, path, options).await
 }
 
+/// Apply any transformations to a node that are required by encode options
+///
+/// Returns a transformed clone of the node if any of the options requiring
+/// a transformation are set, otherwise `None`, so that the node does not
+/// need to be cloned.
+fn transformed(node: &Node, options: &EncodeOptions) -> Option<Node> {
+    let strip = !(options.strip_scopes.is_empty()
+        && options.strip_types.is_empty()
+        && options.strip_props.is_empty());
+    let render_math = options.render_math.unwrap_or_default();
+
+    if !(strip || render_math) {
+        return None;
+    }
+
+    let mut node = node.clone();
+
+    if strip {
+        node.strip(&StripTargets::new(
+            options.strip_scopes.clone(),
+            options.strip_types.clone(),
+            options.strip_props.clone(),
+        ));
+    }
+
+    if render_math {
+        math::render(&mut node);
+    }
+
+    Some(node)
+}
+
 /// Convert a document from one format to another
 #[tracing::instrument]
 pub async fn convert(
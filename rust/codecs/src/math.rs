@@ -0,0 +1,60 @@
+use codec::{
+    common::{once_cell::sync::Lazy, regex::Regex},
+    schema::{Block, Inline, Node, VisitorMut, WalkControl},
+};
+use latex2mathml::{latex_to_mathml, DisplayStyle};
+
+/// Pre-render math nodes within a node to MathML
+///
+/// Walks the node and, for any `MathBlock` or `MathInline` whose math language
+/// is TeX and which does not already have MathML (e.g. because the document
+/// has not been executed), transpiles the TeX to MathML. Transpilation errors
+/// are ignored so that the math code itself is still encoded.
+pub(crate) fn render(node: &mut Node) {
+    Renderer.visit(node);
+}
+
+struct Renderer;
+
+impl VisitorMut for Renderer {
+    fn visit_block(&mut self, block: &mut Block) -> WalkControl {
+        if let Block::MathBlock(math) = block {
+            if math.options.mathml.is_none() && is_tex(math.math_language.as_deref()) {
+                math.options.mathml = tex_to_mathml(&math.code, DisplayStyle::Block);
+            }
+        }
+
+        WalkControl::Continue
+    }
+
+    fn visit_inline(&mut self, inline: &mut Inline) -> WalkControl {
+        if let Inline::MathInline(math) = inline {
+            if math.options.mathml.is_none() && is_tex(math.math_language.as_deref()) {
+                math.options.mathml = tex_to_mathml(&math.code, DisplayStyle::Inline);
+            }
+        }
+
+        WalkControl::Continue
+    }
+}
+
+/// Whether a math language is TeX (the default when none is specified)
+fn is_tex(lang: Option<&str>) -> bool {
+    lang.map_or(true, |lang| {
+        matches!(lang.to_lowercase().as_str(), "tex" | "latex" | "math")
+    })
+}
+
+/// Transpile TeX to MathML
+///
+/// Returns `None` if transpilation fails, including when errors are embedded
+/// in the generated MathML (as `latex2mathml` does for most errors).
+fn tex_to_mathml(tex: &str, style: DisplayStyle) -> Option<String> {
+    static ERROR_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"\[PARSE ERROR: .*?\]").expect("invalid regex"));
+
+    match latex_to_mathml(tex, style) {
+        Ok(mathml) if !ERROR_REGEX.is_match(&mathml) => Some(mathml),
+        _ => None,
+    }
+}
@@ -0,0 +1,57 @@
+//! Benchmark scaffolding for codecs and kernels
+//!
+//! Provides shared, deterministic, large-document fixtures for use in
+//! `criterion` benchmarks so that performance regressions in encoding,
+//! decoding, patch application and kernel round-trips can be caught by
+//! comparing benchmark runs.
+//!
+//! Use in a crate's `benches` directory with `harness = false` e.g.
+//!
+//! ```ignore
+//! use common_dev::{bench::large_article, criterion::{criterion_group, criterion_main, Criterion}};
+//! ```
+
+use codec::schema::{
+    shortcuts::{cb, cc, em, h2, li, ol, p, stg, t, tbl, td, th, tr},
+    Article, Node,
+};
+
+/// Generate a large, deterministic article for benchmarking
+///
+/// The article contains `sections` repeated sections, each with a heading,
+/// paragraphs with inline formatting, a code block, an executable code chunk,
+/// a list, and a table. With the default of 100 sections this produces a
+/// document of several hundred kilobytes when encoded to Markdown.
+pub fn large_article(sections: usize) -> Node {
+    let mut content = Vec::with_capacity(sections * 7);
+    for section in 0..sections {
+        content.push(h2([t(format!("Section {section}"))]));
+        content.push(p([
+            t("Some text with "),
+            em([t("emphasis")]),
+            t(" and "),
+            stg([t("strong")]),
+            t(format!(" content for section {section}.")),
+        ]));
+        content.push(p([t(
+            "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat.",
+        )]));
+        content.push(cb(
+            format!("let section = {section};\nconsole.log(section);"),
+            Some("javascript"),
+        ));
+        content.push(cc(format!("1 + {section}"), Some("python")));
+        content.push(ol([
+            li([t("First item")]),
+            li([t("Second item")]),
+            li([t("Third item")]),
+        ]));
+        content.push(tbl([
+            tr([th([t("Name")]), th([t("Value")])]),
+            tr([td([t("alpha")]), td([t(section.to_string())])]),
+            tr([td([t("beta")]), td([t((section * 2).to_string())])]),
+        ]));
+    }
+
+    Node::Article(Article::new(content))
+}
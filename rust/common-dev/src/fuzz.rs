@@ -0,0 +1,37 @@
+//! Helpers for turning fuzzing artifacts into regression tests
+//!
+//! Crashing inputs found by `cargo fuzz` should be minimized with
+//! `cargo fuzz tmin` and copied into a `tests/fuzz` directory of the
+//! decoder's crate. A test can then use [`regression`] to assert that
+//! none of them cause a panic.
+
+use std::{fs, path::Path};
+
+/// Run a function over each fuzzing artifact in a directory
+///
+/// The directory is relative to the `CARGO_MANIFEST_DIR` of the calling
+/// crate (usually `tests/fuzz`). Does nothing if the directory does not
+/// exist, so the test can be added before any artifacts are.
+///
+/// The function will usually call the crate's decoder, ignoring the result:
+/// the assertion is that decoding does not panic.
+pub fn regression(dir: impl AsRef<Path>, test: impl Fn(&str)) {
+    let dir = dir.as_ref();
+    if !dir.exists() {
+        return;
+    }
+
+    for entry in fs::read_dir(dir).expect("unable to read dir").flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        // Artifacts are arbitrary bytes so decode lossily
+        let bytes = fs::read(&path).expect("unable to read file");
+        let input = String::from_utf8_lossy(&bytes);
+
+        eprintln!("Testing `{}`", path.display());
+        test(&input);
+    }
+}
@@ -0,0 +1,54 @@
+//! Golden-file round-trip harness for codecs
+//!
+//! Round-trips each file in a directory of "golden" files through a codec
+//! (decode then encode) and asserts that the encoded output is the same as
+//! the content of the file. Used by codec crates to regression-test both
+//! decoding and encoding against curated examples.
+
+use codec::{
+    common::{eyre::Result, glob::glob},
+    Codec, DecodeOptions, EncodeOptions,
+};
+use pretty_assertions::assert_eq;
+
+/// Round-trip each golden file matching a glob pattern through a codec
+///
+/// The pattern is relative to the `CARGO_MANIFEST_DIR` of the calling crate
+/// e.g. `tests/golden/*.md`. Panics, with the path of the offending file,
+/// if the round-tripped output differs from the content of the file.
+pub async fn round_trip<C: Codec>(codec: &C, pattern: &str) -> Result<()> {
+    round_trip_with(codec, pattern, None, None).await
+}
+
+/// Round-trip each golden file matching a glob pattern through a codec
+/// with decoding and encoding options
+pub async fn round_trip_with<C: Codec>(
+    codec: &C,
+    pattern: &str,
+    decode_options: Option<DecodeOptions>,
+    encode_options: Option<EncodeOptions>,
+) -> Result<()> {
+    let mut count = 0;
+    for path in glob(pattern)?.flatten() {
+        let content = std::fs::read_to_string(&path)?;
+
+        let (node, ..) = codec.from_str(&content, decode_options.clone()).await?;
+        let (encoded, ..) = codec.to_string(&node, encode_options.clone()).await?;
+
+        assert_eq!(
+            encoded.trim_end(),
+            content.trim_end(),
+            "Round-trip of golden file `{}` is not equal",
+            path.display()
+        );
+
+        count += 1;
+    }
+
+    assert!(
+        count > 0,
+        "No golden files matched the pattern `{pattern}`"
+    );
+
+    Ok(())
+}
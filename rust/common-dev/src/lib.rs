@@ -2,7 +2,15 @@
 //!
 //! Similar to the sibling `common` crate but for dev dependencies.
 
+pub use criterion;
 pub use insta;
 pub use ntest;
 pub use pretty_assertions;
 pub use proptest;
+pub use tempfile;
+
+pub mod bench;
+pub mod fuzz;
+pub mod golden;
+pub mod snaps;
+pub mod workspace;
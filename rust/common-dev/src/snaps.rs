@@ -0,0 +1,57 @@
+//! Helpers for redacting unstable values from insta snapshots
+//!
+//! Node ids, timestamps, absolute paths, and durations differ between test
+//! runs and machines. These helpers add filters to [`insta::Settings`] that
+//! replace such values with stable placeholders so that snapshots are
+//! deterministic.
+//!
+//! Use an individual `redact_*` function to add a filter to existing
+//! settings, or [`redact_unstable`] for settings with all of them:
+//!
+//! ```ignore
+//! redact_unstable().bind(|| assert_json_snapshot!(node));
+//! ```
+
+use insta::Settings;
+
+/// Redact node ids e.g. `par_6dGCNWzPvjDSEqUq9cZ9fM`
+pub fn redact_node_ids(settings: &mut Settings) {
+    settings.add_filter(
+        r"\b[a-zA-Z]{3}_[1-9A-HJ-NP-Za-km-z]{15,30}\b",
+        "<node-id>",
+    );
+}
+
+/// Redact ISO 8601 timestamps e.g. `2024-09-05T01:02:03.456Z` and
+/// numeric `timeUnit`/`value` timestamp pairs
+pub fn redact_timestamps(settings: &mut Settings) {
+    settings.add_filter(
+        r"\b\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?(Z|[+-]\d{2}:\d{2})?\b",
+        "<timestamp>",
+    );
+    settings.add_filter(r#""value": \d{10,}"#, r#""value": <timestamp>"#);
+}
+
+/// Redact absolute paths, both POSIX and Windows
+pub fn redact_paths(settings: &mut Settings) {
+    settings.add_filter(r"(/[\w.@-]+){2,}", "<path>");
+    settings.add_filter(r"\b[A-Z]:(\\[\w.@-]+){2,}", "<path>");
+}
+
+/// Redact durations e.g. `123ms`, `1.5s`
+pub fn redact_durations(settings: &mut Settings) {
+    settings.add_filter(r"\b\d+(\.\d+)?\s*(ns|µs|us|ms|s)\b", "<duration>");
+}
+
+/// Create [`insta::Settings`] with all redaction filters added
+///
+/// Based on the current settings, so can be used within an existing
+/// `Settings::bind` scope.
+pub fn redact_unstable() -> Settings {
+    let mut settings = Settings::clone_current();
+    redact_node_ids(&mut settings);
+    redact_timestamps(&mut settings);
+    redact_paths(&mut settings);
+    redact_durations(&mut settings);
+    settings
+}
@@ -0,0 +1,128 @@
+//! Temporary workspace fixture for integration tests
+//!
+//! Builds a throwaway workspace directory with optional config files, sample
+//! documents, and an initialized git repository. The directory is deleted
+//! when the [`TempWorkspace`] is dropped.
+//!
+//! ```ignore
+//! let workspace = TempWorkspace::builder()
+//!     .config(r#"[document]"#)
+//!     .document("report.smd", "# Report")
+//!     .git()
+//!     .build()?;
+//! ```
+
+use std::{
+    fs::{create_dir_all, write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use tempfile::TempDir;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// A temporary workspace directory for integration tests
+pub struct TempWorkspace {
+    dir: TempDir,
+}
+
+impl TempWorkspace {
+    /// Create a builder for a temporary workspace
+    pub fn builder() -> TempWorkspaceBuilder {
+        TempWorkspaceBuilder::default()
+    }
+
+    /// The path of the workspace directory
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Write a file into the workspace, creating parent directories as needed
+    pub fn write(&self, path: impl AsRef<Path>, content: &str) -> Result<()> {
+        let path = self.dir.path().join(path.as_ref());
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        write(path, content)?;
+        Ok(())
+    }
+}
+
+/// A builder for a [`TempWorkspace`]
+#[derive(Default)]
+pub struct TempWorkspaceBuilder {
+    files: Vec<(PathBuf, String)>,
+    git: bool,
+}
+
+impl TempWorkspaceBuilder {
+    /// Add a file to the workspace
+    pub fn file(mut self, path: impl Into<PathBuf>, content: impl Into<String>) -> Self {
+        self.files.push((path.into(), content.into()));
+        self
+    }
+
+    /// Add a `stencila.toml` config file to the root of the workspace
+    pub fn config(self, content: impl Into<String>) -> Self {
+        self.file("stencila.toml", content)
+    }
+
+    /// Add a sample document to the workspace
+    ///
+    /// Currently an alias for `file` but exists so that documents can be
+    /// created from templates in the future.
+    pub fn document(self, path: impl Into<PathBuf>, content: impl Into<String>) -> Self {
+        self.file(path, content)
+    }
+
+    /// Add a prompt to the `prompts` directory of the workspace
+    pub fn prompt(self, name: &str, content: impl Into<String>) -> Self {
+        self.file(PathBuf::from("prompts").join(name), content)
+    }
+
+    /// Initialize a git repository, with an initial commit, in the workspace
+    pub fn git(mut self) -> Self {
+        self.git = true;
+        self
+    }
+
+    /// Build the temporary workspace
+    pub fn build(self) -> Result<TempWorkspace> {
+        let workspace = TempWorkspace {
+            dir: TempDir::new()?,
+        };
+
+        for (path, content) in &self.files {
+            workspace.write(path, content)?;
+        }
+
+        if self.git {
+            for args in [
+                vec!["init", "--quiet"],
+                vec!["add", "-A"],
+                vec![
+                    "-c",
+                    "user.email=test@example.org",
+                    "-c",
+                    "user.name=Test",
+                    "commit",
+                    "--quiet",
+                    "--allow-empty",
+                    "-m",
+                    "Initial commit",
+                ],
+            ] {
+                let status = Command::new("git")
+                    .args(args)
+                    .current_dir(workspace.path())
+                    .status()?;
+                if !status.success() {
+                    return Err(format!("git exited with status {status}").into());
+                }
+            }
+        }
+
+        Ok(workspace)
+    }
+}
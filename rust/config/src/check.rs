@@ -0,0 +1,174 @@
+//! Checking of config files
+//!
+//! Reports unknown keys, type mismatches, and deprecated options in
+//! `stencila.toml` files with file and line locations, rather than
+//! silently ignoring them.
+
+use std::path::{Path, PathBuf};
+
+use common::{
+    eyre::Result,
+    glob::glob,
+    serde::Serialize,
+    strum::Display,
+    tokio::fs::read_to_string,
+    toml,
+};
+
+use crate::{Config, CONFIG_FILE};
+
+/// Deprecated options and advice on what to use instead
+///
+/// When an unknown key matches one of these a warning, rather than an
+/// error, is reported.
+const DEPRECATED: &[(&str, &str)] = &[("site", "use the `routes` table instead")];
+
+/// The severity level of a [`Diagnostic`]
+#[derive(Debug, Clone, Copy, Display, Serialize)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase", crate = "common::serde")]
+pub enum DiagnosticLevel {
+    Warning,
+    Error,
+}
+
+/// A diagnostic for a config file
+#[derive(Debug, Serialize)]
+#[serde(crate = "common::serde")]
+pub struct Diagnostic {
+    /// The path of the config file
+    pub path: PathBuf,
+
+    /// The 1-based line of the config file that the diagnostic applies to
+    pub line: Option<usize>,
+
+    /// The 1-based column of the config file that the diagnostic applies to
+    pub column: Option<usize>,
+
+    /// The severity level of the diagnostic
+    pub level: DiagnosticLevel,
+
+    /// The diagnostic message
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Format the diagnostic in the conventional `path:line:column` style
+    pub fn formatted(&self) -> String {
+        let mut formatted = self.path.display().to_string();
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            formatted.push_str(&format!(":{line}:{column}"));
+        }
+        formatted.push_str(&format!(" {}: {}", self.level, self.message));
+        formatted
+    }
+}
+
+/// Check all config files at and below a directory
+pub async fn check(dir: &Path) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+    for path in glob(&format!("{}/**/{CONFIG_FILE}", dir.display()))?.flatten() {
+        diagnostics.append(&mut check_file(&path).await?);
+    }
+
+    Ok(diagnostics)
+}
+
+/// Check a single config file
+pub async fn check_file(path: &Path) -> Result<Vec<Diagnostic>> {
+    let toml = read_to_string(path).await?;
+
+    let mut diagnostics = Vec::new();
+    if let Err(error) = toml::from_str::<Config>(&toml) {
+        let (line, column) = match error.span() {
+            Some(span) => {
+                let (line, column) = position(&toml, span.start);
+                (Some(line), Some(column))
+            }
+            None => (None, None),
+        };
+
+        let mut message = error.message().to_string();
+
+        // Report deprecated options as warnings with advice, other
+        // errors (unknown keys, type mismatches, syntax errors) as errors
+        let mut level = DiagnosticLevel::Error;
+        for (key, advice) in DEPRECATED {
+            if message.contains(&format!("`{key}`")) {
+                level = DiagnosticLevel::Warning;
+                message = format!("option `{key}` is deprecated: {advice}");
+                break;
+            }
+        }
+
+        diagnostics.push(Diagnostic {
+            path: path.to_path_buf(),
+            line,
+            column,
+            level,
+            message,
+        });
+    }
+
+    Ok(diagnostics)
+}
+
+/// Get the 1-based line and column of a byte offset in a string
+fn position(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit_once('\n')
+        .map(|(.., rest)| rest.len())
+        .unwrap_or(before.len())
+        + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::tokio;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn unknown_key() -> Result<()> {
+        let dir = common::tempfile::tempdir()?;
+        let path = dir.path().join(CONFIG_FILE);
+        std::fs::write(&path, "themee = \"base\"\n")?;
+
+        let diagnostics = check_file(&path).await?;
+        assert_eq!(diagnostics.len(), 1);
+        assert!(matches!(diagnostics[0].level, DiagnosticLevel::Error));
+        assert_eq!(diagnostics[0].line, Some(1));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn type_mismatch() -> Result<()> {
+        let dir = common::tempfile::tempdir()?;
+        let path = dir.path().join(CONFIG_FILE);
+        std::fs::write(&path, "[execution]\nskip-code = \"yes\"\n")?;
+
+        let diagnostics = check_file(&path).await?;
+        assert_eq!(diagnostics.len(), 1);
+        assert!(matches!(diagnostics[0].level, DiagnosticLevel::Error));
+        assert_eq!(diagnostics[0].line, Some(2));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn deprecated() -> Result<()> {
+        let dir = common::tempfile::tempdir()?;
+        let path = dir.path().join(CONFIG_FILE);
+        std::fs::write(&path, "[site]\ndomain = \"example.org\"\n")?;
+
+        let diagnostics = check_file(&path).await?;
+        assert_eq!(diagnostics.len(), 1);
+        assert!(matches!(diagnostics[0].level, DiagnosticLevel::Warning));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+
+use app::DirType;
+use cli_utils::{Code, ToStdout};
+use common::{
+    clap::{self, Args, Parser, Subcommand},
+    eyre::{bail, Result},
+    serde_json,
+};
+use format::Format;
+
+use crate::check::{check, DiagnosticLevel};
+
+/// Manage configuration
+#[derive(Debug, Parser)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    Check(Check),
+    Dir(Dir),
+}
+
+impl Cli {
+    pub async fn run(self) -> Result<()> {
+        match self.command {
+            Command::Check(check) => check.run().await?,
+            Command::Dir(dir) => dir.run().await?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Check config files for unknown keys, type mismatches and deprecated options
+///
+/// Checks all `stencila.toml` files at and below the directory
+#[derive(Debug, Args)]
+struct Check {
+    /// The directory to check
+    #[arg(default_value = ".")]
+    dir: PathBuf,
+
+    /// Output the diagnostics as JSON, rather than as text
+    #[arg(long)]
+    json: bool,
+}
+
+impl Check {
+    async fn run(self) -> Result<()> {
+        let diagnostics = check(&self.dir).await?;
+
+        if self.json {
+            Code::new(Format::Json, &serde_json::to_string_pretty(&diagnostics)?).to_stdout();
+        } else {
+            for diagnostic in &diagnostics {
+                println!("{}", diagnostic.formatted());
+            }
+        }
+
+        let errors = diagnostics
+            .iter()
+            .filter(|diagnostic| matches!(diagnostic.level, DiagnosticLevel::Error))
+            .count();
+        if errors > 0 {
+            bail!("{errors} error(s) in config files")
+        }
+
+        Ok(())
+    }
+}
+
+/// Show the path of an application directory
+#[derive(Debug, Args)]
+struct Dir {
+    /// The type of directory
+    #[arg(long, default_value = "config")]
+    dir: DirType,
+
+    /// Ensure the directory exists
+    #[arg(long)]
+    ensure: bool,
+}
+
+impl Dir {
+    async fn run(self) -> Result<()> {
+        let dir = app::get_app_dir(self.dir, self.ensure)?;
+        println!("{}", dir.display());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,637 @@
+//! Workspace and directory level configuration
+//!
+//! Configuration is read from `stencila.toml` files. A file applies to
+//! documents in its directory and below. Files in subdirectories override
+//! those in parent directories, so in a monorepo with several publications
+//! each can be configured independently.
+//!
+//! Merge semantics are: set values in a deeper file override values set in
+//! a shallower file; unset values are inherited; maps (e.g. `routes`) are
+//! merged key by key, with deeper keys overriding shallower ones.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use common::{
+    eyre::{eyre, Result, WrapErr},
+    itertools::Itertools,
+    once_cell::sync::Lazy,
+    regex::{Captures, Regex},
+    serde::{Deserialize, Serialize},
+    serde_with::skip_serializing_none,
+    tokio::fs::read_to_string,
+    toml,
+};
+
+pub mod check;
+pub mod cli;
+pub mod quarto;
+
+/// The name of config files
+pub const CONFIG_FILE: &str = "stencila.toml";
+
+/// Configuration that applies to documents within a directory
+#[skip_serializing_none]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields, crate = "common::serde")]
+pub struct Config {
+    /// Options for executing documents
+    pub execution: Option<ExecutionConfig>,
+
+    /// Options for rendering citations and references
+    pub citations: Option<CitationsConfig>,
+
+    /// Options for numbering figures, tables and equations
+    pub numbering: Option<NumberingConfig>,
+
+    /// The theme to use when encoding documents to HTML and other formats
+    pub theme: Option<String>,
+
+    /// Routes for serving and publishing documents
+    ///
+    /// A map of URL paths to document paths (relative to the config file).
+    pub routes: Option<BTreeMap<String, String>>,
+
+    /// Options for provisioning the environment in which documents are executed
+    pub environment: Option<EnvironmentConfig>,
+
+    /// Named profiles that override other settings
+    ///
+    /// A profile is selected using the `--profile` option or the
+    /// `STENCILA_CONFIG_PROFILE` environment variable, so that the same
+    /// workspace can behave differently in, say, CI versus a laptop.
+    pub profile: Option<BTreeMap<String, Profile>>,
+}
+
+/// Overrides applied when a named profile is selected
+#[skip_serializing_none]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields, crate = "common::serde")]
+pub struct Profile {
+    /// Options for executing documents
+    pub execution: Option<ExecutionConfig>,
+
+    /// The theme to use when encoding documents to HTML and other formats
+    pub theme: Option<String>,
+
+    /// Routes for serving and publishing documents
+    pub routes: Option<BTreeMap<String, String>>,
+}
+
+/// The environment variable used to select a config profile
+pub const PROFILE_ENV: &str = "STENCILA_CONFIG_PROFILE";
+
+/// Options for provisioning the environment in which documents are executed
+#[skip_serializing_none]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields, crate = "common::serde")]
+pub struct EnvironmentConfig {
+    /// Python packages that should be available when executing documents
+    ///
+    /// If set, a `.venv` virtual environment is created in the workspace
+    /// (using `uv` or `conda` if available) with these packages installed,
+    /// before the Python kernel is started.
+    pub python: Option<Vec<String>>,
+}
+
+/// Options for rendering citations and references
+///
+/// For example, to use numeric citations as used by many biomedical journals:
+///
+/// ```toml
+/// [citations]
+/// style = "vancouver"
+/// ```
+#[skip_serializing_none]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields, crate = "common::serde")]
+pub struct CitationsConfig {
+    /// The citation style to use
+    ///
+    /// Either the name of a built-in style (e.g. "apa", "vancouver") or the
+    /// path of a CSL style file, relative to the config file, from which the
+    /// citation format (author-date or numeric) is derived.
+    pub style: Option<String>,
+
+    /// Whether supplements maintain their own reference list
+    ///
+    /// When `true`, citations within a `SupplementaryMaterials` section are
+    /// numbered from one, in order of first appearance within the section,
+    /// as required by many journals.
+    pub supplement_references: Option<bool>,
+}
+
+/// Options for numbering figures, tables and equations
+///
+/// For example, to number figures within top level sections (e.g. "Figure 2.3")
+/// with appendices lettered (e.g. "Figure A.1"):
+///
+/// ```toml
+/// [numbering]
+/// per-section = true
+/// appendix-letters = true
+/// ```
+#[skip_serializing_none]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields, crate = "common::serde")]
+pub struct NumberingConfig {
+    /// Number figures, tables and equations within top level sections
+    ///
+    /// When `true`, labels include the number of the current top level
+    /// section e.g. "2.3" for the third figure in the second section.
+    pub per_section: Option<bool>,
+
+    /// Use letters, rather than numbers, for appendices
+    ///
+    /// Applies to per-section labels of figures, tables and equations in
+    /// appendices e.g. "A.1" rather than "7.1".
+    pub appendix_letters: Option<bool>,
+
+    /// Reset figure, table and equation counts at each appendix
+    ///
+    /// When `true`, counts reset at the start of each appendix even when
+    /// `per-section` numbering is not enabled.
+    pub appendix_reset: Option<bool>,
+
+    /// The heading prefix used to detect appendices
+    ///
+    /// Top level headings starting with this text are treated as the start
+    /// of an appendix. Defaults to "Appendix".
+    pub appendix_prefix: Option<String>,
+
+    /// The prefix used when rendering references to figures
+    ///
+    /// Defaults to "Figure".
+    pub figure_prefix: Option<String>,
+
+    /// The prefix used when rendering references to tables
+    ///
+    /// Defaults to "Table".
+    pub table_prefix: Option<String>,
+
+    /// The prefix used when rendering references to equations
+    ///
+    /// Defaults to "Equation".
+    pub equation_prefix: Option<String>,
+}
+
+/// Options for executing documents
+#[skip_serializing_none]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields, crate = "common::serde")]
+pub struct ExecutionConfig {
+    /// Skip executing code
+    pub skip_code: Option<bool>,
+
+    /// Skip executing instructions
+    pub skip_instructions: Option<bool>,
+
+    /// Prepare, but do not actually perform, execution tasks
+    pub dry_run: Option<bool>,
+
+    /// Hooks to run around document and code chunk execution
+    pub hooks: Option<ExecutionHooks>,
+}
+
+/// Hooks to run around document and code chunk execution
+#[skip_serializing_none]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields, crate = "common::serde")]
+pub struct ExecutionHooks {
+    /// Hooks to run before the document is executed
+    ///
+    /// For example, to start a database that code chunks connect to.
+    pub before: Option<Vec<Hook>>,
+
+    /// Hooks to run after the document has been executed
+    pub after: Option<Vec<Hook>>,
+
+    /// Hooks to run before each code chunk is executed
+    pub before_each: Option<Vec<Hook>>,
+
+    /// Hooks to run after each code chunk has been executed
+    ///
+    /// For example, to assert invariants about the state of kernel variables.
+    pub after_each: Option<Vec<Hook>>,
+}
+
+/// A hook run as part of executing a document
+///
+/// Either a shell command, run in the workspace directory e.g.
+///
+/// ```toml
+/// before = ["docker compose up --detach db"]
+/// ```
+///
+/// or a snippet of code, run in one of the document's kernels e.g.
+///
+/// ```toml
+/// after-each = [{ language = "python", code = "assert balance >= 0" }]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    untagged,
+    rename_all = "kebab-case",
+    deny_unknown_fields,
+    crate = "common::serde"
+)]
+pub enum Hook {
+    /// A shell command run in the workspace directory
+    Shell(String),
+
+    /// A snippet of code run in one of the document's kernels
+    Kernel {
+        /// The language of the code
+        language: String,
+
+        /// The code to run
+        code: String,
+    },
+}
+
+impl Config {
+    /// Read the config file, if any, in a directory
+    ///
+    /// Returns `None` if the directory does not have a config file.
+    pub async fn read(dir: &Path) -> Result<Option<Self>> {
+        let path = dir.join(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let toml = read_to_string(&path).await?;
+        let mut config: Config = toml::from_str(&toml)
+            .wrap_err_with(|| format!("While reading `{}`", path.display()))?;
+        config
+            .interpolate()
+            .wrap_err_with(|| format!("While interpolating `{}`", path.display()))?;
+
+        Ok(Some(config))
+    }
+
+    /// Interpolate environment variables and secrets into config values
+    ///
+    /// Replaces `${ENV_VAR}` with the value of the environment variable and
+    /// `${secret:NAME}` with the value of the secret, so that config files can
+    /// be committed without embedding credentials.
+    fn interpolate(&mut self) -> Result<()> {
+        if let Some(theme) = &self.theme {
+            self.theme = Some(interpolate(theme)?);
+        }
+
+        if let Some(routes) = &mut self.routes {
+            for value in routes.values_mut() {
+                *value = interpolate(value)?;
+            }
+        }
+
+        if let Some(profiles) = &mut self.profile {
+            for profile in profiles.values_mut() {
+                if let Some(theme) = &profile.theme {
+                    profile.theme = Some(interpolate(theme)?);
+                }
+                if let Some(routes) = &mut profile.routes {
+                    for value in routes.values_mut() {
+                        *value = interpolate(value)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply a named profile's overrides to this config
+    ///
+    /// Errors if the profile is not defined in any config file.
+    pub fn apply_profile(&mut self, name: &str) -> Result<()> {
+        let profile = self
+            .profile
+            .as_ref()
+            .and_then(|profiles| profiles.get(name))
+            .cloned()
+            .ok_or_else(|| eyre!("Config profile `{name}` is not defined"))?;
+
+        self.merge(Config {
+            execution: profile.execution,
+            theme: profile.theme,
+            routes: profile.routes,
+            ..Default::default()
+        });
+
+        Ok(())
+    }
+
+    /// Merge another config, from a deeper directory, into this one
+    ///
+    /// Values set in `other` override those in `self`; maps are merged
+    /// key by key.
+    pub fn merge(&mut self, other: Self) {
+        if let Some(other) = other.execution {
+            let execution = self.execution.get_or_insert_with(Default::default);
+            execution.skip_code = other.skip_code.or(execution.skip_code);
+            execution.skip_instructions = other.skip_instructions.or(execution.skip_instructions);
+            execution.dry_run = other.dry_run.or(execution.dry_run);
+            execution.hooks = other.hooks.or(execution.hooks.take());
+        }
+
+        if other.citations.is_some() {
+            self.citations = other.citations;
+        }
+
+        if other.numbering.is_some() {
+            self.numbering = other.numbering;
+        }
+
+        if other.theme.is_some() {
+            self.theme = other.theme;
+        }
+
+        if let Some(other) = other.routes {
+            let routes = self.routes.get_or_insert_with(Default::default);
+            routes.extend(other);
+        }
+
+        if let Some(other) = other.environment {
+            let environment = self.environment.get_or_insert_with(Default::default);
+            if other.python.is_some() {
+                environment.python = other.python;
+            }
+        }
+
+        if let Some(other) = other.profile {
+            let profiles = self.profile.get_or_insert_with(Default::default);
+            profiles.extend(other);
+        }
+    }
+}
+
+/// Interpolate environment variables and secrets into a string value
+fn interpolate(value: &str) -> Result<String> {
+    static REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"\$\{(secret:)?([A-Za-z_][A-Za-z0-9_]*)\}").expect("invalid regex"));
+
+    let mut error = None;
+    let interpolated = REGEX.replace_all(value, |captures: &Captures| {
+        let name = &captures[2];
+        let result = if captures.get(1).is_some() {
+            secrets::env_or_get(name)
+        } else {
+            std::env::var(name).map_err(|_| eyre!("Environment variable `{name}` is not set"))
+        };
+        match result {
+            Ok(value) => value,
+            Err(err) => {
+                error.get_or_insert(err);
+                String::new()
+            }
+        }
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(interpolated.to_string()),
+    }
+}
+
+/// Get the merged config that applies to a path
+///
+/// Walks from the root of the filesystem down to the directory of the path,
+/// merging each config file encountered, so that config files in deeper
+/// directories override those in shallower directories. If the
+/// `STENCILA_CONFIG_PROFILE` environment variable is set, that profile's
+/// overrides are applied last.
+pub async fn for_path(path: &Path) -> Result<Config> {
+    let profile = std::env::var(PROFILE_ENV).ok();
+    for_path_with_profile(path, profile.as_deref()).await
+}
+
+/// Get the merged config that applies to a path, with a profile applied
+///
+/// As for [`for_path`] but with the named profile (falling back to the
+/// `STENCILA_CONFIG_PROFILE` environment variable if `None`) applied last.
+pub async fn for_path_with_profile(path: &Path, profile: Option<&str>) -> Result<Config> {
+    let dir = if path.is_file() {
+        path.parent().map(PathBuf::from).unwrap_or_default()
+    } else {
+        path.to_path_buf()
+    };
+
+    let mut config = Config::default();
+    for dir in dir.ancestors().collect_vec().into_iter().rev() {
+        // Read any Quarto project config first so that `stencila.toml`
+        // values in the same directory override it
+        if let Some(other) = quarto::read(dir).await? {
+            config.merge(other);
+        }
+        if let Some(other) = Config::read(dir).await? {
+            config.merge(other);
+        }
+    }
+
+    let profile = profile
+        .map(String::from)
+        .or_else(|| std::env::var(PROFILE_ENV).ok());
+    if let Some(profile) = profile {
+        config.apply_profile(&profile)?;
+    }
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::tokio;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn merging() -> Result<()> {
+        let mut config = Config {
+            theme: Some("base".to_string()),
+            execution: Some(ExecutionConfig {
+                skip_code: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        config.merge(Config {
+            theme: Some("custom".to_string()),
+            execution: Some(ExecutionConfig {
+                skip_instructions: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        assert_eq!(config.theme.as_deref(), Some("custom"));
+
+        let execution = config.execution.expect("should be set");
+        assert_eq!(execution.skip_code, Some(true));
+        assert_eq!(execution.skip_instructions, Some(true));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn profiles() -> Result<()> {
+        let workspace = common::tempfile::tempdir()?;
+
+        std::fs::write(
+            workspace.path().join(CONFIG_FILE),
+            r#"
+theme = "base"
+
+[profile.ci]
+theme = "plain"
+
+[profile.ci.execution]
+dry-run = true
+"#,
+        )?;
+
+        let config = for_path_with_profile(workspace.path(), None).await?;
+        assert_eq!(config.theme.as_deref(), Some("base"));
+        assert_eq!(config.execution, None);
+
+        let config = for_path_with_profile(workspace.path(), Some("ci")).await?;
+        assert_eq!(config.theme.as_deref(), Some("plain"));
+        assert_eq!(
+            config.execution.and_then(|execution| execution.dry_run),
+            Some(true)
+        );
+
+        let result = for_path_with_profile(workspace.path(), Some("unknown")).await;
+        assert!(result.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn hooks() -> Result<()> {
+        let workspace = common::tempfile::tempdir()?;
+
+        std::fs::write(
+            workspace.path().join(CONFIG_FILE),
+            r#"
+[execution.hooks]
+before = ["docker compose up --detach db"]
+after-each = [{ language = "python", code = "assert balance >= 0" }]
+"#,
+        )?;
+
+        let config = for_path_with_profile(workspace.path(), None).await?;
+        let hooks = config
+            .execution
+            .and_then(|execution| execution.hooks)
+            .expect("should be set");
+        assert_eq!(
+            hooks.before,
+            Some(vec![Hook::Shell(
+                "docker compose up --detach db".to_string()
+            )])
+        );
+        assert_eq!(
+            hooks.after_each,
+            Some(vec![Hook::Kernel {
+                language: "python".to_string(),
+                code: "assert balance >= 0".to_string()
+            }])
+        );
+        assert_eq!(hooks.after, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn interpolation() -> Result<()> {
+        std::env::set_var("STENCILA_TEST_THEME", "custom");
+
+        assert_eq!(interpolate("${STENCILA_TEST_THEME}")?, "custom");
+        assert_eq!(interpolate("themes/${STENCILA_TEST_THEME}/")?, "themes/custom/");
+        assert_eq!(interpolate("no placeholders")?, "no placeholders");
+
+        assert!(interpolate("${STENCILA_TEST_UNSET}").is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn quarto() -> Result<()> {
+        let workspace = common::tempfile::tempdir()?;
+
+        std::fs::write(
+            workspace.path().join("_quarto.yml"),
+            r#"
+project:
+  type: website
+  output-dir: _site
+  render:
+    - index.qmd
+    - about.qmd
+website:
+  navbar:
+    left:
+      - href: posts/welcome.qmd
+        text: Welcome
+format:
+  html:
+    theme: cosmo
+"#,
+        )?;
+
+        let config = for_path_with_profile(workspace.path(), None).await?;
+        assert_eq!(config.theme.as_deref(), Some("cosmo"));
+
+        let routes = config.routes.expect("should be set");
+        assert_eq!(routes.get("/"), Some(&"index.qmd".to_string()));
+        assert_eq!(routes.get("/about"), Some(&"about.qmd".to_string()));
+        assert_eq!(
+            routes.get("/posts/welcome"),
+            Some(&"posts/welcome.qmd".to_string())
+        );
+
+        // A `stencila.toml` in the same directory overrides
+        std::fs::write(workspace.path().join(CONFIG_FILE), "theme = \"custom\"\n")?;
+        let config = for_path_with_profile(workspace.path(), None).await?;
+        assert_eq!(config.theme.as_deref(), Some("custom"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn hierarchy() -> Result<()> {
+        let workspace = common::tempfile::tempdir()?;
+        let subdir = workspace.path().join("sub");
+        std::fs::create_dir(&subdir)?;
+
+        std::fs::write(
+            workspace.path().join(CONFIG_FILE),
+            r#"
+theme = "base"
+
+[execution]
+skip-code = true
+"#,
+        )?;
+
+        std::fs::write(
+            subdir.join(CONFIG_FILE),
+            r#"
+theme = "custom"
+"#,
+        )?;
+
+        let config = for_path(&subdir).await?;
+        assert_eq!(config.theme.as_deref(), Some("custom"));
+        assert_eq!(
+            config.execution.and_then(|execution| execution.skip_code),
+            Some(true)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+//! Reading Quarto project configuration
+//!
+//! So that whole Quarto websites can be migrated to, or published with,
+//! Stencila without per-file changes, a `_quarto.yml` (or `_quarto.yaml`)
+//! file in a directory is read as a [`Config`] with Quarto options mapped
+//! to their closest Stencila equivalents:
+//!
+//! - `project.render` targets and `website.navbar` items become `routes`
+//! - `format.html.theme` becomes `theme`
+//!
+//! Other Quarto options (e.g. `project.output-dir`, which has no equivalent
+//! because Stencila sites are rendered on demand) are ignored. Values in
+//! `stencila.toml` files override those derived from `_quarto.yml`.
+
+use std::{collections::BTreeMap, path::Path};
+
+use common::{
+    eyre::{Result, WrapErr},
+    serde::Deserialize,
+    serde_yaml,
+    tokio::fs::read_to_string,
+};
+
+use crate::Config;
+
+/// The names of Quarto project config files
+pub const QUARTO_FILES: &[&str] = &["_quarto.yml", "_quarto.yaml"];
+
+/// Read the Quarto project config file, if any, in a directory
+///
+/// Returns `None` if the directory does not have a Quarto project config file.
+pub async fn read(dir: &Path) -> Result<Option<Config>> {
+    let Some(path) = QUARTO_FILES
+        .iter()
+        .map(|file| dir.join(file))
+        .find(|path| path.exists())
+    else {
+        return Ok(None);
+    };
+
+    let yaml = read_to_string(&path).await?;
+    let quarto: QuartoConfig = serde_yaml::from_str(&yaml)
+        .wrap_err_with(|| format!("While reading `{}`", path.display()))?;
+
+    Ok(Some(quarto.into_config()))
+}
+
+/// A subset of Quarto project configuration
+///
+/// Only the options with a Stencila equivalent are represented. Unknown
+/// options are ignored, rather than erroring, so that any valid Quarto
+/// project can be read.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, crate = "common::serde")]
+struct QuartoConfig {
+    project: QuartoProject,
+    website: QuartoWebsite,
+    format: QuartoFormat,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, crate = "common::serde")]
+struct QuartoProject {
+    render: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, crate = "common::serde")]
+struct QuartoWebsite {
+    navbar: QuartoNavbar,
+    sidebar: QuartoSidebar,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, crate = "common::serde")]
+struct QuartoNavbar {
+    left: Vec<QuartoNavItem>,
+    right: Vec<QuartoNavItem>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, crate = "common::serde")]
+struct QuartoSidebar {
+    contents: Vec<QuartoNavItem>,
+}
+
+/// An item in a Quarto navbar or sidebar: either a bare path or a map
+/// with an `href`
+#[derive(Debug, Deserialize)]
+#[serde(untagged, crate = "common::serde")]
+enum QuartoNavItem {
+    Path(String),
+    Link {
+        href: Option<String>,
+        #[serde(default)]
+        contents: Vec<QuartoNavItem>,
+    },
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, crate = "common::serde")]
+struct QuartoFormat {
+    html: QuartoHtml,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, crate = "common::serde")]
+struct QuartoHtml {
+    theme: Option<QuartoTheme>,
+}
+
+/// A Quarto theme: either a name or a list of names and files
+#[derive(Debug, Deserialize)]
+#[serde(untagged, crate = "common::serde")]
+enum QuartoTheme {
+    Name(String),
+    List(Vec<String>),
+}
+
+impl QuartoConfig {
+    /// Convert Quarto project configuration into a [`Config`]
+    fn into_config(self) -> Config {
+        let mut routes = BTreeMap::new();
+        for target in self.project.render {
+            if let Some((url, path)) = route(&target) {
+                routes.insert(url, path);
+            }
+        }
+        for item in self
+            .website
+            .navbar
+            .left
+            .into_iter()
+            .chain(self.website.navbar.right)
+            .chain(self.website.sidebar.contents)
+        {
+            item.add_routes(&mut routes);
+        }
+        let routes = (!routes.is_empty()).then_some(routes);
+
+        let theme = self.format.html.theme.and_then(|theme| match theme {
+            QuartoTheme::Name(name) => Some(name),
+            QuartoTheme::List(names) => names.into_iter().next(),
+        });
+
+        Config {
+            theme,
+            routes,
+            ..Default::default()
+        }
+    }
+}
+
+impl QuartoNavItem {
+    /// Add the routes for a nav item, and any nested items, to a routes map
+    fn add_routes(self, routes: &mut BTreeMap<String, String>) {
+        match self {
+            QuartoNavItem::Path(path) => {
+                if let Some((url, path)) = route(&path) {
+                    routes.insert(url, path);
+                }
+            }
+            QuartoNavItem::Link { href, contents } => {
+                if let Some((url, path)) = href.as_deref().and_then(route) {
+                    routes.insert(url, path);
+                }
+                for item in contents {
+                    item.add_routes(routes);
+                }
+            }
+        }
+    }
+}
+
+/// Get the route for a Quarto render target or navigation href
+///
+/// Returns `None` for globs and external links. The URL path is the target
+/// without its extension, with `index` pages mapped to the directory path.
+fn route(target: &str) -> Option<(String, String)> {
+    if target.contains('*') || target.contains("://") {
+        return None;
+    }
+
+    let path = Path::new(target);
+    if !matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("qmd" | "md" | "ipynb")
+    ) {
+        return None;
+    }
+
+    let stem = path.with_extension("");
+    let mut url = ["/", &stem.to_string_lossy()].concat();
+    if let Some(prefix) = url.strip_suffix("index") {
+        url = prefix.to_string();
+    }
+    if url.len() > 1 {
+        url = url.trim_end_matches('/').to_string();
+    }
+
+    Some((url, target.to_string()))
+}
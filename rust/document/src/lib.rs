@@ -24,7 +24,7 @@ use common::{
 };
 use format::Format;
 use kernels::Kernels;
-use node_execute::ExecuteOptions;
+use node_execute::{ExecuteOptions, ExecutionPlan, ExecutionSummary};
 use schema::{Article, AuthorRole, Node, NodeId, NodeType, Null, Patch, Prompt};
 
 mod config;
@@ -35,8 +35,11 @@ mod sync_format;
 mod sync_object;
 mod task_command;
 mod task_update;
+mod variables;
+mod watch_execute;
 
 pub use sync_dom::DomPatch;
+pub use variables::VariableInfo;
 
 #[derive(Default)]
 pub struct Document_;
@@ -301,6 +304,13 @@ pub struct Document {
 
     /// A channel for receiving notifications of command status
     command_status_receiver: DocumentCommandStatusReceiver,
+
+    /// Options to execute the document with after each update to the root node
+    ///
+    /// Set by [`Document::watch_execute`] so that, when the source file is
+    /// saved, the document is executed (rather than just compiled) and only
+    /// the nodes whose code or dependencies changed are re-executed.
+    execute_on_update: Arc<RwLock<Option<ExecuteOptions>>>,
 }
 
 impl Document {
@@ -369,10 +379,13 @@ impl Document {
         let (command_sender, command_receiver) = mpsc::channel(256);
         let (command_status_sender, command_status_receiver) = broadcast::channel(256);
 
+        let execute_on_update = Arc::new(RwLock::new(None));
+
         // Start the update task
         {
             let root = root.clone();
             let command_sender = command_sender.clone();
+            let execute_on_update = execute_on_update.clone();
             tokio::spawn(async move {
                 Self::update_task(
                     update_receiver,
@@ -380,6 +393,7 @@ impl Document {
                     root,
                     watch_sender,
                     command_sender,
+                    execute_on_update,
                 )
                 .await
             });
@@ -421,6 +435,7 @@ impl Document {
             command_counter,
             command_sender,
             command_status_receiver,
+            execute_on_update,
         })
     }
 
@@ -729,4 +744,29 @@ impl Document {
 
         self.command(Command::ExecuteDocument(options), wait).await
     }
+
+    /// Plan which nodes in the document would be executed, without executing them
+    #[tracing::instrument(skip(self))]
+    pub async fn plan(&self, options: ExecuteOptions) -> Result<ExecutionPlan> {
+        tracing::trace!("Planning execution of document");
+
+        node_execute::plan(
+            self.home.clone(),
+            self.root.clone(),
+            self.kernels.clone(),
+            None,
+            Some(options),
+        )
+        .await
+    }
+
+    /// Summarize the execution status of the nodes in the document
+    ///
+    /// Should be called after the document has been executed.
+    #[tracing::instrument(skip(self))]
+    pub async fn execution_summary(&self) -> ExecutionSummary {
+        tracing::trace!("Summarizing execution of document");
+
+        node_execute::summary(self.root.clone()).await
+    }
 }
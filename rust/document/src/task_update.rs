@@ -1,7 +1,10 @@
+use std::sync::Arc;
+
 use common::{
-    tokio::{self},
+    tokio::{self, sync::RwLock},
     tracing,
 };
+use node_execute::ExecuteOptions;
 use schema::{Node, PatchNode, PatchOp};
 
 use crate::{
@@ -27,6 +30,7 @@ impl Document {
         root: DocumentRoot,
         watch_sender: DocumentWatchSender,
         command_sender: DocumentCommandSender,
+        execute_on_update: Arc<RwLock<Option<ExecuteOptions>>>,
     ) {
         tracing::debug!("Document update task started");
 
@@ -89,7 +93,13 @@ impl Document {
             // TODO: consider throttling or debouncing this (although note that if the document is already
             // compiling or executing then the command will be ignored anyway)
             if compile {
-                if let Err(error) = command_sender.send((Command::CompileDocument, 0)).await {
+                // If the document is being watched for re-execution then execute,
+                // rather than just compile, it (only stale nodes will be re-executed)
+                let command = match execute_on_update.read().await.clone() {
+                    Some(options) => Command::ExecuteDocument(options),
+                    None => Command::CompileDocument,
+                };
+                if let Err(error) = command_sender.send((command, 0)).await {
                     tracing::error!("While sending command to document: {error}");
                 }
             }
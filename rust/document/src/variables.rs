@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use common::{eyre::Result, serde::Serialize};
+use schema::{Block, Inline, Variable, Visitor, WalkControl, WalkNode};
+
+use crate::Document;
+
+/// Information about a variable in a document's kernels
+///
+/// In addition to the variable itself (including any `hint` of its value),
+/// records which nodes in the document write and read the variable, based
+/// on parsing the code of each node.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase", crate = "common::serde")]
+pub struct VariableInfo {
+    /// The variable, as listed by the kernel instance it resides in
+    pub variable: Variable,
+
+    /// The id of the node (usually a `CodeChunk`) that last writes the variable
+    pub written_by: Option<String>,
+
+    /// The ids of the nodes that read the variable
+    pub read_by: Vec<String>,
+}
+
+/// A visitor that collects which nodes read and write each variable
+#[derive(Default)]
+struct VariableUses {
+    /// Map of variable name to the id of the node that last writes it
+    writes: HashMap<String, String>,
+
+    /// Map of variable name to the ids of the nodes that read it
+    reads: HashMap<String, Vec<String>>,
+}
+
+impl VariableUses {
+    fn record(&mut self, node_id: String, code: &str, lang: Option<&str>) {
+        let info = parsers::parse(code, lang.unwrap_or_default());
+        for variable in info.variables_read {
+            self.reads.entry(variable).or_default().push(node_id.clone());
+        }
+        for variable in info.variables_written {
+            self.writes.insert(variable, node_id.clone());
+        }
+    }
+}
+
+impl Visitor for VariableUses {
+    fn visit_block(&mut self, block: &Block) -> WalkControl {
+        match block {
+            Block::CodeChunk(chunk) => self.record(
+                chunk.node_id().to_string(),
+                &chunk.code,
+                chunk.programming_language.as_deref(),
+            ),
+            Block::ForBlock(for_block) => {
+                self.record(
+                    for_block.node_id().to_string(),
+                    &for_block.code,
+                    for_block.programming_language.as_deref(),
+                );
+                self.writes.insert(
+                    for_block.variable.trim().to_string(),
+                    for_block.node_id().to_string(),
+                );
+            }
+            _ => {}
+        }
+
+        WalkControl::Continue
+    }
+
+    fn visit_inline(&mut self, inline: &Inline) -> WalkControl {
+        match inline {
+            Inline::CodeExpression(expr) => self.record(
+                expr.node_id().to_string(),
+                &expr.code,
+                expr.programming_language.as_deref(),
+            ),
+            Inline::Parameter(param) => {
+                self.writes
+                    .insert(param.name.clone(), param.node_id().to_string());
+            }
+            _ => {}
+        }
+
+        WalkControl::Continue
+    }
+}
+
+impl Document {
+    /// List the variables in the document's kernels
+    ///
+    /// Lists the variables in all of the document's kernel instances with,
+    /// for each variable, a `hint` of its current value, the id of the node
+    /// that last writes it, and the ids of the nodes that read it. Usually
+    /// called after the document has been executed so that the kernels have
+    /// been populated with variables.
+    pub async fn variables(&self) -> Result<Vec<VariableInfo>> {
+        let mut uses = VariableUses::default();
+        self.root.read().await.walk(&mut uses);
+
+        let mut infos = Vec::new();
+        for instance in self.kernels.read().await.instances().await {
+            let Ok(variables) = instance.lock().await.list().await else {
+                continue;
+            };
+            for variable in variables {
+                let written_by = uses.writes.get(&variable.name).cloned();
+                let read_by = uses.reads.get(&variable.name).cloned().unwrap_or_default();
+                infos.push(VariableInfo {
+                    variable,
+                    written_by,
+                    read_by,
+                });
+            }
+        }
+        infos.sort_by(|a, b| a.variable.name.cmp(&b.variable.name));
+
+        Ok(infos)
+    }
+}
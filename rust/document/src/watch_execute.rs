@@ -0,0 +1,30 @@
+use common::{
+    eyre::{bail, Result},
+    tracing,
+};
+use node_execute::ExecuteOptions;
+
+use crate::{Document, SyncDirection};
+
+impl Document {
+    /// Watch the document's source file and re-execute it when the file is saved
+    ///
+    /// Starts an inward file sync so that saves to the source file update the
+    /// root node, and arranges for the document to be executed, rather than
+    /// just compiled, after each update. Because execution statuses are based
+    /// on compilation digests and variable dependencies, only the nodes whose
+    /// code or dependencies changed are re-executed, with patches streamed to
+    /// connected clients as usual.
+    #[tracing::instrument(skip(self))]
+    pub async fn watch_execute(&self, options: ExecuteOptions) -> Result<()> {
+        let Some(path) = self.path.clone() else {
+            bail!("Document does not have a path so can not be watched")
+        };
+
+        tracing::debug!("Watching `{}` for re-execution", path.display());
+
+        *self.execute_on_update.write().await = Some(options);
+
+        self.sync_file(&path, SyncDirection::In, None, None).await
+    }
+}
@@ -0,0 +1,297 @@
+//! Embeddings-based semantic search over workspace documents
+//!
+//! Builds an index of embeddings for the documents in a workspace and
+//! provides nearest-neighbor search over them. Used to provide relevant
+//! context to generative models (i.e. for retrieval augmented generation).
+
+use std::{
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use codec_text_trait::to_text;
+use common::{
+    eyre::{eyre, Result},
+    glob::glob,
+    itertools::Itertools,
+    reqwest::Client,
+    serde::{Deserialize, Serialize},
+    serde_json::{self, json},
+    tokio::fs::{create_dir_all, read_to_string, write},
+    tracing,
+};
+
+/// The name of the env var or secret for the OpenAI API key
+const API_KEY: &str = "OPENAI_API_KEY";
+
+/// The id of the embedding model used
+///
+/// Recorded in the index so that entries embedded with a different
+/// model can be re-embedded.
+const MODEL: &str = "openai/text-embedding-3-small";
+
+/// The file formats that are indexed within a workspace
+const FORMATS: &[&str] = &["smd", "md", "myst", "ipynb"];
+
+/// The target maximum number of characters in an indexed chunk
+const CHUNK_SIZE: usize = 2000;
+
+/// An index of embeddings for the documents in a workspace
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "common::serde")]
+pub struct EmbeddingsIndex {
+    /// The id of the model used to generate the embeddings
+    model: String,
+
+    /// The entries in the index
+    entries: Vec<IndexEntry>,
+
+    /// The directory of the workspace that is indexed
+    #[serde(skip)]
+    dir: PathBuf,
+}
+
+/// An entry in an [`EmbeddingsIndex`]: an embedded chunk of a document
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "common::serde")]
+pub struct IndexEntry {
+    /// The path of the document, relative to the workspace directory
+    path: PathBuf,
+
+    /// The modification time of the document when it was indexed
+    ///
+    /// Used to avoid re-embedding documents that have not changed.
+    modified: u64,
+
+    /// The text of the chunk
+    text: String,
+
+    /// The embedding of the chunk
+    embedding: Vec<f32>,
+}
+
+/// A result from a search of an [`EmbeddingsIndex`]
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "common::serde")]
+pub struct SearchResult {
+    /// The path of the document, relative to the workspace directory
+    pub path: PathBuf,
+
+    /// The text of the matching chunk
+    pub text: String,
+
+    /// The cosine similarity between the query and the chunk
+    pub score: f32,
+}
+
+impl EmbeddingsIndex {
+    /// Open the index for a workspace, reading it from disk if it exists
+    pub async fn open(dir: &Path) -> Result<Self> {
+        let path = Self::path(dir);
+        let mut index = if path.exists() {
+            serde_json::from_str(&read_to_string(&path).await?)?
+        } else {
+            Self {
+                model: MODEL.to_string(),
+                entries: Vec::new(),
+                dir: PathBuf::new(),
+            }
+        };
+
+        // Discard entries generated with a different model
+        if index.model != MODEL {
+            index.model = MODEL.to_string();
+            index.entries.clear();
+        }
+
+        index.dir = dir.to_path_buf();
+
+        Ok(index)
+    }
+
+    /// The path of the index file for a workspace
+    fn path(dir: &Path) -> PathBuf {
+        dir.join(".stencila").join("embeddings.json")
+    }
+
+    /// Update the index for documents in the workspace that have been
+    /// added, changed, or removed since it was last updated
+    pub async fn update(&mut self) -> Result<()> {
+        let mut paths = Vec::new();
+        for format in FORMATS {
+            for path in glob(&format!("{}/**/*.{format}", self.dir.display()))?.flatten() {
+                paths.push(path);
+            }
+        }
+
+        // Remove entries for documents that no longer exist
+        self.entries
+            .retain(|entry| paths.contains(&self.dir.join(&entry.path)));
+
+        for path in paths {
+            let relative_path = path.strip_prefix(&self.dir).unwrap_or(&path).to_path_buf();
+
+            let modified = path
+                .metadata()?
+                .modified()?
+                .duration_since(UNIX_EPOCH)?
+                .as_secs();
+
+            // Skip documents that have not changed since they were indexed
+            if self
+                .entries
+                .iter()
+                .any(|entry| entry.path == relative_path && entry.modified == modified)
+            {
+                continue;
+            }
+            self.entries.retain(|entry| entry.path != relative_path);
+
+            tracing::debug!("Indexing `{}`", relative_path.display());
+
+            let node = match codecs::from_path(&path, None).await {
+                Ok(node) => node,
+                Err(error) => {
+                    tracing::warn!("While decoding `{}`: {error}", path.display());
+                    continue;
+                }
+            };
+
+            let chunks = chunk(&to_text(&node));
+            if chunks.is_empty() {
+                continue;
+            }
+
+            let embeddings = embed(&chunks).await?;
+            for (text, embedding) in chunks.into_iter().zip(embeddings) {
+                self.entries.push(IndexEntry {
+                    path: relative_path.clone(),
+                    modified,
+                    text,
+                    embedding,
+                });
+            }
+        }
+
+        self.write().await
+    }
+
+    /// Search the index for the entries most similar to a query
+    ///
+    /// Returns up to `limit` results, ordered by descending cosine similarity
+    /// between the embedding of the query and the embedding of each entry.
+    pub async fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        let embedding = embed(&[query.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| eyre!("No embedding returned for query"))?;
+
+        Ok(self
+            .entries
+            .iter()
+            .map(|entry| SearchResult {
+                path: entry.path.clone(),
+                text: entry.text.clone(),
+                score: cosine_similarity(&embedding, &entry.embedding),
+            })
+            .sorted_by(|a, b| b.score.total_cmp(&a.score))
+            .take(limit)
+            .collect_vec())
+    }
+
+    /// Write the index to disk
+    async fn write(&self) -> Result<()> {
+        let path = Self::path(&self.dir);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent).await?;
+        }
+        write(path, serde_json::to_string(self)?).await?;
+
+        Ok(())
+    }
+}
+
+/// Split text into chunks for embedding
+///
+/// Splits on blank lines (i.e. between paragraphs and other blocks) and then
+/// greedily merges successive parts up to [`CHUNK_SIZE`] characters.
+fn chunk(text: &str) -> Vec<String> {
+    let mut chunks: Vec<String> = Vec::new();
+    for part in text.split("\n\n") {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+
+        match chunks.last_mut() {
+            Some(chunk) if chunk.len() + part.len() < CHUNK_SIZE => {
+                chunk.push_str("\n\n");
+                chunk.push_str(part);
+            }
+            _ => chunks.push(part.to_string()),
+        }
+    }
+    chunks
+}
+
+/// Calculate the cosine similarity between two embeddings
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b.iter()).map(|(a, b)| a * b).sum();
+    let norm_a: f32 = a.iter().map(|a| a * a).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|b| b * b).sum::<f32>().sqrt();
+
+    if norm_a == 0. || norm_b == 0. {
+        0.
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Generate embeddings for a list of texts
+///
+/// Currently uses the OpenAI embeddings API. In the future, a local
+/// embedding model may be used as a fallback when no API key is available.
+async fn embed(texts: &[String]) -> Result<Vec<Vec<f32>>> {
+    let api_key = secrets::env_or_get(API_KEY)?;
+
+    let model = MODEL
+        .split_once('/')
+        .map(|(.., model)| model)
+        .unwrap_or(MODEL);
+
+    #[derive(Deserialize)]
+    #[serde(crate = "common::serde")]
+    struct Response {
+        data: Vec<ResponseDatum>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(crate = "common::serde")]
+    struct ResponseDatum {
+        embedding: Vec<f32>,
+    }
+
+    let response = Client::new()
+        .post("https://api.openai.com/v1/embeddings")
+        .bearer_auth(api_key)
+        .json(&json!({
+            "model": model,
+            "input": texts,
+        }))
+        .send()
+        .await?;
+
+    if let Err(error) = response.error_for_status_ref() {
+        let message = response.text().await.unwrap_or_default();
+        return Err(eyre!(error).wrap_err(message));
+    }
+
+    let response: Response = response.json().await?;
+
+    Ok(response
+        .data
+        .into_iter()
+        .map(|datum| datum.embedding)
+        .collect_vec())
+}
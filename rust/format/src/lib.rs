@@ -32,39 +32,62 @@ pub enum Format {
     Dom,
     Html,
     Jats,
+    Confluence,
+    Mediawiki,
+    Tei,
+    Textile,
     // Markdown and derivatives
     Markdown, // Commonmark Markdown with GitHub Flavored Markdown extensions (as in the `markdown` crate)
     Smd,
     Qmd,
+    Rmd,
     Myst,
     Llmd,
     // Typesetting / text formats
     Latex,
+    Beamer,
     Pdf,
     Text,
+    Typst,
     // Notebook formats
     Ipynb,
     // Word processor formats
     Docx,
     Odt,
+    // Presentation formats
+    Pptx,
+    // Spreadsheet and tabular data formats
+    Ods,
+    Xlsx,
+    Csv,
+    Tsv,
     // Math languages
     AsciiMath,
     Tex,
     // Programming languages
     Bash,
     Shell,
+    Go,
     JavaScript,
     Jinja,
     Python,
     R,
     Rhai,
+    Rust,
+    Sql,
     // Diagramming languages
     Dot,
     Mermaid,
     // Styling languages
     Tailwind,
     Css,
+    // Bibliographic formats
+    Bibtex,
+    CslJson,
+    Ris,
     // Data serialization formats
+    Arrow,
+    GeoJson,
     Json,
     JsonZip,
     Json5,
@@ -110,19 +133,27 @@ impl Format {
         use Format::*;
         match self {
             Aac => "AAC",
+            Arrow => "Apache Arrow IPC",
             AsciiMath => "AsciiMath",
             Avi => "AVI",
             Bash => "Bash",
+            Beamer => "Beamer",
+            Bibtex => "BibTeX",
             Cbor => "CBOR",
             CborZst => "CBOR+Zstandard",
+            Confluence => "Confluence storage format",
+            CslJson => "CSL-JSON",
             Css => "CSS",
+            Csv => "CSV",
             Debug => "Debug",
             Directory => "Directory",
             Docx => "Microsoft Word DOCX",
             Dom => "DOM HTML",
             Dot => "Graphviz DOT",
             Flac => "FLAC",
+            GeoJson => "GeoJSON",
             Gif => "GIF",
+            Go => "Go",
             Html => "HTML",
             Ipynb => "IPYNB",
             Jats => "JATS",
@@ -136,31 +167,43 @@ impl Format {
             Latex => "LaTeX",
             Llmd => "LLM Markdown",
             Markdown => "Markdown",
+            Mediawiki => "MediaWiki",
             Mermaid => "Mermaid",
             Mkv => "Matroska",
             Mp3 => "MPEG-3",
             Mp4 => "MPEG-4",
             Myst => "MyST Markdown",
+            Ods => "OpenDocument ODS",
             Odt => "OpenDocument ODT",
             Ogg => "Ogg Vorbis",
             Ogv => "Ogg Vorbis Video",
             Pandoc => "Pandoc AST",
             Pdf => "PDF",
             Png => "PNG",
+            Pptx => "Microsoft PowerPoint PPTX",
             Python => "Python",
             Qmd => "Quarto Markdown",
             R => "R",
             Rhai => "Rhai",
+            Ris => "RIS",
+            Rmd => "R Markdown",
+            Rust => "Rust",
+            Sql => "SQL",
             Shell => "Shell",
             Smd => "Stencila Markdown",
             Swb => "Stencila Web Bundle",
             Svg => "SVG",
             Tailwind => "Tailwind",
+            Tei => "TEI",
             Tex => "TeX",
             Text => "Plain text",
+            Textile => "Textile",
             Toml => "TOML",
+            Tsv => "TSV",
+            Typst => "Typst",
             Wav => "WAV",
             WebM => "WebM",
+            Xlsx => "Microsoft Excel XLSX",
             WebP => "WebP",
             Yaml => "YAML",
             Other(name) => name,
@@ -214,7 +257,7 @@ impl Format {
     /// Is this format a flavor or Markdown?
     pub fn is_markdown_flavor(&self) -> bool {
         use Format::*;
-        matches!(self, Markdown | Smd | Myst | Qmd | Llmd)
+        matches!(self, Markdown | Smd | Myst | Qmd | Rmd | Llmd)
     }
 
     /// Resolve a [`Format`] from a name for the format
@@ -222,11 +265,17 @@ impl Format {
         use Format::*;
         match name.to_lowercase().trim() {
             "aac" => Aac,
+            "arrow" | "feather" => Arrow,
             "asciimath" => AsciiMath,
             "avi" => Avi,
             "bash" => Bash,
+            "beamer" => Beamer,
+            "bibtex" | "bib" => Bibtex,
             "cbor" => Cbor,
             "cborzst" | "cbor.zstd" => CborZst,
+            "confluence" => Confluence,
+            "csljson" | "csl-json" | "csl" => CslJson,
+            "csv" => Csv,
             "css" => Css,
             "debug" => Debug,
             "directory" | "dir" => Directory,
@@ -234,7 +283,9 @@ impl Format {
             "dom" | "dom.html" => Dom,
             "dot" => Dot,
             "flac" => Flac,
+            "geojson" => GeoJson,
             "gif" => Gif,
+            "go" => Go,
             "html" => Html,
             "ipynb" => Ipynb,
             "jats" | "jats.xml" => Jats,
@@ -248,32 +299,44 @@ impl Format {
             "latex" => Latex,
             "llmd" | "llmmd" => Llmd,
             "markdown" | "md" => Markdown,
+            "mediawiki" | "wiki" => Mediawiki,
             "mermaid" => Mermaid,
             "myst" => Myst,
             "mkv" => Mkv,
             "mp3" => Mp3,
             "mp4" => Mp4,
+            "ods" => Ods,
             "odt" => Odt,
             "ogg" => Ogg,
             "ogv" => Ogv,
             "pandoc" => Pandoc,
             "png" => Png,
             "pdf" => Pdf,
+            "pptx" => Pptx,
             "python" | "py" => Python,
             "qmd" => Qmd,
             "r" => R,
             "rhai" => Rhai,
+            "ris" => Ris,
+            "rmd" => Rmd,
+            "rust" | "rs" => Rust,
+            "sql" => Sql,
             "shell" | "sh" => Shell,
             "smd" => Smd,
             "svg" => Svg,
             "swb" => Swb,
             "tailwind" => Tailwind,
+            "tei" => Tei,
             "tex" => Tex,
             "text" | "txt" => Text,
+            "textile" => Textile,
             "toml" => Toml,
+            "tsv" => Tsv,
+            "typst" | "typ" => Typst,
             "wav" => Wav,
             "webm" => WebM,
             "webp" => WebP,
+            "xlsx" => Xlsx,
             "yaml" | "yml" => Yaml,
             "unknown" => Unknown,
             _ => Other(name.to_string()),
@@ -329,6 +392,7 @@ impl Format {
         match media_type {
             "application/cbor" => Ok(Cbor),
             "application/cbor+zstd" => Ok(CborZst),
+            "application/geo+json" => Ok(GeoJson),
             "application/json" => Ok(Json),
             "application/json+zip" => Ok(JsonZip),
             "application/ld+json" => Ok(JsonLd),
@@ -355,6 +419,7 @@ impl Format {
         match self {
             Cbor => "application/cbor".to_string(),
             CborZst => "application/cbor+zstd".to_string(),
+            GeoJson => "application/geo+json".to_string(),
             Json => "application/json".to_string(),
             JsonZip => "application/json+zip".to_string(),
             JsonLd => "application/ld+json".to_string(),
@@ -395,11 +460,17 @@ impl Display for Format {
         use Format::*;
         f.write_str(match self {
             Aac => "aac",
+            Arrow => "arrow",
             AsciiMath => "asciimath",
             Avi => "avi",
             Bash => "bash",
+            Beamer => "beamer",
+            Bibtex => "bib",
             Cbor => "cbor",
             CborZst => "cbor.zstd",
+            Confluence => "confluence",
+            CslJson => "csl",
+            Csv => "csv",
             Css => "css",
             Debug => "debug",
             Directory => "directory",
@@ -407,7 +478,9 @@ impl Display for Format {
             Dom => "dom.html",
             Dot => "dot",
             Flac => "flac",
+            GeoJson => "geojson",
             Gif => "gif",
+            Go => "go",
             Html => "html",
             Ipynb => "ipynb",
             Jats => "jats",
@@ -421,32 +494,44 @@ impl Display for Format {
             Latex => "latex",
             Llmd => "llmd",
             Markdown => "md",
+            Mediawiki => "wiki",
             Mermaid => "mermaid",
             Mkv => "mkv",
             Mp3 => "mp3",
             Mp4 => "mp4",
             Myst => "myst",
+            Ods => "ods",
             Odt => "odt",
             Ogg => "ogg",
             Ogv => "ogv",
             Pandoc => "pandoc",
             Pdf => "pdf",
             Png => "png",
+            Pptx => "pptx",
             Python => "python",
             Qmd => "qmd",
             R => "r",
             Rhai => "rhai",
+            Ris => "ris",
+            Rmd => "rmd",
+            Rust => "rust",
+            Sql => "sql",
             Shell => "shell",
             Svg => "svg",
             Smd => "smd",
             Swb => "swb",
             Tailwind => "tailwind",
+            Tei => "tei",
             Tex => "tex",
             Text => "text",
+            Textile => "textile",
             Toml => "toml",
+            Tsv => "tsv",
+            Typst => "typ",
             Wav => "wav",
             WebM => "webm",
             WebP => "webp",
+            Xlsx => "xlsx",
             Yaml => "yaml",
             Other(name) => name,
             Unknown => "unknown",
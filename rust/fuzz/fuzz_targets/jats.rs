@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use codec::Codec;
+use codec_jats::JatsCodec;
+use stencila_fuzz::RUNTIME;
+
+fuzz_target!(|input: &str| {
+    // Decoding may return an error but should never panic
+    let _ = RUNTIME.block_on(JatsCodec {}.from_str(input, None));
+});
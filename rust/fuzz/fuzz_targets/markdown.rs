@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use codec::Codec;
+use codec_markdown::MarkdownCodec;
+use stencila_fuzz::RUNTIME;
+
+fuzz_target!(|input: &str| {
+    // Decoding may return an error but should never panic
+    let _ = RUNTIME.block_on(MarkdownCodec {}.from_str(input, None));
+});
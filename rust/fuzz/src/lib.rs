@@ -0,0 +1,24 @@
+//! Shared utilities for fuzz targets
+//!
+//! Fuzz targets for the text-format decoders. Run with `cargo fuzz` from
+//! this directory e.g.
+//!
+//! ```sh
+//! cargo +nightly fuzz run markdown
+//! ```
+//!
+//! Crashing inputs found by fuzzing should be minimized (`cargo fuzz tmin`)
+//! and added to the `corpus` directory of the target, and as regression
+//! tests using `common_dev::fuzz::regression` in the decoder's own crate.
+
+use common::{
+    once_cell::sync::Lazy,
+    tokio::runtime::{Builder, Runtime},
+};
+
+/// A single-threaded runtime for driving async decoders from fuzz targets
+pub static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
+    Builder::new_current_thread()
+        .build()
+        .expect("unable to create runtime")
+});
@@ -1,8 +1,9 @@
 use std::{
-    fs::{copy, create_dir_all, read_to_string, File},
+    fs::{copy, create_dir_all, read_to_string, write, File},
     hash::{Hash, Hasher},
     io::{Cursor, Write},
     path::{Path, PathBuf},
+    process::Command,
 };
 
 use base64::{engine::general_purpose::STANDARD, Engine as _};
@@ -12,6 +13,7 @@ use common::{
     once_cell::sync::Lazy,
     regex::{Captures, Regex},
     seahash::SeaHasher,
+    which::which,
 };
 use image::{ImageFormat, ImageReader};
 use mime_guess::from_path;
@@ -194,6 +196,104 @@ pub fn file_uri_to_path(
     Ok(image_path)
 }
 
+/**
+ * Render Mermaid code to an SVG image file
+ *
+ * Requires the Mermaid CLI (`mmdc`) to be installed.
+ *
+ * # Arguments
+ *
+ * - `code`: the Mermaid code for the diagram
+ *
+ * # Returns
+ *
+ * The path of the generated SVG file.
+ */
+pub fn mermaid_to_svg(code: &str) -> Result<PathBuf> {
+    code_to_svg(code, "mmd", |input, output| {
+        if which("mmdc").is_err() {
+            bail!("Mermaid CLI `mmdc` is not installed");
+        }
+
+        let status = Command::new("mmdc")
+            .arg("--input")
+            .arg(input)
+            .arg("--output")
+            .arg(output)
+            .status()?;
+        if !status.success() {
+            bail!("Failed to render Mermaid diagram using `mmdc`");
+        }
+
+        Ok(())
+    })
+}
+
+/**
+ * Render Graphviz DOT code to an SVG image file
+ *
+ * Requires Graphviz (`dot`) to be installed.
+ *
+ * # Arguments
+ *
+ * - `code`: the DOT code for the graph
+ *
+ * # Returns
+ *
+ * The path of the generated SVG file.
+ */
+pub fn dot_to_svg(code: &str) -> Result<PathBuf> {
+    code_to_svg(code, "dot", |input, output| {
+        if which("dot").is_err() {
+            bail!("Graphviz `dot` is not installed");
+        }
+
+        let status = Command::new("dot")
+            .arg("-Tsvg")
+            .arg(input)
+            .arg("-o")
+            .arg(output)
+            .status()?;
+        if !status.success() {
+            bail!("Failed to render DOT graph using `dot`");
+        }
+
+        Ok(())
+    })
+}
+
+/**
+ * Render diagram code to an SVG image file, with caching
+ *
+ * The SVG is written to a directory within the temporary directory with a
+ * name based on the hash of the code, so that unchanged diagrams are only
+ * rendered once.
+ */
+fn code_to_svg(
+    code: &str,
+    extension: &str,
+    render: impl Fn(&Path, &Path) -> Result<()>,
+) -> Result<PathBuf> {
+    let mut hash = SeaHasher::new();
+    code.hash(&mut hash);
+    let hash = hash.finish();
+
+    let dir = std::env::temp_dir().join("stencila-diagrams");
+    create_dir_all(&dir)?;
+
+    let output = dir.join(format!("{hash:x}.svg"));
+    if output.exists() {
+        return Ok(output);
+    }
+
+    let input = dir.join(format!("{hash:x}.{extension}"));
+    write(&input, code)?;
+
+    render(&input, &output)?;
+
+    Ok(output)
+}
+
 /// Transform all the <img> `src` attributes in a string, which are not HTTP, to paths
 pub fn img_srcs_to_paths(
     html: &str,
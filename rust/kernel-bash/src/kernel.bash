@@ -91,7 +91,8 @@ do
       # Return an empty list of packages
       ;;
     "$LIST")
-      # Return a list of variables (with limited type info and END flag after each)
+      # Return a list of variables, including exported environment variables,
+      # (with limited type info, a hint, and END flag after each)
       declare -p | sed -n 's/declare -\([-aAilnrtux]\) \([a-zA-Z_][a-zA-Z0-9_]*\)=\(.*\)/\1 \2/p' | while read -r stencila_options stencila_name; do
         if [[ $stencila_name == stencila_* ]]; then
           continue
@@ -99,18 +100,28 @@ do
         if [[ $stencila_options == *"i"* ]]; then
           stencila_node_type="Integer"
           stencila_native_type="integer"
+          stencila_hint="${!stencila_name}"
         elif [[ $stencila_options == *"a"* ]]; then
           stencila_node_type="Array"
           stencila_native_type="array"
+          eval "stencila_length=\${#${stencila_name}[@]}"
+          stencila_hint="{\"type\":\"ArrayHint\",\"length\":$stencila_length}"
+          unset stencila_length
         elif [[ $stencila_options == *"A"* ]]; then
           stencila_node_type="Object"
           stencila_native_type="associative array"
         else
           stencila_node_type="String"
           stencila_native_type="string"
+          stencila_value="${!stencila_name}"
+          stencila_hint="{\"type\":\"StringHint\",\"chars\":${#stencila_value}}"
+          unset stencila_value
         fi
-        echo "{\"type\":\"Variable\",\"name\":\"$stencila_name\",\"nodeType\":\"$stencila_node_type\",\"nativeType\":\"$stencila_native_type\",\"programmingLanguage\":\"Bash\"} $END"
-        unset stencila_options stencila_name stencila_node_type stencila_native_type
+        if [[ -n "$stencila_hint" ]]; then
+          stencila_hint=",\"hint\":$stencila_hint"
+        fi
+        echo "{\"type\":\"Variable\",\"name\":\"$stencila_name\",\"nodeType\":\"$stencila_node_type\",\"nativeType\":\"$stencila_native_type\",\"programmingLanguage\":\"Bash\"$stencila_hint} $END"
+        unset stencila_options stencila_name stencila_node_type stencila_native_type stencila_hint
       done
       ;;
     "$GET")
@@ -124,11 +135,17 @@ do
         if [[ "${#stencila_array[@]}" == "0" ]]; then
           printf "[]"
         else
-          printf "[${stencila_array[0]}"
-          for stencila_item in "${stencila_array[@]:1}"; do
-            printf ",$stencila_item"
+          printf "["
+          stencila_sep=""
+          for stencila_item in "${stencila_array[@]}"; do
+            # Quote items which are not JSON numbers, booleans or null
+            if [[ ! "$stencila_item" =~ ^(-?[0-9]+(\.[0-9]+)?|true|false|null)$ ]]; then
+              stencila_item="\"${stencila_item//\"/\\\"}\""
+            fi
+            printf "$stencila_sep$stencila_item"
+            stencila_sep=","
           done
-          unset stencila_item
+          unset stencila_item stencila_sep
           printf "]"
         fi
         unset stencila_array
@@ -140,8 +157,32 @@ do
       ;;
     "$SET")
       # Set a variable. Uses -x option (for export) so that variables
-      # are available to forks
-      declare -x "${stencila_lines[1]}=${stencila_lines[2]}"
+      # are available to forks and to commands run from chunks.
+      # Values arrive as JSON so strings are unquoted and arrays are
+      # converted to Bash indexed arrays.
+      stencila_name="${stencila_lines[1]}"
+      stencila_value="${stencila_lines[2]}"
+      if [[ "$stencila_value" =~ ^\".*\"$ ]]; then
+        # JSON string: strip surrounding quotes and unescape inner quotes
+        stencila_value="${stencila_value:1:${#stencila_value}-2}"
+        stencila_value="${stencila_value//\\\"/\"}"
+        declare -x "$stencila_name=$stencila_value"
+      elif [[ "$stencila_value" =~ ^\[.*\]$ ]]; then
+        # JSON array: split on commas (items containing commas are not supported)
+        IFS="," read -ra stencila_array <<< "${stencila_value:1:${#stencila_value}-2}"
+        stencila_items=()
+        for stencila_item in "${stencila_array[@]}"; do
+          if [[ "$stencila_item" =~ ^\".*\"$ ]]; then
+            stencila_item="${stencila_item:1:${#stencila_item}-2}"
+          fi
+          stencila_items+=("$stencila_item")
+        done
+        eval "$stencila_name=(\"\${stencila_items[@]}\")"
+        unset stencila_array stencila_items stencila_item
+      else
+        declare -x "$stencila_name=$stencila_value"
+      fi
+      unset stencila_name stencila_value BASH_REMATCH
       ;;
     "$REMOVE")
       # Remove a variable
@@ -0,0 +1,701 @@
+use std::{
+    path::Path,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc,
+    },
+};
+
+use duckdb::{
+    types::{Value, ValueRef},
+    Connection,
+};
+
+use kernel::{
+    common::{
+        async_trait::async_trait,
+        eyre::{bail, OptionExt, Result},
+        itertools::Itertools,
+        tokio::{
+            self,
+            sync::{mpsc, watch},
+        },
+        tracing,
+    },
+    format::Format,
+    generate_id,
+    schema::{
+        ArrayValidator, BooleanValidator, Datatable, DatatableColumn, DatatableColumnHint,
+        DatatableHint, ExecutionMessage, Hint, IntegerValidator, MessageLevel, Node, Null,
+        NumberValidator, Primitive, SoftwareApplication, SoftwareApplicationOptions,
+        SoftwareSourceCode, StringValidator, Validator, Variable,
+    },
+    Kernel, KernelInstance, KernelSignal, KernelStatus, KernelType,
+};
+
+/// A kernel for executing SQL against an in-process DuckDB database
+///
+/// Query results are returned as `Datatable` nodes and `Datatable`s can be
+/// `set` as tables in the database so that data can be passed between SQL
+/// chunks and chunks in other languages (e.g. Python, R) in a document.
+#[derive(Default)]
+pub struct DuckDbKernel;
+
+const NAME: &str = "duckdb";
+
+impl Kernel for DuckDbKernel {
+    fn name(&self) -> String {
+        NAME.to_string()
+    }
+
+    fn r#type(&self) -> KernelType {
+        KernelType::Database
+    }
+
+    fn supports_languages(&self) -> Vec<Format> {
+        vec![Format::Sql]
+    }
+
+    fn create_instance(&self) -> Result<Box<dyn KernelInstance>> {
+        Ok(Box::new(DuckDbKernelInstance::new()))
+    }
+}
+
+pub struct DuckDbKernelInstance {
+    /// The name of this instance
+    id: String,
+
+    /// The connection to the in-process database for this instance
+    connection: Option<Connection>,
+
+    /// The status of this instance
+    status: Arc<AtomicU8>,
+
+    /// A channel sender for the status of this instance
+    status_sender: watch::Sender<KernelStatus>,
+
+    /// A channel sender for sending signals to the instance
+    signal_sender: mpsc::Sender<KernelSignal>,
+}
+
+#[async_trait]
+impl KernelInstance for DuckDbKernelInstance {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn status(&self) -> Result<KernelStatus> {
+        Ok(self.get_status())
+    }
+
+    fn status_watcher(&self) -> Result<watch::Receiver<KernelStatus>> {
+        Ok(self.status_sender.subscribe())
+    }
+
+    fn signal_sender(&self) -> Result<mpsc::Sender<KernelSignal>> {
+        Ok(self.signal_sender.clone())
+    }
+
+    async fn start(&mut self, _directory: &Path) -> Result<()> {
+        tracing::trace!("Starting DuckDB kernel instance");
+
+        self.connection = Some(Connection::open_in_memory()?);
+
+        self.set_status(KernelStatus::Ready)
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        tracing::trace!("Stopping DuckDB kernel instance");
+
+        self.connection = None;
+
+        self.set_status(KernelStatus::Stopped)
+    }
+
+    async fn execute(&mut self, code: &str) -> Result<(Vec<Node>, Vec<ExecutionMessage>)> {
+        tracing::trace!("Executing SQL in DuckDB");
+
+        self.set_status(KernelStatus::Busy)?;
+
+        let mut outputs = Vec::new();
+        let mut messages = Vec::new();
+
+        // Execute each statement, returning the result set of those that
+        // produce one (e.g. SELECT) as a `Datatable`
+        for statement in split_statements(code) {
+            if statement.trim().is_empty() {
+                continue;
+            }
+
+            match self.query(statement) {
+                Ok(Some(datatable)) => outputs.push(Node::Datatable(datatable)),
+                Ok(None) => {}
+                Err(error) => {
+                    messages.push(ExecutionMessage::new(MessageLevel::Error, error.to_string()));
+                    break;
+                }
+            }
+        }
+
+        self.set_status(KernelStatus::Ready)?;
+
+        Ok((outputs, messages))
+    }
+
+    async fn evaluate(&mut self, code: &str) -> Result<(Node, Vec<ExecutionMessage>)> {
+        tracing::trace!("Evaluating SQL expression in DuckDB");
+
+        if code.trim().is_empty() {
+            return Ok((Node::Null(Null), Vec::new()));
+        }
+
+        // Evaluate as a single-valued SELECT so that expressions such as
+        // `1 + 1` or `count(*) FROM data` can be used
+        let (mut outputs, messages) = self.execute(&["SELECT ", code.trim()].concat()).await?;
+
+        let node = match outputs.pop() {
+            Some(Node::Datatable(datatable)) => datatable
+                .columns
+                .first()
+                .and_then(|column| column.values.first().cloned())
+                .map(primitive_to_node)
+                .unwrap_or(Node::Null(Null)),
+            Some(node) => node,
+            None => Node::Null(Null),
+        };
+
+        Ok((node, messages))
+    }
+
+    async fn info(&mut self) -> Result<SoftwareApplication> {
+        tracing::trace!("Getting DuckDB runtime info");
+
+        let version = self
+            .connection()?
+            .query_row("SELECT version()", [], |row| row.get::<_, String>(0))
+            .ok()
+            .map(|version| version.trim_start_matches('v').to_string());
+
+        Ok(SoftwareApplication {
+            name: "DuckDB".to_string(),
+            options: Box::new(SoftwareApplicationOptions {
+                software_version: version,
+                operating_system: Some(std::env::consts::OS.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    async fn packages(&mut self) -> Result<Vec<SoftwareSourceCode>> {
+        tracing::trace!("Getting DuckDB packages");
+
+        Ok(Vec::new())
+    }
+
+    async fn list(&mut self) -> Result<Vec<Variable>> {
+        tracing::trace!("Listing DuckDB tables");
+
+        self.set_status(KernelStatus::Busy)?;
+
+        let connection = self.connection()?;
+
+        let mut tables = Vec::new();
+        {
+            let mut statement = connection.prepare(
+                "SELECT table_name FROM information_schema.tables WHERE table_schema = 'main'",
+            )?;
+            let mut rows = statement.query([])?;
+            while let Some(row) = rows.next()? {
+                tables.push(row.get::<_, String>(0)?);
+            }
+        }
+
+        let mut variables = Vec::new();
+        for name in tables {
+            let rows: i64 = connection.query_row(
+                &format!("SELECT count(*) FROM \"{name}\""),
+                [],
+                |row| row.get(0),
+            )?;
+
+            let mut columns = Vec::new();
+            {
+                let mut statement = connection.prepare(
+                    "SELECT column_name, data_type FROM information_schema.columns WHERE table_name = ?",
+                )?;
+                let mut column_rows = statement.query([&name])?;
+                while let Some(row) = column_rows.next()? {
+                    let column: String = row.get(0)?;
+                    let data_type: String = row.get(1)?;
+                    columns.push(DatatableColumnHint::new(
+                        column,
+                        sql_type_to_item_type(&data_type).to_string(),
+                    ));
+                }
+            }
+
+            variables.push(Variable {
+                name,
+                programming_language: Some("SQL".to_string()),
+                native_type: Some("table".to_string()),
+                node_type: Some("Datatable".to_string()),
+                hint: Some(Hint::DatatableHint(DatatableHint::new(rows, columns))),
+                ..Default::default()
+            });
+        }
+
+        self.set_status(KernelStatus::Ready)?;
+
+        Ok(variables)
+    }
+
+    async fn get(&mut self, name: &str) -> Result<Option<Node>> {
+        tracing::trace!("Getting DuckDB table");
+
+        self.set_status(KernelStatus::Busy)?;
+
+        let datatable = self.query(&format!("SELECT * FROM \"{name}\"")).ok().flatten();
+
+        self.set_status(KernelStatus::Ready)?;
+
+        Ok(datatable.map(Node::Datatable))
+    }
+
+    async fn set(&mut self, name: &str, node: &Node) -> Result<()> {
+        tracing::trace!("Setting DuckDB table");
+
+        let Node::Datatable(datatable) = node else {
+            bail!(
+                "Setting variables of type `{}` is not supported by DuckDB kernels",
+                node.node_type()
+            )
+        };
+
+        self.set_status(KernelStatus::Busy)?;
+
+        let connection = self.connection()?;
+
+        let columns = datatable
+            .columns
+            .iter()
+            .map(|column| {
+                let sql_type = match column.validator.as_ref().and_then(|validator| {
+                    validator.items_validator.as_deref()
+                }) {
+                    Some(Validator::BooleanValidator(..)) => "BOOLEAN",
+                    Some(Validator::IntegerValidator(..)) => "BIGINT",
+                    Some(Validator::NumberValidator(..)) => "DOUBLE",
+                    _ => "VARCHAR",
+                };
+                format!("\"{}\" {}", column.name, sql_type)
+            })
+            .join(", ");
+        connection.execute_batch(&format!(
+            "CREATE OR REPLACE TABLE \"{name}\" ({columns})"
+        ))?;
+
+        let rows = datatable
+            .columns
+            .iter()
+            .map(|column| column.values.len())
+            .max()
+            .unwrap_or(0);
+        if rows > 0 {
+            let placeholders = vec!["?"; datatable.columns.len()].join(", ");
+            let mut statement = connection.prepare(&format!(
+                "INSERT INTO \"{name}\" VALUES ({placeholders})"
+            ))?;
+            for row in 0..rows {
+                let values = datatable
+                    .columns
+                    .iter()
+                    .map(|column| primitive_to_value(column.values.get(row)))
+                    .collect_vec();
+                statement.execute(duckdb::params_from_iter(values))?;
+            }
+        }
+
+        self.set_status(KernelStatus::Ready)
+    }
+
+    async fn remove(&mut self, name: &str) -> Result<()> {
+        tracing::trace!("Removing DuckDB table");
+
+        self.set_status(KernelStatus::Busy)?;
+
+        self.connection()?
+            .execute_batch(&format!("DROP TABLE IF EXISTS \"{name}\""))?;
+
+        self.set_status(KernelStatus::Ready)
+    }
+}
+
+impl DuckDbKernelInstance {
+    /// Create a new kernel instance
+    fn new() -> Self {
+        let status = Arc::new(AtomicU8::new(KernelStatus::Pending.into()));
+        let (status_sender, ..) = watch::channel(KernelStatus::Pending);
+
+        let (signal_sender, mut signal_receiver) = mpsc::channel(1);
+
+        // Start a task to handle signals
+        let status_clone = status.clone();
+        tokio::spawn(async move {
+            while let Some(kernel_signal) = signal_receiver.recv().await {
+                if matches!(kernel_signal, KernelSignal::Terminate | KernelSignal::Kill) {
+                    status_clone.store(KernelStatus::Stopped.into(), Ordering::SeqCst);
+                }
+            }
+        });
+
+        Self {
+            id: generate_id(NAME),
+            connection: None,
+            status,
+            status_sender,
+            signal_sender,
+        }
+    }
+
+    /// Get the connection to the database, erroring if not started
+    fn connection(&self) -> Result<&Connection> {
+        self.connection.as_ref().ok_or_eyre("Kernel not started")
+    }
+
+    /// Get the status of the kernel instance
+    fn get_status(&self) -> KernelStatus {
+        self.status.load(Ordering::SeqCst).into()
+    }
+
+    /// Set the status of the kernel instance and notify watchers if there was a change
+    fn set_status(&mut self, status: KernelStatus) -> Result<()> {
+        self.status.store(status.into(), Ordering::SeqCst);
+
+        self.status_sender.send_if_modified(|previous| {
+            if status != *previous {
+                tracing::trace!(
+                    "Status of `{}` kernel changed from `{previous}` to `{status}`",
+                    self.id()
+                );
+                *previous = status;
+                true
+            } else {
+                false
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Run a SQL statement, returning a [`Datatable`] if it produces a result set
+    fn query(&self, statement: &str) -> Result<Option<Datatable>> {
+        let connection = self.connection()?;
+
+        let mut prepared = connection.prepare(statement)?;
+        let mut rows = prepared.query([])?;
+
+        let mut names: Vec<String> = Vec::new();
+        let mut columns: Vec<Vec<Primitive>> = Vec::new();
+        while let Some(row) = rows.next()? {
+            if names.is_empty() {
+                names = row.as_ref().column_names();
+                if names.is_empty() {
+                    // Not a result producing statement
+                    return Ok(None);
+                }
+                columns = vec![Vec::new(); names.len()];
+            }
+
+            for (index, values) in columns.iter_mut().enumerate() {
+                values.push(value_ref_to_primitive(row.get_ref(index)?));
+            }
+        }
+
+        if names.is_empty() {
+            return Ok(None);
+        }
+
+        let columns = names
+            .into_iter()
+            .zip(columns)
+            .map(|(name, values)| {
+                let items_validator = values
+                    .iter()
+                    .find(|value| !matches!(value, Primitive::Null(..)))
+                    .and_then(|value| match value {
+                        Primitive::Boolean(..) => {
+                            Some(Validator::BooleanValidator(BooleanValidator::new()))
+                        }
+                        Primitive::Integer(..) => {
+                            Some(Validator::IntegerValidator(IntegerValidator::new()))
+                        }
+                        Primitive::Number(..) => {
+                            Some(Validator::NumberValidator(NumberValidator::new()))
+                        }
+                        Primitive::String(..) => {
+                            Some(Validator::StringValidator(StringValidator::new()))
+                        }
+                        _ => None,
+                    });
+
+                DatatableColumn {
+                    name,
+                    values,
+                    validator: items_validator.map(|validator| ArrayValidator {
+                        items_validator: Some(Box::new(validator)),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        Ok(Some(Datatable {
+            columns,
+            ..Default::default()
+        }))
+    }
+}
+
+/// Split SQL code into statements at semicolons
+///
+/// A naive split on semicolons would break statements containing semicolons
+/// within string literals (e.g. `INSERT INTO t VALUES ('a;b')`), quoted
+/// identifiers, or comments, so these are skipped over.
+fn split_statements(code: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+
+    let mut chars = code.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            // Single quoted string or double quoted identifier: skip to the
+            // closing quote, treating a doubled quote as an escape
+            '\'' | '"' => loop {
+                match chars.next() {
+                    Some((.., next)) if next == c => {
+                        if chars.peek().is_some_and(|(.., peeked)| *peeked == c) {
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    Some(..) => {}
+                    None => break,
+                }
+            },
+            // Line comment: skip to the end of the line
+            '-' if chars.peek().is_some_and(|(.., peeked)| *peeked == '-') => {
+                for (.., next) in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+            }
+            // Block comment: skip to the closing delimiter
+            '/' if chars.peek().is_some_and(|(.., peeked)| *peeked == '*') => {
+                chars.next();
+                let mut prev = ' ';
+                for (.., next) in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            ';' => {
+                statements.push(&code[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    statements.push(&code[start..]);
+
+    statements
+}
+
+/// Convert a DuckDB [`ValueRef`] to a [`Primitive`]
+fn value_ref_to_primitive(value: ValueRef) -> Primitive {
+    match value {
+        ValueRef::Null => Primitive::Null(Null),
+        ValueRef::Boolean(value) => Primitive::Boolean(value),
+        ValueRef::TinyInt(value) => Primitive::Integer(value as i64),
+        ValueRef::SmallInt(value) => Primitive::Integer(value as i64),
+        ValueRef::Int(value) => Primitive::Integer(value as i64),
+        ValueRef::BigInt(value) => Primitive::Integer(value),
+        ValueRef::UTinyInt(value) => Primitive::Integer(value as i64),
+        ValueRef::USmallInt(value) => Primitive::Integer(value as i64),
+        ValueRef::UInt(value) => Primitive::Integer(value as i64),
+        ValueRef::UBigInt(value) => Primitive::Integer(value as i64),
+        ValueRef::Float(value) => Primitive::Number(value as f64),
+        ValueRef::Double(value) => Primitive::Number(value),
+        ValueRef::Text(value) => Primitive::String(String::from_utf8_lossy(value).to_string()),
+        value => Primitive::String(format!("{value:?}")),
+    }
+}
+
+/// Convert a [`Primitive`] to a DuckDB [`Value`] for binding to a statement
+fn primitive_to_value(primitive: Option<&Primitive>) -> Value {
+    match primitive {
+        Some(Primitive::Boolean(value)) => Value::Boolean(*value),
+        Some(Primitive::Integer(value)) => Value::BigInt(*value),
+        Some(Primitive::Number(value)) => Value::Double(*value),
+        Some(Primitive::String(value)) => Value::Text(value.clone()),
+        _ => Value::Null,
+    }
+}
+
+/// Convert a [`Primitive`] to a [`Node`]
+fn primitive_to_node(primitive: Primitive) -> Node {
+    match primitive {
+        Primitive::Null(value) => Node::Null(value),
+        Primitive::Boolean(value) => Node::Boolean(value),
+        Primitive::Integer(value) => Node::Integer(value),
+        Primitive::UnsignedInteger(value) => Node::UnsignedInteger(value),
+        Primitive::Number(value) => Node::Number(value),
+        Primitive::String(value) => Node::String(value),
+        Primitive::Array(value) => Node::Array(value),
+        Primitive::Object(value) => Node::Object(value),
+    }
+}
+
+/// Map a SQL data type to the type of items in a Stencila `DatatableColumn`
+fn sql_type_to_item_type(data_type: &str) -> &'static str {
+    match data_type.to_uppercase().as_str() {
+        "BOOLEAN" => "Boolean",
+        "TINYINT" | "SMALLINT" | "INTEGER" | "BIGINT" | "HUGEINT" | "UTINYINT" | "USMALLINT"
+        | "UINTEGER" | "UBIGINT" => "Integer",
+        "FLOAT" | "REAL" | "DOUBLE" | "DECIMAL" => "Number",
+        "VARCHAR" | "TEXT" => "String",
+        _ => "String",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_dev::pretty_assertions::assert_eq;
+    use kernel::{common::tokio, schema::Node, tests::start_instance};
+
+    use super::*;
+
+    /// Test execution of SQL returning result sets as Datatables
+    #[test_log::test(tokio::test)]
+    async fn execution() -> Result<()> {
+        let Some(mut instance) = start_instance::<DuckDbKernel>().await? else {
+            return Ok(());
+        };
+
+        let (outputs, messages) = instance
+            .execute("CREATE TABLE data (a BIGINT); INSERT INTO data VALUES (1), (2);")
+            .await?;
+        assert_eq!(messages, vec![]);
+        assert_eq!(outputs, vec![]);
+
+        let (outputs, messages) = instance.execute("SELECT * FROM data").await?;
+        assert_eq!(messages, vec![]);
+        assert_eq!(outputs.len(), 1);
+        let Some(Node::Datatable(datatable)) = outputs.first() else {
+            bail!("Expected a Datatable")
+        };
+        assert_eq!(datatable.columns.len(), 1);
+        assert_eq!(
+            datatable.columns[0].values,
+            vec![Primitive::Integer(1), Primitive::Integer(2)]
+        );
+
+        Ok(())
+    }
+
+    /// Test splitting of SQL code into statements
+    #[test]
+    fn splitting() {
+        assert_eq!(split_statements("SELECT 1"), vec!["SELECT 1"]);
+        assert_eq!(
+            split_statements("SELECT 1; SELECT 2;"),
+            vec!["SELECT 1", " SELECT 2", ""]
+        );
+        assert_eq!(
+            split_statements("INSERT INTO t VALUES ('a;b'); SELECT 1"),
+            vec!["INSERT INTO t VALUES ('a;b')", " SELECT 1"]
+        );
+        assert_eq!(
+            split_statements("SELECT 'it''s;ok'"),
+            vec!["SELECT 'it''s;ok'"]
+        );
+        assert_eq!(
+            split_statements("SELECT 1 -- comment; here\n; SELECT 2"),
+            vec!["SELECT 1 -- comment; here\n", " SELECT 2"]
+        );
+        assert_eq!(
+            split_statements("SELECT /* a;b */ 1; SELECT \";\""),
+            vec!["SELECT /* a;b */ 1", " SELECT \";\""]
+        );
+    }
+
+    /// Test that semicolons within string literals do not split statements
+    #[test_log::test(tokio::test)]
+    async fn semicolons_in_strings() -> Result<()> {
+        let Some(mut instance) = start_instance::<DuckDbKernel>().await? else {
+            return Ok(());
+        };
+
+        let (outputs, messages) = instance
+            .execute("CREATE TABLE t (s VARCHAR); INSERT INTO t VALUES ('a;b'); SELECT * FROM t")
+            .await?;
+        assert_eq!(messages, vec![]);
+        let Some(Node::Datatable(datatable)) = outputs.first() else {
+            bail!("Expected a Datatable")
+        };
+        assert_eq!(
+            datatable.columns[0].values,
+            vec![Primitive::String("a;b".to_string())]
+        );
+
+        Ok(())
+    }
+
+    /// Test evaluation of SQL expressions
+    #[test_log::test(tokio::test)]
+    async fn evaluation() -> Result<()> {
+        let Some(mut instance) = start_instance::<DuckDbKernel>().await? else {
+            return Ok(());
+        };
+
+        let (node, messages) = instance.evaluate("1 + 1").await?;
+        assert_eq!(messages, vec![]);
+        assert_eq!(node, Node::Integer(2));
+
+        Ok(())
+    }
+
+    /// Test setting and getting Datatables as tables
+    #[test_log::test(tokio::test)]
+    async fn set_get() -> Result<()> {
+        let Some(mut instance) = start_instance::<DuckDbKernel>().await? else {
+            return Ok(());
+        };
+
+        let datatable = Datatable {
+            columns: vec![DatatableColumn {
+                name: "x".to_string(),
+                values: vec![Primitive::Integer(1), Primitive::Integer(2)],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        instance.set("data", &Node::Datatable(datatable)).await?;
+
+        let (node, messages) = instance.evaluate("count(*) FROM data").await?;
+        assert_eq!(messages, vec![]);
+        assert_eq!(node, Node::Integer(2));
+
+        let variables = instance.list().await?;
+        assert_eq!(variables.len(), 1);
+        assert_eq!(variables[0].name, "data");
+        assert_eq!(variables[0].node_type.as_deref(), Some("Datatable"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,324 @@
+// During development it can be useful to run this kernel script directly e.g.
+//
+//     DEV=true yaegi run rust/kernel-go/src/kernel.go
+//
+// Use Ctrl+D to quit.
+
+package main
+
+import (
+	"bufio"
+	"encoding/json"
+	"fmt"
+	"os"
+	"reflect"
+	"regexp"
+	"runtime"
+	"strings"
+
+	"github.com/traefik/yaegi/interp"
+	"github.com/traefik/yaegi/stdlib"
+)
+
+var dev = os.Getenv("DEV") != ""
+
+func flag(devFlag, unicode string) string {
+	if dev {
+		return devFlag
+	}
+	return unicode
+}
+
+var (
+	READY  = flag("READY", "\U0010ACDC")
+	LINE   = flag("|", "\U0010ABBA")
+	EXEC   = flag("EXEC", "\U0010B522")
+	EVAL   = flag("EVAL", "\U001010CC")
+	FORK   = flag("FORK", "\U0010DE70")
+	INFO   = flag("INFO", "\U0010EE15")
+	PKGS   = flag("PKGS", "\U0010BEC4")
+	LIST   = flag("LIST", "\U0010C155")
+	GET    = flag("GET", "\U0010A51A")
+	SET    = flag("SET", "\U00107070")
+	REMOVE = flag("REMOVE", "\U0010C41C")
+	END    = flag("END", "\U0010CB40")
+)
+
+var stdout = os.Stdout
+var stderr = os.Stderr
+
+// The interpreter in which all tasks are evaluated so that variables
+// and imports persist across tasks
+var i = interp.New(interp.Options{Stdout: stdout, Stderr: stderr})
+
+// Matches lines whose value should not be emitted as an output
+// (declarations, assignments and imports)
+var assignRegex = regexp.MustCompile(`^\s*(?:var\s+|const\s+|type\s+|func\s+|import\s|package\s|[\w_.\[\]]+\s*(?:,\s*[\w_.\[\]]+\s*)*(?::=|=[^=]))`)
+
+// Write an `ExecutionMessage` to stderr
+func message(level string, err error) {
+	msg := map[string]interface{}{
+		"type":    "ExecutionMessage",
+		"level":   level,
+		"message": err.Error(),
+	}
+	if panicErr, ok := err.(interp.Panic); ok {
+		msg["stackTrace"] = string(panicErr.Stack)
+	}
+	json_, _ := json.Marshal(msg)
+	fmt.Fprintf(stderr, "%s%s\n", json_, END)
+}
+
+// Execute lines of code
+func execute(lines []string) {
+	code := strings.Join(lines, "\n")
+
+	last := ""
+	for index := len(lines) - 1; index >= 0; index-- {
+		if strings.TrimSpace(lines[index]) != "" {
+			last = lines[index]
+			break
+		}
+	}
+
+	value, err := i.Eval(code)
+	if err != nil {
+		message("Exception", err)
+		return
+	}
+
+	if value.IsValid() && value.CanInterface() && !assignRegex.MatchString(last) {
+		if json_, err := json.Marshal(value.Interface()); err == nil {
+			fmt.Fprintf(stdout, "%s", json_)
+		}
+	}
+}
+
+// Evaluate an expression
+func evaluate(expression string) {
+	value, err := i.Eval(expression)
+	if err != nil {
+		message("Exception", err)
+		return
+	}
+
+	if value.IsValid() && value.CanInterface() {
+		if json_, err := json.Marshal(value.Interface()); err == nil {
+			fmt.Fprintf(stdout, "%s", json_)
+		}
+	}
+}
+
+// Get runtime information
+func info() {
+	app := map[string]interface{}{
+		"type":            "SoftwareApplication",
+		"name":            "Go",
+		"softwareVersion": strings.TrimPrefix(runtime.Version(), "go"),
+		"operatingSystem": runtime.GOOS + " " + runtime.GOARCH,
+	}
+	json_, _ := json.Marshal(app)
+	fmt.Fprintf(stdout, "%s", json_)
+}
+
+// Get a list of packages available
+//
+// Symbol map keys have the package name appended to the import path
+// (e.g. `encoding/json/json`) so the last segment is trimmed off
+func packages() {
+	seen := map[string]bool{}
+	for path := range stdlib.Symbols {
+		name := path
+		if index := strings.LastIndex(path, "/"); index != -1 {
+			name = path[:index]
+		}
+		if seen[name] {
+			continue
+		}
+		seen[name] = true
+
+		ssc := map[string]interface{}{
+			"type":                "SoftwareSourceCode",
+			"programmingLanguage": "Go",
+			"name":                name,
+		}
+		json_, _ := json.Marshal(ssc)
+		fmt.Fprintf(stdout, "%s%s\n", json_, END)
+	}
+}
+
+// Get the native type, node type and hint for a value
+func nodeTypesHint(value reflect.Value) (string, string, interface{}) {
+	if !value.IsValid() {
+		return "nil", "Null", nil
+	}
+
+	kind := value.Kind()
+	for kind == reflect.Interface || kind == reflect.Ptr {
+		if value.IsNil() {
+			return value.Type().String(), "Null", nil
+		}
+		value = value.Elem()
+		kind = value.Kind()
+	}
+
+	nativeType := value.Type().String()
+	switch kind {
+	case reflect.Bool:
+		return nativeType, "Boolean", value.Bool()
+	case reflect.Int, reflect.Int8, reflect.Int16, reflect.Int32, reflect.Int64,
+		reflect.Uint, reflect.Uint8, reflect.Uint16, reflect.Uint32, reflect.Uint64:
+		return nativeType, "Integer", value.Interface()
+	case reflect.Float32, reflect.Float64:
+		return nativeType, "Number", value.Float()
+	case reflect.String:
+		return nativeType, "String", map[string]interface{}{
+			"type":  "StringHint",
+			"chars": len([]rune(value.String())),
+		}
+	case reflect.Slice, reflect.Array:
+		return nativeType, "Array", map[string]interface{}{
+			"type":   "ArrayHint",
+			"length": value.Len(),
+		}
+	case reflect.Map:
+		keys := []interface{}{}
+		for _, key := range value.MapKeys() {
+			keys = append(keys, fmt.Sprint(key.Interface()))
+		}
+		return nativeType, "Object", map[string]interface{}{
+			"type":   "ObjectHint",
+			"length": value.Len(),
+			"keys":   keys,
+		}
+	case reflect.Struct:
+		return nativeType, "Object", nil
+	default:
+		return nativeType, "Object", nil
+	}
+}
+
+// List variables defined in the interpreter
+func list() {
+	for name, value := range i.Symbols("main")["main"] {
+		kind := value.Kind()
+		if kind == reflect.Func || kind == reflect.Invalid {
+			continue
+		}
+
+		nativeType, nodeType, hint := nodeTypesHint(value)
+
+		variable := map[string]interface{}{
+			"type":                "Variable",
+			"name":                name,
+			"programmingLanguage": "Go",
+			"nativeType":          nativeType,
+			"nodeType":            nodeType,
+			"hint":                hint,
+		}
+		json_, _ := json.Marshal(variable)
+		fmt.Fprintf(stdout, "%s%s\n", json_, END)
+	}
+}
+
+// Get a variable
+func get(name string) {
+	value, err := i.Eval(name)
+	if err != nil || !value.IsValid() || !value.CanInterface() {
+		return
+	}
+
+	if json_, err := json.Marshal(value.Interface()); err == nil {
+		fmt.Fprintf(stdout, "%s", json_)
+	}
+}
+
+// Set a variable
+//
+// The value is deserialized from JSON in Go, rather than in the interpreter,
+// so that the variable gets a concrete type where possible
+func set(name, json_ string) {
+	var value interface{}
+	if err := json.Unmarshal([]byte(json_), &value); err != nil {
+		message("Error", err)
+		return
+	}
+
+	code := ""
+	switch v := value.(type) {
+	case bool:
+		code = fmt.Sprintf("%s := %v", name, v)
+	case float64:
+		if float64(int64(v)) == v && !strings.ContainsAny(json_, ".eE") {
+			code = fmt.Sprintf("%s := %d", name, int64(v))
+		} else {
+			code = fmt.Sprintf("%s := %v", name, v)
+		}
+	case string:
+		code = fmt.Sprintf("%s := %q", name, v)
+	default:
+		// Fall back to unmarshalling within the interpreter for
+		// arrays, objects and null
+		code = fmt.Sprintf(
+			"%s := func() interface{} { var v interface{}; json.Unmarshal([]byte(%q), &v); return v }()",
+			name, json_,
+		)
+	}
+
+	if _, err := i.Eval(code); err != nil {
+		message("Error", err)
+	}
+}
+
+// Remove a variable
+//
+// The interpreter does not support removing symbols so the variable is
+// set to nil instead
+func remove(name string) {
+	i.Eval(fmt.Sprintf("%s = nil", name))
+}
+
+func main() {
+	i.Use(stdlib.Symbols)
+
+	// Import packages used by `set` so they are always available
+	i.Eval(`import "encoding/json"`)
+
+	// Indicate ready for first task
+	fmt.Fprintf(stdout, "%s\n", READY)
+	fmt.Fprintf(stderr, "%s\n", READY)
+
+	scanner := bufio.NewScanner(os.Stdin)
+	scanner.Buffer(make([]byte, 1024*1024), 1024*1024*64)
+	for scanner.Scan() {
+		task := scanner.Text()
+		lines := strings.Split(task, LINE)
+
+		switch lines[0] {
+		case EXEC:
+			execute(lines[1:])
+		case EVAL:
+			evaluate(lines[1])
+		case INFO:
+			info()
+		case PKGS:
+			packages()
+		case LIST:
+			list()
+		case GET:
+			get(lines[1])
+		case SET:
+			set(lines[1], lines[2])
+		case REMOVE:
+			remove(lines[1])
+		case FORK:
+			message("Error", fmt.Errorf("forking is not supported by the Go kernel"))
+		default:
+			message("Error", fmt.Errorf("unrecognized task %s", lines[0]))
+		}
+
+		// Indicate ready for next task
+		fmt.Fprintf(stdout, "%s\n", READY)
+		fmt.Fprintf(stderr, "%s\n", READY)
+	}
+}
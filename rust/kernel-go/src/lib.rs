@@ -0,0 +1,196 @@
+use kernel_micro::{
+    common::eyre::Result, format::Format, Kernel, KernelAvailability, KernelForks, KernelInstance,
+    KernelInterrupt, KernelKill, KernelProvider, KernelTerminate, Microkernel,
+};
+
+/// A kernel for executing Go code using the `yaegi` interpreter
+#[derive(Default)]
+pub struct GoKernel;
+
+const NAME: &str = "go";
+
+impl Kernel for GoKernel {
+    fn name(&self) -> String {
+        NAME.to_string()
+    }
+
+    fn provider(&self) -> KernelProvider {
+        KernelProvider::Environment
+    }
+
+    fn availability(&self) -> KernelAvailability {
+        self.microkernel_availability()
+    }
+
+    fn supports_languages(&self) -> Vec<Format> {
+        vec![Format::Go]
+    }
+
+    fn supports_interrupt(&self) -> KernelInterrupt {
+        self.microkernel_supports_interrupt()
+    }
+
+    fn supports_terminate(&self) -> KernelTerminate {
+        self.microkernel_supports_terminate()
+    }
+
+    fn supports_kill(&self) -> KernelKill {
+        self.microkernel_supports_kill()
+    }
+
+    fn supports_forks(&self) -> KernelForks {
+        // Not supported because the `yaegi` interpreter state can not
+        // be copied to a child process
+        KernelForks::No
+    }
+
+    fn create_instance(&self) -> Result<Box<dyn KernelInstance>> {
+        self.microkernel_create_instance(NAME)
+    }
+}
+
+impl Microkernel for GoKernel {
+    fn executable_name(&self) -> String {
+        "yaegi".to_string()
+    }
+
+    fn executable_arguments(&self) -> Vec<String> {
+        vec!["run".to_string(), "{{script}}".to_string()]
+    }
+
+    fn microkernel_script_name(&self) -> String {
+        // `yaegi run` requires a `.go` extension on the script
+        [NAME, ".go"].concat()
+    }
+
+    fn microkernel_script(&self) -> String {
+        include_str!("kernel.go").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_dev::pretty_assertions::assert_eq;
+    use kernel_micro::{
+        common::tokio,
+        schema::{Node, Variable},
+        tests::{create_instance, start_instance},
+    };
+
+    use super::*;
+
+    // Pro-tip! Use get logs for these tests use:
+    //
+    // ```sh
+    // RUST_LOG=trace cargo test -p kernel-go -- --nocapture
+    // ```
+
+    /// Standard kernel test for execution of code
+    #[test_log::test(tokio::test)]
+    async fn execution() -> Result<()> {
+        let Some(instance) = create_instance::<GoKernel>().await? else {
+            return Ok(());
+        };
+
+        kernel_micro::tests::execution(
+            instance,
+            vec![
+                // Empty code: no outputs
+                ("", vec![], vec![]),
+                (" ", vec![], vec![]),
+                ("\n\n", vec![], vec![]),
+                // Only an expression: one output
+                (
+                    "
+1 + 1",
+                    vec![Node::Integer(2)],
+                    vec![],
+                ),
+                // Variables declared in one chunk are available in the next
+                (
+                    "
+a := 21
+b := 2",
+                    vec![],
+                    vec![],
+                ),
+                (
+                    "
+a * b",
+                    vec![Node::Integer(42)],
+                    vec![],
+                ),
+            ],
+        )
+        .await
+    }
+
+    /// Standard kernel test for evaluation of expressions
+    #[test_log::test(tokio::test)]
+    async fn evaluation() -> Result<()> {
+        let Some(instance) = create_instance::<GoKernel>().await? else {
+            return Ok(());
+        };
+
+        kernel_micro::tests::evaluation(
+            instance,
+            vec![
+                ("1 + 1", Node::Integer(2), None),
+                ("2.0 * 2.2", Node::Number(4.4), None),
+            ],
+        )
+        .await
+    }
+
+    /// Standard kernel test for getting and setting variables
+    #[test_log::test(tokio::test)]
+    async fn var_get_set() -> Result<()> {
+        let Some(mut instance) = start_instance::<GoKernel>().await? else {
+            return Ok(());
+        };
+
+        instance.set("a", &Node::Integer(123)).await?;
+        assert_eq!(instance.get("a").await?, Some(Node::Integer(123)));
+
+        instance.set("s", &Node::String("str".to_string())).await?;
+        assert_eq!(
+            instance.get("s").await?,
+            Some(Node::String("str".to_string()))
+        );
+
+        Ok(())
+    }
+
+    /// Standard kernel test for listing variables
+    #[test_log::test(tokio::test)]
+    async fn var_listing() -> Result<()> {
+        let Some(mut instance) = start_instance::<GoKernel>().await? else {
+            return Ok(());
+        };
+
+        instance.execute("answer := 42").await?;
+
+        let list = instance.list().await?;
+        assert!(list.iter().any(|variable| matches!(
+            variable,
+            Variable { name, .. } if name == "answer"
+        )));
+
+        Ok(())
+    }
+
+    /// Standard kernel test for getting runtime information
+    #[test_log::test(tokio::test)]
+    async fn info() -> Result<()> {
+        let Some(instance) = create_instance::<GoKernel>().await? else {
+            return Ok(());
+        };
+
+        let sw = kernel_micro::tests::info(instance).await?;
+        assert_eq!(sw.name, "Go");
+        assert!(sw.options.software_version.is_some());
+        assert!(sw.options.operating_system.is_some());
+
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ use std::{
     fs::write,
     path::{Path, PathBuf},
     process::Stdio,
+    time::Duration,
 };
 
 use which::which;
@@ -39,6 +40,47 @@ use kernel::{
     },
 };
 
+/// The environment variable for limiting the memory (in MiB) of microkernels
+///
+/// Applied using `RLIMIT_AS` so only available on POSIX systems. Useful for
+/// safely rendering untrusted documents on shared servers.
+pub const MEMORY_LIMIT_VAR: &str = "STENCILA_KERNEL_MEMORY_LIMIT";
+
+/// The environment variable for limiting the CPU time (in seconds) of microkernels
+///
+/// Applied using `RLIMIT_CPU` so only available on POSIX systems.
+pub const CPU_TIME_LIMIT_VAR: &str = "STENCILA_KERNEL_CPU_TIME_LIMIT";
+
+/// The environment variable for the interval (in seconds) between checks
+/// that a microkernel process is still alive
+///
+/// Set to `0` to disable liveness checking.
+pub const LIVENESS_INTERVAL_VAR: &str = "STENCILA_KERNEL_LIVENESS_INTERVAL";
+
+/// The default interval (in seconds) between liveness checks
+const LIVENESS_INTERVAL_SECS: u64 = 5;
+
+/// The environment variable for a container image to run microkernels in
+///
+/// When set, microkernels are launched inside a container based on the image
+/// with the workspace mounted read-only and no network access. This provides
+/// stronger isolation than resource limits alone: the kernel can not modify
+/// the workspace or reach the network.
+pub const CONTAINER_IMAGE_VAR: &str = "STENCILA_KERNEL_CONTAINER";
+
+/// The environment variable for the engine used to run microkernel containers
+///
+/// Defaults to `docker`; set to `podman` (or another Docker CLI compatible
+/// engine) to use that instead.
+pub const CONTAINER_ENGINE_VAR: &str = "STENCILA_KERNEL_CONTAINER_ENGINE";
+
+/// The environment variable to turn on reporting of resource metrics
+/// (CPU time, peak memory, wall time) for each execution
+///
+/// When set, each execution has a `Debug` level message appended with the
+/// metrics. Useful for profiling slow reports.
+pub const METRICS_VAR: &str = "STENCILA_KERNEL_METRICS";
+
 /// A specification for a minimal, lightweight execution kernel in a spawned process
 #[async_trait]
 pub trait Microkernel: Sync + Send + Kernel {
@@ -64,6 +106,14 @@ pub trait Microkernel: Sync + Send + Kernel {
         MessageLevel::Error
     }
 
+    /// Get the name of the file that the microkernel script is written to
+    ///
+    /// Defaults to the name of the kernel. Override this where the executable
+    /// requires the script to have a particular extension (e.g. `.go` for `yaegi`).
+    fn microkernel_script_name(&self) -> String {
+        self.name()
+    }
+
     /// Get the script to run for the microkernel
     ///
     /// For most microkernels the script will be written in an external file
@@ -73,6 +123,39 @@ pub trait Microkernel: Sync + Send + Kernel {
     /// file then include them and concat them in this method.
     fn microkernel_script(&self) -> String;
 
+    /// Get code for snapshotting the state of a kernel instance to a file
+    ///
+    /// The code should use the `{{path}}` placeholder for the path of the
+    /// snapshot file. Returning `None` (the default) indicates that the
+    /// microkernel does not support snapshots.
+    fn snapshot_code(&self) -> Option<String> {
+        None
+    }
+
+    /// Get code for restoring the state of a kernel instance from a file
+    ///
+    /// As for [`Microkernel::snapshot_code`] but for restoring state.
+    fn restore_code(&self) -> Option<String> {
+        None
+    }
+
+    /// Get code for writing a variable to a file in Arrow IPC format
+    ///
+    /// The code should use the `{{name}}` placeholder for the name of the
+    /// variable and the `{{path}}` placeholder for the path of the file,
+    /// and should error if the variable is not tabular. Returning `None`
+    /// (the default) indicates that the microkernel does not support Arrow.
+    fn arrow_dump_code(&self) -> Option<String> {
+        None
+    }
+
+    /// Get code for reading a variable from a file in Arrow IPC format
+    ///
+    /// As for [`Microkernel::arrow_dump_code`] but for reading the variable.
+    fn arrow_load_code(&self) -> Option<String> {
+        None
+    }
+
     /// Whether the executable used by this microkernel is available on this machine
     ///
     /// Returns `true` if an executable with `executable_name()` is in the `PATH`,
@@ -143,7 +226,7 @@ pub trait Microkernel: Sync + Send + Kernel {
         // Always write the script file, even if it already exists, to allow for changes
         // to the microkernel's script
         let kernels_dir = app::get_app_dir(app::DirType::Kernels, true)?;
-        let script_file = kernels_dir.join(self.name());
+        let script_file = kernels_dir.join(self.microkernel_script_name());
         write(&script_file, self.microkernel_script())?;
 
         // Get args to the executable and replace placeholder in args with the script path
@@ -161,6 +244,12 @@ pub trait Microkernel: Sync + Send + Kernel {
 
         let default_message_level = self.default_message_level();
 
+        let snapshot_code = self.snapshot_code();
+        let restore_code = self.restore_code();
+
+        let arrow_dump_code = self.arrow_dump_code();
+        let arrow_load_code = self.arrow_load_code();
+
         // Set up status and status channel
         let status = KernelStatus::Pending;
         let status_sender = MicrokernelInstance::setup_status_channel(status);
@@ -171,6 +260,10 @@ pub trait Microkernel: Sync + Send + Kernel {
             executable_name,
             executable_args,
             default_message_level,
+            snapshot_code,
+            restore_code,
+            arrow_dump_code,
+            arrow_load_code,
             executable_path: None,
             working_dir: None,
             command: None,
@@ -215,6 +308,18 @@ pub struct MicrokernelInstance {
     /// The default level for execution messages
     default_message_level: MessageLevel,
 
+    /// Code for snapshotting the state of the instance (with `{{path}}` placeholder)
+    snapshot_code: Option<String>,
+
+    /// Code for restoring the state of the instance (with `{{path}}` placeholder)
+    restore_code: Option<String>,
+
+    /// Code for writing a variable in Arrow IPC format (with `{{name}}` and `{{path}}` placeholders)
+    arrow_dump_code: Option<String>,
+
+    /// Code for reading a variable in Arrow IPC format (with `{{name}}` and `{{path}}` placeholders)
+    arrow_load_code: Option<String>,
+
     /// The child process (for main processes only, not forks)
     child: Option<Child>,
 
@@ -303,6 +408,9 @@ enum MicrokernelFlag {
     Set,
     /// Sent by Rust to signal the start of a `remove` task
     Remove,
+    /// Sent by the microkernel instance to request a line of input from the user
+    /// (e.g. for Python's `input()`), with the response written to its stdin
+    Input,
     /// Sent by the microkernel instance to signal the end of an output or message
     End,
 }
@@ -326,6 +434,7 @@ impl MicrokernelFlag {
             Get => "\u{10A51A}",
             Set => "\u{107070}",
             Remove => "\u{10C41C}",
+            Input => "\u{10D1D0}",
             End => "\u{10CB40}",
         }
     }
@@ -371,9 +480,60 @@ impl KernelInstance for MicrokernelInstance {
         let mut exec_args = self.executable_args.clone();
         let mut exec_path = None;
 
+        // Provision a virtual environment if Python dependencies are declared
+        // for the directory but no environment exists yet
+        if exec_name == "python3" {
+            if let Err(error) = ensure_venv(directory).await {
+                tracing::warn!("While provisioning virtual environment: {error}");
+            }
+        }
+
+        // Activate, and optionally restore, any renv project so that pinned
+        // R package versions are used
+        if exec_name == "Rscript" {
+            if let Err(error) = ensure_renv(directory).await {
+                tracing::warn!("While provisioning renv project: {error}");
+            }
+        }
+
+        // If a container image is configured, launch the kernel inside a
+        // container with the workspace mounted read-only. The app's kernels
+        // directory is also mounted so that the kernel script is available
+        // at the same path inside the container.
+        let container_image = env::var(CONTAINER_IMAGE_VAR).ok();
+        if let Some(image) = &container_image {
+            let engine = env::var(CONTAINER_ENGINE_VAR).unwrap_or_else(|..| "docker".to_string());
+
+            let workspace = directory.to_string_lossy().to_string();
+            let kernels_dir = app::get_app_dir(app::DirType::Kernels, false)?
+                .to_string_lossy()
+                .to_string();
+
+            exec_args.splice(
+                0..0,
+                [
+                    "run".to_string(),
+                    "--rm".to_string(),
+                    "--interactive".to_string(),
+                    "--network".to_string(),
+                    "none".to_string(),
+                    "--volume".to_string(),
+                    format!("{workspace}:{workspace}:ro"),
+                    "--volume".to_string(),
+                    format!("{kernels_dir}:{kernels_dir}:ro"),
+                    "--workdir".to_string(),
+                    workspace,
+                    image.to_string(),
+                    exec_name,
+                ],
+            );
+            exec_name = engine;
+        }
+
         // Search for an environment in the current, or a parent, directories
+        // (not relevant when running in a container)
         let mut current_dir = directory.to_path_buf();
-        loop {
+        while container_image.is_none() {
             // Check for devbox.json
             let devbox_path = current_dir.join("devbox.json");
             if devbox_path.is_file() {
@@ -428,8 +588,58 @@ impl KernelInstance for MicrokernelInstance {
             }
         }
 
+        // If this is the R microkernel and the workspace has an renv project
+        // then ensure the kernel uses its library. The `.Rprofile` created by
+        // renv is only sourced when the kernel starts in the project directory
+        // itself, so point `R_PROFILE_USER` at the activation script.
+        if self.executable_name == "Rscript" {
+            if let Some(activate) = directory
+                .ancestors()
+                .map(|dir| dir.join("renv").join("activate.R"))
+                .find(|path| path.exists())
+            {
+                command.env("R_PROFILE_USER", &activate);
+            }
+        }
+
         self.executable_path = Some(exec_path);
 
+        // Apply any configured resource limits to the child process.
+        // Limits are applied using `setrlimit` between `fork` and `exec`
+        // so are only available on POSIX systems.
+        let memory_limit = env::var(MEMORY_LIMIT_VAR)
+            .ok()
+            .and_then(|limit| limit.parse::<u64>().ok());
+        let cpu_time_limit = env::var(CPU_TIME_LIMIT_VAR)
+            .ok()
+            .and_then(|limit| limit.parse::<u64>().ok());
+        if memory_limit.is_some() || cpu_time_limit.is_some() {
+            #[cfg(unix)]
+            {
+                use nix::sys::resource::{setrlimit, Resource};
+
+                unsafe {
+                    command.pre_exec(move || {
+                        if let Some(mib) = memory_limit {
+                            let bytes = mib.saturating_mul(1024 * 1024);
+                            setrlimit(Resource::RLIMIT_AS, bytes, bytes)
+                                .map_err(std::io::Error::from)?;
+                        }
+                        if let Some(seconds) = cpu_time_limit {
+                            setrlimit(Resource::RLIMIT_CPU, seconds, seconds)
+                                .map_err(std::io::Error::from)?;
+                        }
+                        Ok(())
+                    });
+                }
+            }
+
+            #[cfg(not(unix))]
+            tracing::warn!(
+                "Kernel resource limits are not supported on this operating system and will be ignored"
+            );
+        }
+
         tracing::debug!(
             "Running `{} {}` in `{}`",
             exec_name,
@@ -488,6 +698,35 @@ impl KernelInstance for MicrokernelInstance {
         // Setup signalling channel
         self.signal_sender = Some(Self::setup_signals_channel(self.id.clone(), pid));
 
+        // Start a task to periodically check that the child process is still
+        // alive and notify status watchers if it has died. Without this, a
+        // kernel that crashes while idle is only discovered the next time
+        // code is sent to it, leaving dependent nodes pending.
+        let interval_secs = env::var(LIVENESS_INTERVAL_VAR)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(LIVENESS_INTERVAL_SECS);
+        if interval_secs > 0 {
+            let id = self.id.clone();
+            let status_sender = self.status_sender.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+                loop {
+                    interval.tick().await;
+
+                    if *status_sender.borrow() >= KernelStatus::Stopping {
+                        break;
+                    }
+
+                    if !process_is_alive(pid) {
+                        tracing::warn!("Process of `{id}` kernel has died");
+                        status_sender.send_replace(KernelStatus::Failed);
+                        break;
+                    }
+                }
+            });
+        }
+
         // Check status of the process in case start up errors
         // have caused it to fail
         let status = self
@@ -533,7 +772,56 @@ impl KernelInstance for MicrokernelInstance {
     }
 
     async fn execute(&mut self, code: &str) -> Result<(Vec<Node>, Vec<ExecutionMessage>)> {
-        self.send_receive(MicrokernelFlag::Exec, [code]).await
+        if env::var(METRICS_VAR).is_err() {
+            return self.send_receive(MicrokernelFlag::Exec, [code]).await;
+        }
+
+        // Measure the resources used by the execution and report them as
+        // a debug message alongside any messages from the kernel itself
+        let started = std::time::Instant::now();
+        let cpu_before = process_cpu_time(self.pid);
+
+        let (outputs, mut messages) = self.send_receive(MicrokernelFlag::Exec, [code]).await?;
+
+        let wall_time = started.elapsed();
+        let cpu_time = match (cpu_before, process_cpu_time(self.pid)) {
+            (Some(before), Some(after)) => Some(after.saturating_sub(before)),
+            _ => None,
+        };
+        let peak_memory = process_peak_memory(self.pid);
+
+        messages.push(ExecutionMessage {
+            level: MessageLevel::Debug,
+            message: format!(
+                "Execution took {wall} ms wall time, {cpu} CPU time, {memory} peak memory",
+                wall = wall_time.as_millis(),
+                cpu = cpu_time
+                    .map(|millis| format!("{millis} ms"))
+                    .unwrap_or_else(|| "unknown".to_string()),
+                memory = peak_memory
+                    .map(|kib| format!("{} MiB", kib / 1024))
+                    .unwrap_or_else(|| "unknown".to_string())
+            ),
+            error_type: Some("ResourceUsage".to_string()),
+            ..Default::default()
+        });
+
+        Ok((outputs, messages))
+    }
+
+    async fn execute_stream(
+        &mut self,
+        code: &str,
+        sender: mpsc::UnboundedSender<Node>,
+    ) -> Result<(Vec<Node>, Vec<ExecutionMessage>)> {
+        self.set_status(KernelStatus::Busy)?;
+
+        self.send(MicrokernelFlag::Exec, code).await?;
+        let result = self.receive_with(Some(&sender)).await;
+
+        self.set_status(KernelStatus::Ready)?;
+
+        result
     }
 
     async fn evaluate(&mut self, code: &str) -> Result<(Node, Vec<ExecutionMessage>)> {
@@ -650,6 +938,58 @@ impl KernelInstance for MicrokernelInstance {
         Ok(())
     }
 
+    async fn snapshot(&mut self, path: &Path) -> Result<()> {
+        let Some(code) = &self.snapshot_code else {
+            bail!("Kernel `{}` does not support snapshots", self.id())
+        };
+        let code = code.replace("{{path}}", &path.to_string_lossy().replace('\\', "/"));
+
+        let (.., messages) = self.send_receive(MicrokernelFlag::Exec, [code.as_str()]).await?;
+        self.check_for_errors(messages, "snapshotting kernel")?;
+
+        Ok(())
+    }
+
+    async fn restore(&mut self, path: &Path) -> Result<()> {
+        let Some(code) = &self.restore_code else {
+            bail!("Kernel `{}` does not support snapshots", self.id())
+        };
+        let code = code.replace("{{path}}", &path.to_string_lossy().replace('\\', "/"));
+
+        let (.., messages) = self.send_receive(MicrokernelFlag::Exec, [code.as_str()]).await?;
+        self.check_for_errors(messages, "restoring kernel")?;
+
+        Ok(())
+    }
+
+    async fn get_arrow(&mut self, name: &str, path: &Path) -> Result<()> {
+        let Some(code) = &self.arrow_dump_code else {
+            bail!("Kernel `{}` does not support Arrow", self.id())
+        };
+        let code = code
+            .replace("{{name}}", name)
+            .replace("{{path}}", &path.to_string_lossy().replace('\\', "/"));
+
+        let (.., messages) = self.send_receive(MicrokernelFlag::Exec, [code.as_str()]).await?;
+        self.check_for_errors(messages, "writing variable as Arrow")?;
+
+        Ok(())
+    }
+
+    async fn set_arrow(&mut self, name: &str, path: &Path) -> Result<()> {
+        let Some(code) = &self.arrow_load_code else {
+            bail!("Kernel `{}` does not support Arrow", self.id())
+        };
+        let code = code
+            .replace("{{name}}", name)
+            .replace("{{path}}", &path.to_string_lossy().replace('\\', "/"));
+
+        let (.., messages) = self.send_receive(MicrokernelFlag::Exec, [code.as_str()]).await?;
+        self.check_for_errors(messages, "reading variable from Arrow")?;
+
+        Ok(())
+    }
+
     async fn fork(&mut self) -> Result<Box<dyn KernelInstance>> {
         #[cfg(unix)]
         {
@@ -740,6 +1080,10 @@ impl KernelInstance for MicrokernelInstance {
                 executable_path: None,
                 command: None,
                 default_message_level,
+                snapshot_code: self.snapshot_code.clone(),
+                restore_code: self.restore_code.clone(),
+                arrow_dump_code: self.arrow_dump_code.clone(),
+                arrow_load_code: self.arrow_load_code.clone(),
                 child: None,
                 pid,
                 status,
@@ -929,16 +1273,51 @@ impl MicrokernelInstance {
 
     /// Receive outputs and messages from this microkernel instance
     async fn receive(&mut self) -> Result<(Vec<Node>, Vec<ExecutionMessage>)> {
-        let (Some(output), Some(errors)) = (self.output.as_mut(), self.errors.as_mut()) else {
+        self.receive_with(None).await
+    }
+
+    /// Receive outputs and messages from this microkernel instance, optionally
+    /// streaming outputs on a channel as they are produced
+    async fn receive_with(
+        &mut self,
+        output_sender: Option<&mpsc::UnboundedSender<Node>>,
+    ) -> Result<(Vec<Node>, Vec<ExecutionMessage>)> {
+        let (Some(input), Some(output), Some(errors)) = (
+            self.input.as_mut(),
+            self.output.as_mut(),
+            self.errors.as_mut(),
+        ) else {
             bail!("Microkernel has not been started yet!");
         };
 
-        match (output, errors) {
-            (MicrokernelOutput::Standard(output), MicrokernelErrors::Standard(errors)) => {
-                receive_results(output, errors, &self.default_message_level).await
+        match (input, output, errors) {
+            (
+                MicrokernelInput::Standard(input),
+                MicrokernelOutput::Standard(output),
+                MicrokernelErrors::Standard(errors),
+            ) => {
+                receive_results(
+                    output,
+                    errors,
+                    Some(input),
+                    output_sender,
+                    &self.default_message_level,
+                )
+                .await
             }
-            (MicrokernelOutput::Pipe(output), MicrokernelErrors::Pipe(errors)) => {
-                receive_results(output, errors, &self.default_message_level).await
+            (
+                MicrokernelInput::Pipe(input),
+                MicrokernelOutput::Pipe(output),
+                MicrokernelErrors::Pipe(errors),
+            ) => {
+                receive_results(
+                    output,
+                    errors,
+                    Some(input),
+                    output_sender,
+                    &self.default_message_level,
+                )
+                .await
             }
             _ => unreachable!(),
         }
@@ -966,7 +1345,15 @@ async fn startup_warnings<R1: AsyncBufRead + Unpin, R2: AsyncBufRead + Unpin>(
     stderr: &mut R2,
     default_message_level: &MessageLevel,
 ) {
-    match receive_results(stdout, stderr, default_message_level).await {
+    match receive_results::<_, _, BufWriter<ChildStdin>>(
+        stdout,
+        stderr,
+        None,
+        None,
+        default_message_level,
+    )
+    .await
+    {
         Ok((.., messages)) => {
             if !messages.is_empty() {
                 let messages = messages
@@ -983,6 +1370,74 @@ async fn startup_warnings<R1: AsyncBufRead + Unpin, R2: AsyncBufRead + Unpin>(
     }
 }
 
+/// Check whether a process is still alive
+///
+/// Uses a null signal (which does not affect the process) so works for
+/// processes that are not direct children (e.g. forks). On operating systems
+/// where this is not possible assumes the process is alive.
+fn process_is_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        use nix::{sys::signal, unistd::Pid};
+
+        signal::kill(Pid::from_raw(pid as i32), None).is_ok()
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _pid = pid;
+        true
+    }
+}
+
+/// Get the CPU time (in milliseconds) used by a process
+///
+/// Reads `utime` and `stime` from `/proc/<pid>/stat` so only available
+/// on Linux; returns `None` elsewhere.
+fn process_cpu_time(pid: u32) -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        // Skip past the command field (which may contain spaces) to the
+        // fields after the closing parenthesis
+        let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+        let utime: u64 = fields.get(11)?.parse().ok()?;
+        let stime: u64 = fields.get(12)?.parse().ok()?;
+
+        // `USER_HZ` is 100 on all modern Linux systems
+        const TICKS_PER_SEC: u64 = 100;
+        Some((utime + stime) * 1000 / TICKS_PER_SEC)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _pid = pid;
+        None
+    }
+}
+
+/// Get the peak memory (in KiB) used by a process
+///
+/// Reads `VmHWM` from `/proc/<pid>/status` so only available on Linux;
+/// returns `None` elsewhere.
+fn process_peak_memory(pid: u32) -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = std::fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+        status
+            .lines()
+            .find(|line| line.starts_with("VmHWM:"))
+            .and_then(|line| line.split_whitespace().nth(1))
+            .and_then(|kib| kib.parse().ok())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _pid = pid;
+        None
+    }
+}
+
 /// Send a task to a microkernel instance
 async fn send_task<W: AsyncWrite + Unpin>(
     flag: MicrokernelFlag,
@@ -1009,10 +1464,183 @@ async fn send_task<W: AsyncWrite + Unpin>(
     Ok(())
 }
 
+/// Ensure that a `.venv` virtual environment exists for a directory that
+/// declares Python dependencies
+///
+/// Dependencies can be declared in the `[environment]` table of a
+/// `stencila.toml` config file, or in a `requirements.txt` file. If any are
+/// declared, and there is no `.venv` in the directory or an ancestor, creates
+/// one using `uv` (preferred for speed), `conda`, or `python3 -m venv`, and
+/// installs the dependencies into it.
+async fn ensure_venv(directory: &Path) -> Result<()> {
+    // Check whether a `.venv` already exists in the directory or an ancestor
+    for dir in directory.ancestors() {
+        if dir.join(".venv").exists() {
+            return Ok(());
+        }
+    }
+
+    // Collect declared dependencies, returning early if there are none
+    let config = config::for_path(directory).await?;
+    let packages = config
+        .environment
+        .and_then(|environment| environment.python)
+        .unwrap_or_default();
+    let requirements = directory.join("requirements.txt");
+    if packages.is_empty() && !requirements.exists() {
+        return Ok(());
+    }
+
+    let venv = directory.join(".venv");
+    if which("uv").is_ok() {
+        tracing::info!("Creating virtual environment `{}` using uv", venv.display());
+
+        run_provisioning(Command::new("uv").arg("venv").current_dir(directory)).await?;
+
+        let mut install = Command::new("uv");
+        install.args(["pip", "install"]).current_dir(directory);
+        if requirements.exists() {
+            install.args(["-r", "requirements.txt"]);
+        }
+        install.args(&packages);
+        run_provisioning(&mut install).await?;
+    } else if which("conda").is_ok() {
+        tracing::info!("Creating virtual environment `{}` using conda", venv.display());
+
+        run_provisioning(
+            Command::new("conda")
+                .args(["create", "--yes", "--prefix", ".venv", "python", "pip"])
+                .current_dir(directory),
+        )
+        .await?;
+
+        let mut install = Command::new(venv.join("bin").join("pip"));
+        install.arg("install").current_dir(directory);
+        if requirements.exists() {
+            install.args(["-r", "requirements.txt"]);
+        }
+        install.args(&packages);
+        run_provisioning(&mut install).await?;
+    } else {
+        tracing::info!("Creating virtual environment `{}` using venv", venv.display());
+
+        run_provisioning(
+            Command::new("python3")
+                .args(["-m", "venv", ".venv"])
+                .current_dir(directory),
+        )
+        .await?;
+
+        let mut install = Command::new(venv.join("bin").join("pip"));
+        install.arg("install").current_dir(directory);
+        if requirements.exists() {
+            install.args(["-r", "requirements.txt"]);
+        }
+        install.args(&packages);
+        run_provisioning(&mut install).await?;
+    }
+
+    Ok(())
+}
+
+/// The name of the environment variable to have `renv::restore()` run before
+/// the R kernel is started in a workspace with an `renv.lock`
+///
+/// Restoring can be slow so is opt-in rather than being done on every start.
+const RENV_RESTORE_VAR: &str = "STENCILA_KERNEL_RENV_RESTORE";
+
+/// Ensure that any renv project for a directory is activated, and optionally
+/// restored, before the R kernel starts
+///
+/// Looks for an `renv.lock` in the directory or an ancestor. If the project
+/// has not yet been activated (no `renv/activate.R`) runs `renv::activate()`.
+/// If the `STENCILA_KERNEL_RENV_RESTORE` environment variable is set also runs
+/// `renv::restore()` so that the pinned package versions in the lockfile are
+/// installed.
+async fn ensure_renv(directory: &Path) -> Result<()> {
+    // Find the project directory containing an `renv.lock`
+    let Some(project) = directory
+        .ancestors()
+        .find(|dir| dir.join("renv.lock").exists())
+    else {
+        return Ok(());
+    };
+
+    // Ensure the project is activated so that the kernel uses the renv library
+    if !project.join("renv").join("activate.R").exists() {
+        tracing::info!("Activating renv project `{}`", project.display());
+
+        run_provisioning(
+            Command::new("Rscript")
+                .args(["-e", "renv::activate()"])
+                .current_dir(project),
+        )
+        .await?;
+    }
+
+    // Restore pinned package versions if requested
+    if env::var(RENV_RESTORE_VAR).is_ok() {
+        tracing::info!("Restoring renv library for `{}`", project.display());
+
+        run_provisioning(
+            Command::new("Rscript")
+                .args(["-e", "renv::restore(prompt = FALSE)"])
+                .current_dir(project),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Run an environment provisioning command, erroring with stderr if it fails
+async fn run_provisioning(command: &mut Command) -> Result<()> {
+    let output = command.output().await?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    Ok(())
+}
+
+/// Request a line of input from the user on behalf of a microkernel instance
+///
+/// Prompts on the terminal if stdin is interactive. Otherwise, responds with
+/// an empty line (rather than leaving the kernel blocked waiting for a
+/// response that will never come).
+async fn request_input(prompt: &str) -> String {
+    use std::io::{stderr, stdin, IsTerminal, Write};
+
+    if !stdin().is_terminal() {
+        tracing::warn!(
+            "Kernel requested input but stdin is not interactive; responding with empty string"
+        );
+        return String::new();
+    }
+
+    let prompt = prompt.to_string();
+    tokio::task::spawn_blocking(move || {
+        eprint!("{prompt}");
+        stderr().flush().ok();
+
+        let mut line = String::new();
+        stdin().read_line(&mut line).ok();
+        line.trim_end_matches(['\r', '\n']).to_string()
+    })
+    .await
+    .unwrap_or_default()
+}
+
 /// Receive results (outputs and messages) from a microkernel instance
-async fn receive_results<R1: AsyncBufRead + Unpin, R2: AsyncBufRead + Unpin>(
+///
+/// The `input_stream` is used to respond to any requests for user input
+/// (e.g. Python's `input()`) received on the output stream while waiting
+/// for results.
+async fn receive_results<R1: AsyncBufRead + Unpin, R2: AsyncBufRead + Unpin, W: AsyncWrite + Unpin>(
     output_stream: &mut R1,
     message_stream: &mut R2,
+    mut input_stream: Option<&mut W>,
+    output_sender: Option<&mpsc::UnboundedSender<Node>>,
     default_message_level: &MessageLevel,
 ) -> Result<(Vec<Node>, Vec<ExecutionMessage>)> {
     tracing::trace!("Receiving results from microkernel");
@@ -1030,21 +1658,43 @@ async fn receive_results<R1: AsyncBufRead + Unpin, R2: AsyncBufRead + Unpin>(
             }
         };
 
+        // Respond to any request for user input
+        if let Some(prompt) = line.strip_suffix(MicrokernelFlag::Input.as_unicode()) {
+            let Some(input_stream) = input_stream.as_mut() else {
+                bail!("Kernel requested input but has no input stream")
+            };
+
+            let response = [&request_input(prompt).await, "\n"].concat();
+            if let Err(error) = input_stream.write_all(response.as_bytes()).await {
+                bail!("When writing input to kernel: {error}")
+            }
+            if let Err(error) = input_stream.flush().await {
+                bail!("When flushing input to kernel: {error}")
+            }
+
+            continue;
+        }
+
         //tracing::trace!("Received on output stream: {}", &line);
-        if !handle_line(&line, &mut item, &mut items) {
+        let items_before = items.len();
+        let proceed = handle_line(&line, &mut item, &mut items);
+
+        // Stream any newly completed outputs
+        if let Some(sender) = output_sender {
+            for output in items.iter().skip(items_before) {
+                sender.send(parse_output(output)).ok();
+            }
+        }
+
+        if !proceed {
             break;
         }
     }
 
     // Attempt to parse each output as JSON into a `Node`, falling back to a string.
     let outputs: Vec<Node> = items
-        .into_iter()
-        .map(|output| -> Node {
-            match serde_json::from_str(&output) {
-                Ok(node) => node,
-                Err(..) => Node::String(output),
-            }
-        })
+        .iter()
+        .map(|output| parse_output(output))
         .collect();
 
     let mut item = String::new();
@@ -1077,6 +1727,16 @@ async fn receive_results<R1: AsyncBufRead + Unpin, R2: AsyncBufRead + Unpin>(
     Ok((outputs, messages))
 }
 
+/// Parse an output item from a microkernel into a [`Node`]
+///
+/// Attempts to parse the output as JSON, falling back to a string.
+fn parse_output(output: &str) -> Node {
+    match serde_json::from_str(output) {
+        Ok(node) => node,
+        Err(..) => Node::String(output.to_string()),
+    }
+}
+
 /// Handle a line on an output or error stream
 fn handle_line(line: &str, item: &mut String, items: &mut Vec<String>) -> bool {
     if let Some(line) = line.strip_suffix(MicrokernelFlag::End.as_unicode()) {
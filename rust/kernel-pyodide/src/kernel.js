@@ -0,0 +1,245 @@
+#!/usr/bin/env node
+
+// A microkernel that executes Python inside a Pyodide WebAssembly runtime.
+//
+// Requires the `pyodide` npm package to be installed (globally, or in a
+// `node_modules` directory above the working directory).
+//
+// During development it can be useful to run this kernel script directly e.g.
+//
+//     DEV=true node rust/kernel-pyodide/src/kernel.js
+//
+// Use Ctrl+D to quit.
+
+const os = require("os");
+const readline = require("readline");
+
+const dev = process.env.DEV !== undefined;
+
+const READY = dev ? "READY" : "\u{10ACDC}";
+const LINE = dev ? "|" : new RegExp("\u{10ABBA}", "g");
+const EXEC = dev ? "EXEC" : "\u{10B522}";
+const EVAL = dev ? "EVAL" : "\u{1010CC}";
+const FORK = dev ? "FORK" : "\u{10DE70}";
+const INFO = dev ? "INFO" : "\u{10EE15}";
+const PKGS = dev ? "PKGS" : "\u{10BEC4}";
+const LIST = dev ? "LIST" : "\u{10C155}";
+const GET = dev ? "GET" : "\u{10A51A}";
+const SET = dev ? "SET" : "\u{107070}";
+const REMOVE = dev ? "REMOVE" : "\u{10C41C}";
+const END = dev ? "END" : "\u{10CB40}";
+
+const { stdin, stdout, stderr } = process;
+
+// Write an `ExecutionMessage` to stderr
+function message(level, msg, errorType) {
+  stderr.write(
+    `${JSON.stringify({
+      type: "ExecutionMessage",
+      level,
+      message: msg,
+      errorType,
+    })}${END}\n`
+  );
+}
+
+// The Pyodide instance, loaded lazily on the first task so that startup
+// failures are reported as execution messages rather than crashing the kernel
+let pyodide;
+async function load() {
+  if (pyodide !== undefined) {
+    return pyodide;
+  }
+
+  let loadPyodide;
+  try {
+    ({ loadPyodide } = require("pyodide"));
+  } catch {
+    throw new Error(
+      "The `pyodide` npm package is required for the Pyodide kernel; install it with `npm install -g pyodide`"
+    );
+  }
+
+  pyodide = await loadPyodide({
+    // Forward Python's stdout as separate output nodes and stderr as messages
+    stdout: (line) => stdout.write(`${JSON.stringify(line)}${END}\n`),
+    stderr: (line) => message("Error", line),
+  });
+
+  return pyodide;
+}
+
+// Convert a Python result to a JSON-able JavaScript value
+function toJs(py, value) {
+  if (value === undefined || value === null) {
+    return undefined;
+  }
+  if (value instanceof py.ffi.PyProxy) {
+    const js = value.toJs({ dict_converter: Object.fromEntries });
+    value.destroy();
+    return js;
+  }
+  return value;
+}
+
+// Execute lines of code
+async function execute(py, lines) {
+  const code = lines.join("\n");
+  const result = toJs(py, await py.runPythonAsync(code));
+  if (result !== undefined) {
+    stdout.write(JSON.stringify(result));
+  }
+}
+
+// Evaluate an expression
+async function evaluate(py, expression) {
+  const value = toJs(py, await py.runPythonAsync(expression));
+  if (value !== undefined) {
+    stdout.write(JSON.stringify(value));
+  }
+}
+
+// Get runtime information
+function info(py) {
+  stdout.write(
+    JSON.stringify({
+      type: "SoftwareApplication",
+      name: "Pyodide",
+      softwareVersion: py.version,
+      operatingSystem: `${os.type()} ${os.arch()} ${os.release()}`,
+    })
+  );
+}
+
+// Get a list of packages loaded into the runtime
+function packages(py) {
+  for (const name of Object.keys(py.loadedPackages)) {
+    stdout.write(
+      `${JSON.stringify({
+        type: "SoftwareSourceCode",
+        programmingLanguage: "Python",
+        name,
+      })}${END}\n`
+    );
+  }
+}
+
+// List variables in the Python global scope
+async function list(py) {
+  const names = toJs(
+    py,
+    await py.runPythonAsync(
+      "[name for name in globals() if not name.startswith('_')]"
+    )
+  );
+  for (const name of names ?? []) {
+    const nativeType = toJs(
+      py,
+      await py.runPythonAsync(`type(${name}).__name__`)
+    );
+    const nodeType =
+      {
+        NoneType: "Null",
+        bool: "Boolean",
+        int: "Integer",
+        float: "Number",
+        str: "String",
+        list: "Array",
+        tuple: "Array",
+        dict: "Object",
+      }[nativeType] ?? "Object";
+
+    stdout.write(
+      `${JSON.stringify({
+        type: "Variable",
+        name,
+        programmingLanguage: "Python",
+        nativeType,
+        nodeType,
+      })}${END}\n`
+    );
+  }
+}
+
+// Get a variable
+async function get(py, name) {
+  const value = toJs(
+    py,
+    await py.runPythonAsync(`globals().get(${JSON.stringify(name)})`)
+  );
+  if (value !== undefined) {
+    stdout.write(JSON.stringify(value));
+  }
+}
+
+// Set a variable
+function set(py, name, json) {
+  py.globals.set(name, py.toPy(JSON.parse(json)));
+}
+
+// Remove a variable
+async function remove(py, name) {
+  await py.runPythonAsync(`globals().pop(${JSON.stringify(name)}, None)`);
+}
+
+// Read lines and handle tasks sequentially
+const rl = readline.createInterface({
+  input: stdin,
+  prompt: "",
+  terminal: false,
+});
+let queue = Promise.resolve();
+rl.on("line", (task) => {
+  queue = queue.then(async () => {
+    const lines = task.split(LINE);
+
+    try {
+      const py = await load();
+
+      switch (lines[0]) {
+        case EXEC:
+          await execute(py, lines.slice(1));
+          break;
+        case EVAL:
+          await evaluate(py, lines[1]);
+          break;
+        case INFO:
+          info(py);
+          break;
+        case PKGS:
+          packages(py);
+          break;
+        case LIST:
+          await list(py);
+          break;
+        case GET:
+          await get(py, lines[1]);
+          break;
+        case SET:
+          set(py, lines[1], lines[2]);
+          break;
+        case REMOVE:
+          await remove(py, lines[1]);
+          break;
+        case FORK:
+          throw new Error("The Pyodide kernel does not support forks");
+        default:
+          throw new Error(`Unrecognized task ${lines[0]}`);
+      }
+    } catch (error) {
+      message(
+        "Exception",
+        error.message ?? error.toString(),
+        error.type ?? error.name
+      );
+    }
+
+    // Indicate ready for next task
+    stdout.write(`${READY}\n`);
+    stderr.write(`${READY}\n`);
+  });
+});
+
+// Indicate ready for first task
+stdout.write(`${READY}\n`);
+stderr.write(`${READY}\n`);
@@ -0,0 +1,120 @@
+use kernel_micro::{
+    common::eyre::Result, format::Format, Kernel, KernelAvailability, KernelForks, KernelInstance,
+    KernelInterrupt, KernelKill, KernelProvider, KernelTerminate, Microkernel,
+};
+
+/// A kernel for executing Python code in a Pyodide WebAssembly runtime
+///
+/// Unlike the `python` kernel, which spawns a native Python process, this
+/// kernel runs Python inside a WASM sandbox (Pyodide on Node.js). The code
+/// has no access to the host filesystem, network, or environment variables,
+/// making it a sandboxing alternative on platforms where `fork`-based
+/// boxing is not available (e.g. Windows).
+#[derive(Default)]
+pub struct PyodideKernel;
+
+const NAME: &str = "pyodide";
+
+impl Kernel for PyodideKernel {
+    fn name(&self) -> String {
+        NAME.to_string()
+    }
+
+    fn provider(&self) -> KernelProvider {
+        KernelProvider::Environment
+    }
+
+    fn availability(&self) -> KernelAvailability {
+        self.microkernel_availability()
+    }
+
+    fn supports_languages(&self) -> Vec<Format> {
+        vec![Format::Python]
+    }
+
+    fn supports_interrupt(&self) -> KernelInterrupt {
+        // Pyodide does not support interrupting a running task from
+        // outside the WASM runtime
+        KernelInterrupt::No
+    }
+
+    fn supports_terminate(&self) -> KernelTerminate {
+        self.microkernel_supports_terminate()
+    }
+
+    fn supports_kill(&self) -> KernelKill {
+        self.microkernel_supports_kill()
+    }
+
+    fn supports_forks(&self) -> KernelForks {
+        // The WASM runtime's state cannot be forked
+        KernelForks::No
+    }
+
+    fn create_instance(&self) -> Result<Box<dyn KernelInstance>> {
+        self.microkernel_create_instance(NAME)
+    }
+}
+
+impl Microkernel for PyodideKernel {
+    fn executable_name(&self) -> String {
+        // Pyodide is run within Node.js (the `pyodide` npm package must be
+        // installed; the kernel script emits an instructive error if not)
+        "node".to_string()
+    }
+
+    fn microkernel_script(&self) -> String {
+        include_str!("kernel.js").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kernel_micro::{
+        common::tokio,
+        schema::Node,
+        tests::create_instance,
+    };
+
+    use super::*;
+
+    // Pro-tip! Use get logs for these tests use:
+    //
+    // ```sh
+    // RUST_LOG=trace cargo test -p kernel-pyodide -- --nocapture
+    // ```
+
+    /// Standard kernel test for execution of code
+    ///
+    /// Only run if the `pyodide` npm package is installed since, unlike
+    /// Node.js itself, it can not be assumed to be present.
+    #[test_log::test(tokio::test)]
+    async fn execution() -> Result<()> {
+        if std::process::Command::new("node")
+            .args(["-e", "require.resolve('pyodide')"])
+            .output()
+            .map(|output| !output.status.success())
+            .unwrap_or(true)
+        {
+            return Ok(());
+        }
+
+        let Some(instance) = create_instance::<PyodideKernel>().await? else {
+            return Ok(());
+        };
+
+        kernel_micro::tests::execution(
+            instance,
+            vec![
+                // Empty code: no outputs
+                ("", vec![], vec![]),
+                // Only an expression: one output
+                ("1 + 1", vec![Node::Integer(2)], vec![]),
+                // Variables set in one chunk are available in the next
+                ("a = 2", vec![], vec![]),
+                ("a * 3", vec![Node::Integer(6)], vec![]),
+            ],
+        )
+        .await
+    }
+}
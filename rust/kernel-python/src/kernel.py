@@ -7,6 +7,9 @@
 # pyright: reportMissingImports=false
 # pyright: reportPossiblyUnboundVariable = false
 
+import ast
+import asyncio
+import inspect
 import io
 import json
 import logging
@@ -157,6 +160,7 @@ LIST = "LIST" if DEV_MODE else "\U0010c155"
 GET = "GET" if DEV_MODE else "\U0010a51a"
 SET = "SET" if DEV_MODE else "\U00107070"
 REMOVE = "REMOVE" if DEV_MODE else "\U0010c41c"
+INPUT = "INPUT" if DEV_MODE else "\U0010d1d0"
 END = "END" if DEV_MODE else "\U0010cb40"
 
 # Try to get the maximum number of file descriptors the process can have open
@@ -409,6 +413,79 @@ try:
 except ImportError:
     PANDAS_AVAILABLE = False
 
+# Custom serialization and hints for polars
+try:
+    import polars as pl
+
+    POLARS_AVAILABLE = True
+
+    def polars_dtype_to_item_type(dtype: Any) -> str:
+        if dtype == pl.Boolean:
+            return "Boolean"
+        if dtype.is_unsigned_integer():
+            return "UnsignedInteger"
+        if dtype.is_integer():
+            return "Integer"
+        if dtype.is_float():
+            return "Number"
+        return "String"
+
+    def polars_to_hint(df: pl.DataFrame) -> DatatableHint:
+        columns = []
+        try:
+            for name in df.columns:
+                column = df.get_column(name)
+
+                hint: DatatableColumnHint = {
+                    "type": "DatatableColumnHint",
+                    "name": str(name),
+                    "itemType": polars_dtype_to_item_type(column.dtype),
+                }
+                columns.append(hint)
+        except Exception:
+            pass
+
+        return {"type": "DatatableHint", "rows": df.height, "columns": columns}
+
+    def polars_to_datatable(df: pl.DataFrame) -> Datatable:
+        columns = []
+        for name in df.columns:
+            column = df.get_column(name)
+
+            item_type = polars_dtype_to_item_type(column.dtype)
+            validator_type = (
+                "IntegerValidator"
+                if item_type == "UnsignedInteger"
+                else item_type + "Validator"
+            )
+            validator: ArrayValidator = {
+                "type": "ArrayValidator",
+                "itemsValidator": {"type": validator_type},  # type: ignore
+            }
+
+            columns.append(
+                {
+                    "type": "DatatableColumn",
+                    "name": str(name),
+                    "values": column.to_list(),
+                    "validator": validator,
+                }
+            )
+
+        return {"type": "Datatable", "columns": columns}
+
+    def polars_from_datatable(dt: Datatable) -> pl.DataFrame:
+        columns = dt.get("columns") or []
+        data = {
+            column.get("name") or "unnamed": column.get("values") or []
+            for column in columns
+        }
+
+        return pl.DataFrame(data)
+
+except ImportError:
+    POLARS_AVAILABLE = False
+
 # Custom serialization for `matplotlib` plots
 try:
     import matplotlib
@@ -521,6 +598,37 @@ try:
 except ImportError:
     ALTAIR_AVAILABLE = False
 
+# Represent Jupyter widgets using their embedded state so that they can be
+# rendered as interactive widgets in HTML
+try:
+    import ipywidgets
+
+    IPYWIDGETS_AVAILABLE = True
+
+    def is_ipywidget(value: Any) -> bool:
+        """Is the value a Jupyter widget?"""
+        return isinstance(value, ipywidgets.Widget)
+
+    def ipywidget_to_image_object(widget: Any) -> ImageObject:
+        """Convert a Jupyter widget to an `ImageObject` with embedded widget state"""
+        from ipywidgets.embed import embed_data
+
+        data = embed_data(views=[widget])
+        return {
+            "type": "ImageObject",
+            "mediaType": "application/vnd.jupyter.widget-view+json",
+            "contentUrl": json.dumps(
+                {
+                    "manager_state": data["manager_state"],
+                    "view_specs": data["view_specs"],
+                },
+                cls=MimeBundleJSONEncoder,
+            ),
+        }
+
+except ImportError:
+    IPYWIDGETS_AVAILABLE = False
+
 
 class MimeBundleJSONEncoder(json.JSONEncoder):
     """
@@ -583,9 +691,15 @@ def to_json(obj: Any) -> str:
     if PANDAS_AVAILABLE and isinstance(obj, pd.DataFrame):  # pyright: ignore
         return json.dumps(dataframe_to_datatable(obj))
 
+    if POLARS_AVAILABLE and isinstance(obj, pl.DataFrame):  # pyright: ignore
+        return json.dumps(polars_to_datatable(obj))
+
     if MATPLOTLIB_AVAILABLE and is_matplotlib(obj):
         return json.dumps(matplotlib_to_image_object())
 
+    if IPYWIDGETS_AVAILABLE and is_ipywidget(obj):
+        return json.dumps(ipywidget_to_image_object(obj))
+
     if hasattr(obj, "_repr_mimebundle_"):
         bundle = obj._repr_mimebundle_()
 
@@ -599,6 +713,53 @@ def to_json(obj: Any) -> str:
 
         return json.dumps(bundle, cls=MimeBundleJSONEncoder)
 
+    # Fall back to IPython-style rich repr methods, used by libraries such as
+    # `great_tables`, `folium` and `shap`, in preference to plain `repr()`
+    if hasattr(obj, "_repr_html_"):
+        try:
+            html = obj._repr_html_()
+        except Exception:
+            html = None
+        if isinstance(html, str):
+            return json.dumps(
+                {
+                    "type": "ImageObject",
+                    "mediaType": "text/html",
+                    "contentUrl": html,
+                }
+            )
+
+    if hasattr(obj, "_repr_svg_"):
+        try:
+            svg = obj._repr_svg_()
+        except Exception:
+            svg = None
+        if isinstance(svg, str):
+            return json.dumps(
+                {
+                    "type": "ImageObject",
+                    "mediaType": "image/svg+xml",
+                    "contentUrl": svg,
+                }
+            )
+
+    if hasattr(obj, "_repr_png_"):
+        try:
+            png = obj._repr_png_()
+        except Exception:
+            png = None
+        if isinstance(png, bytes):
+            import base64
+
+            return json.dumps(
+                {
+                    "type": "ImageObject",
+                    "mediaType": "image/png",
+                    "contentUrl": "data:image/png;base64,"
+                    + base64.b64encode(png).decode(),
+                }
+            )
+
     try:
         return json.dumps(obj)
     except:  # noqa: E722
@@ -615,8 +776,11 @@ def from_json(string: str) -> Any:
     if isinstance(obj, dict):
         typ = obj.get("type")
 
-        if PANDAS_AVAILABLE and typ == "Datatable":
-            return dataframe_from_datatable(obj)  # type: ignore
+        if typ == "Datatable":
+            if PANDAS_AVAILABLE:
+                return dataframe_from_datatable(obj)  # type: ignore
+            if POLARS_AVAILABLE:
+                return polars_from_datatable(obj)  # type: ignore
 
     return obj
 
@@ -629,8 +793,22 @@ def print(*objects, sep=" ", end="\n", file=sys.stdout, flush=False):  # noqa
         sys.stdout.write(to_json(obj) + END + "\n")
 
 
-# Create the initial context with monkey patched print
-CONTEXT: dict[str, Any] = {"print": print}
+# Replacement for `input` which requests a line of input from the user via a
+# sentinel on stdout (rather than blocking on stdin, which is used for tasks)
+def input_request(prompt="") -> str:
+    sys.stdout.write(str(prompt) + INPUT + "\n")
+    sys.stdout.flush()
+    return sys.stdin.readline().rstrip("\n")
+
+
+# Create the initial context with monkey patched print and input
+CONTEXT: dict[str, Any] = {"print": print, "input": input_request}
+
+# Compile flag to allow top-level `await` in code chunks
+TOP_LEVEL_AWAIT = ast.PyCF_ALLOW_TOP_LEVEL_AWAIT
+
+# Event loop used to run code chunks containing top-level `await`
+EVENT_LOOP = asyncio.new_event_loop()
 
 
 # Execute lines of code
@@ -642,34 +820,47 @@ def execute(lines: list[str], file: str) -> None:
         # Try to compile the current buffer to see if it's complete
         # and just continue to accumulate to buffer if not
         try:
-            compile(buffer, file, "exec")
+            compile(buffer, file, "exec", TOP_LEVEL_AWAIT)
         except Exception:
             continue
 
         # Code is complete, execute it
         try:
             # First, try to compile and evaluate as an expression
-            compiled = compile(buffer, file, "eval")
+            compiled = compile(buffer, file, "eval", TOP_LEVEL_AWAIT)
             value = eval(compiled, CONTEXT)
+            if inspect.iscoroutine(value):
+                value = EVENT_LOOP.run_until_complete(value)
             if value is not None:
                 sys.stdout.write(to_json(value) + END + "\n")
         except SyntaxError:
             # Not an expression, execute as statements
-            compiled = compile(buffer, file, "exec")
-            exec(compiled, CONTEXT)
+            exec_compiled(buffer, file)
 
         # Reset buffer
         buffer = "\n" * (index + 1)
 
     # If any buffer remaining then compile to raise the syntax error
-    compiled = compile(buffer, file, "exec")
-    exec(compiled, CONTEXT)
+    exec_compiled(buffer, file)
+
+
+# Compile and execute statements, awaiting them on the event loop if they
+# contain top-level `await`
+def exec_compiled(buffer: str, file: str) -> None:
+    compiled = compile(buffer, file, "exec", TOP_LEVEL_AWAIT)
+    if compiled.co_flags & inspect.CO_COROUTINE:
+        EVENT_LOOP.run_until_complete(eval(compiled, CONTEXT))
+    else:
+        exec(compiled, CONTEXT)
 
 
 # Evaluate an expression
 def evaluate(expression: str) -> None:
     if expression:
-        value = eval(expression, CONTEXT)
+        compiled = compile(expression, "<expression>", "eval", TOP_LEVEL_AWAIT)
+        value = eval(compiled, CONTEXT)
+        if inspect.iscoroutine(value):
+            value = EVENT_LOOP.run_until_complete(value)
         sys.stdout.write(to_json(value))
 
 
@@ -756,6 +947,8 @@ def determine_type_and_hint(value: Any) -> tuple[str, Any]:
         return "Array", ndarray_to_hint(value)
     if PANDAS_AVAILABLE and isinstance(value, pd.DataFrame):  # pyright: ignore
         return "Datatable", dataframe_to_hint(value)
+    if POLARS_AVAILABLE and isinstance(value, pl.DataFrame):  # pyright: ignore
+        return "Datatable", polars_to_hint(value)
     if isinstance(value, dict):
         typ = value.get("type")
         if typ:
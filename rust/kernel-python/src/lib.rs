@@ -1,6 +1,17 @@
+use std::{env, fs, path::Path, process::Command};
+
 use kernel_micro::{
-    common::eyre::Result, format::Format, Kernel, KernelAvailability, KernelForks, KernelInstance,
-    KernelInterrupt, KernelKill, KernelProvider, KernelTerminate, Microkernel,
+    common::{
+        eyre::Result,
+        once_cell::sync::Lazy,
+        regex::Regex,
+        tempfile,
+        which::which,
+    },
+    format::Format,
+    schema::{CodeLocation, CompilationMessage, MessageLevel},
+    Kernel, KernelAvailability, KernelForks, KernelInstance, KernelInterrupt, KernelKill,
+    KernelProvider, KernelTerminate, Microkernel,
 };
 
 /// A kernel for executing Python code
@@ -9,6 +20,12 @@ pub struct PythonKernel;
 
 const NAME: &str = "python";
 
+/// The name of the environment variable for choosing the Python type checker
+/// used for linting
+///
+/// May be set to `none` to disable type checking. Defaults to `mypy`.
+const TYPE_CHECKER_VAR: &str = "STENCILA_PYTHON_TYPE_CHECKER";
+
 impl Kernel for PythonKernel {
     fn name(&self) -> String {
         NAME.to_string()
@@ -26,6 +43,75 @@ impl Kernel for PythonKernel {
         vec![Format::Python]
     }
 
+    fn lint(&self, code: &str, directory: &Path) -> Result<Vec<CompilationMessage>> {
+        let checker = env::var(TYPE_CHECKER_VAR).unwrap_or_else(|_| "mypy".to_string());
+        if checker == "none" || which(&checker).is_err() {
+            return Ok(Vec::new());
+        }
+
+        // Write the code to a temporary file for the checker to read
+        let temp_dir = tempfile::tempdir()?;
+        let code_path = temp_dir.path().join("code.py");
+        fs::write(&code_path, code)?;
+
+        // Run in the document's directory so that project level configuration
+        // (e.g. `mypy.ini`, `pyproject.toml`) is respected
+        let output = Command::new(&checker)
+            .args(["--show-column-numbers", "--no-error-summary", "--no-color-output"])
+            .arg(&code_path)
+            .current_dir(directory)
+            .output()?;
+
+        // Parse diagnostic lines e.g.
+        //   code.py:3:5: error: Unsupported operand types  [operator]
+        static REGEX: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"(?m)^.*?:(\d+):(\d+): (error|warning|note): (.*?)(?:\s+\[([\w-]+)\])?$")
+                .expect("invalid regex")
+        });
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let messages = REGEX
+            .captures_iter(&stdout)
+            .map(|captures| {
+                let start_line = captures[1].parse::<u64>().ok().map(|line| line - 1);
+                let start_column = captures[2].parse::<u64>().ok().map(|column| column - 1);
+
+                CompilationMessage {
+                    level: match &captures[3] {
+                        "error" => MessageLevel::Error,
+                        "warning" => MessageLevel::Warning,
+                        _ => MessageLevel::Info,
+                    },
+                    message: captures[4].to_string(),
+                    error_type: captures.get(5).map(|code| code.as_str().to_string()),
+                    code_location: Some(CodeLocation {
+                        start_line,
+                        start_column,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        Ok(messages)
+    }
+
+    fn seed_code(&self, seed: u64) -> Option<String> {
+        // NumPy's legacy seeding requires a value less than 2^32
+        Some(format!(
+            r#"
+import random
+random.seed({seed})
+try:
+    import numpy
+    numpy.random.seed({seed} % 2**32)
+except ImportError:
+    pass
+"#
+        ))
+    }
+
     fn supports_interrupt(&self) -> KernelInterrupt {
         self.microkernel_supports_interrupt()
     }
@@ -56,6 +142,74 @@ impl Microkernel for PythonKernel {
     fn microkernel_script(&self) -> String {
         include_str!("kernel.py").to_string()
     }
+
+    fn snapshot_code(&self) -> Option<String> {
+        Some(
+            r#"
+def _snapshot(path):
+    import pickle
+    import types
+    state = {}
+    for name, value in globals().items():
+        if name.startswith("_") or isinstance(value, types.ModuleType):
+            continue
+        try:
+            pickle.dumps(value)
+        except Exception:
+            continue
+        state[name] = value
+    with open(path, "wb") as file:
+        pickle.dump(state, file)
+_snapshot("{{path}}")
+del _snapshot
+"#
+            .to_string(),
+        )
+    }
+
+    fn restore_code(&self) -> Option<String> {
+        Some(
+            r#"
+def _restore(path):
+    import pickle
+    with open(path, "rb") as file:
+        globals().update(pickle.load(file))
+_restore("{{path}}")
+del _restore
+"#
+            .to_string(),
+        )
+    }
+
+    fn arrow_dump_code(&self) -> Option<String> {
+        Some(
+            r#"
+def _arrow_dump(name, path):
+    import pandas
+    from pyarrow import feather
+    value = globals()[name]
+    if not isinstance(value, pandas.DataFrame):
+        raise TypeError(f"Variable `{name}` is not a DataFrame")
+    feather.write_feather(value, path)
+_arrow_dump("{{name}}", "{{path}}")
+del _arrow_dump
+"#
+            .to_string(),
+        )
+    }
+
+    fn arrow_load_code(&self) -> Option<String> {
+        Some(
+            r#"
+def _arrow_load(name, path):
+    from pyarrow import feather
+    globals()[name] = feather.read_feather(path)
+_arrow_load("{{name}}", "{{path}}")
+del _arrow_load
+"#
+            .to_string(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -15,6 +15,7 @@ LIST = ifelse(DEV_MODE, "LIST", "\U0010C155")
 GET = ifelse(DEV_MODE, "GET", "\U0010A51A")
 SET = ifelse(DEV_MODE, "SET", "\U00107070")
 REMOVE = ifelse(DEV_MODE, "REMOVE", "\U0010C41C")
+INPUT = ifelse(DEV_MODE, "INPUT", "\U0010D1D0")
 END = ifelse(DEV_MODE, "END", "\U0010CB40")
 
 # Set UTF-8 locale if necessary to ensure above codes are output properly
@@ -335,6 +336,15 @@ unbox <- jsonlite::unbox
 # Create environment in which code will be executed
 envir <- new.env()
 
+# Replacement for `readline` which requests a line of input from the user via
+# a sentinel on stdout (rather than returning an empty string, as base R's
+# `readline` does when the session is not interactive)
+assign("readline", function(prompt = "") {
+  write(paste0(prompt, INPUT), stdout)
+  flush(stdout)
+  readLines(stdin, n = 1)
+}, envir = envir)
+
 # Execute lines of code
 #
 # An alternative to most of the code in this function would be to use the
@@ -27,6 +27,11 @@ impl Kernel for RKernel {
         vec![Format::R]
     }
 
+    fn seed_code(&self, seed: u64) -> Option<String> {
+        // `set.seed` requires an integer so reduce the seed modulo 2^31
+        Some(format!("set.seed({})", seed % 2u64.pow(31)))
+    }
+
     fn supports_interrupt(&self) -> KernelInterrupt {
         self.microkernel_supports_interrupt()
     }
@@ -60,6 +65,31 @@ impl Microkernel for RKernel {
     fn default_message_level(&self) -> MessageLevel {
         MessageLevel::Info
     }
+
+    fn snapshot_code(&self) -> Option<String> {
+        Some(r#"save(list = ls(envir), file = "{{path}}", envir = envir)"#.to_string())
+    }
+
+    fn restore_code(&self) -> Option<String> {
+        Some(r#"load("{{path}}", envir = envir)"#.to_string())
+    }
+
+    fn arrow_dump_code(&self) -> Option<String> {
+        Some(
+            r#"
+if (!inherits(get("{{name}}", envir = envir), "data.frame")) stop("Variable `{{name}}` is not a data frame")
+arrow::write_feather(get("{{name}}", envir = envir), "{{path}}")
+"#
+            .to_string(),
+        )
+    }
+
+    fn arrow_load_code(&self) -> Option<String> {
+        Some(
+            r#"assign("{{name}}", as.data.frame(arrow::read_feather("{{path}}")), envir = envir)"#
+                .to_string(),
+        )
+    }
 }
 
 #[cfg(test)]
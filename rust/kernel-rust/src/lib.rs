@@ -0,0 +1,582 @@
+use std::{
+    path::Path,
+    process::Stdio,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc,
+    },
+};
+
+use kernel::{
+    common::{
+        async_trait::async_trait,
+        eyre::{bail, OptionExt, Result},
+        once_cell::sync::Lazy,
+        regex::Regex,
+        serde_json,
+        tokio::{
+            self,
+            io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter},
+            process::{Child, ChildStderr, ChildStdin, ChildStdout, Command},
+            sync::{mpsc, watch},
+        },
+        tracing,
+        which::which,
+    },
+    format::Format,
+    generate_id,
+    schema::{
+        CodeLocation, ExecutionMessage, MessageLevel, Node, Null, SoftwareApplication,
+        SoftwareApplicationOptions, SoftwareSourceCode, StringOrNumber, Variable,
+    },
+    Kernel, KernelAvailability, KernelInstance, KernelProvider, KernelSignal, KernelStatus,
+};
+
+/// A kernel for executing Rust code using the `evcxr` evaluation context
+///
+/// Unlike most other language kernels, which run a long-lived kernel script,
+/// this kernel drives the `evcxr` REPL (https://github.com/evcxr/evcxr)
+/// over its standard input and output streams. State (`let` bindings, `fn`
+/// and `struct` definitions etc) persists between executions because
+/// `evcxr` maintains an incremental compilation context.
+#[derive(Default)]
+pub struct RustKernel;
+
+const NAME: &str = "rust";
+
+impl Kernel for RustKernel {
+    fn name(&self) -> String {
+        NAME.to_string()
+    }
+
+    fn provider(&self) -> KernelProvider {
+        KernelProvider::Environment
+    }
+
+    fn availability(&self) -> KernelAvailability {
+        if which("evcxr").is_ok() {
+            KernelAvailability::Available
+        } else {
+            KernelAvailability::Installable
+        }
+    }
+
+    fn supports_languages(&self) -> Vec<Format> {
+        vec![Format::Rust]
+    }
+
+    fn create_instance(&self) -> Result<Box<dyn KernelInstance>> {
+        Ok(Box::new(RustKernelInstance::new()))
+    }
+}
+
+/// A sentinel printed to both output streams after each task so that the
+/// end of the task can be detected (in the same private use area as the
+/// flags used by microkernels)
+const SENTINEL: &str = "\u{10CB40}";
+
+pub struct RustKernelInstance {
+    /// The name of this instance
+    id: String,
+
+    /// The `evcxr` child process
+    child: Option<Child>,
+
+    /// The standard input stream of the child process
+    stdin: Option<BufWriter<ChildStdin>>,
+
+    /// The standard output stream of the child process
+    stdout: Option<BufReader<ChildStdout>>,
+
+    /// The standard error stream of the child process
+    stderr: Option<BufReader<ChildStderr>>,
+
+    /// The status of this instance
+    status: Arc<AtomicU8>,
+
+    /// A channel sender for the status of this instance
+    status_sender: watch::Sender<KernelStatus>,
+
+    /// A channel sender for sending signals to the instance
+    signal_sender: mpsc::Sender<KernelSignal>,
+}
+
+#[async_trait]
+impl KernelInstance for RustKernelInstance {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn status(&self) -> Result<KernelStatus> {
+        Ok(self.get_status())
+    }
+
+    fn status_watcher(&self) -> Result<watch::Receiver<KernelStatus>> {
+        Ok(self.status_sender.subscribe())
+    }
+
+    fn signal_sender(&self) -> Result<mpsc::Sender<KernelSignal>> {
+        Ok(self.signal_sender.clone())
+    }
+
+    async fn start(&mut self, directory: &Path) -> Result<()> {
+        tracing::trace!("Starting Rust kernel instance");
+
+        let path = which("evcxr")?;
+
+        let mut child = Command::new(path)
+            .arg("--disable-readline")
+            .current_dir(directory)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        self.stdin = Some(BufWriter::new(
+            child.stdin.take().ok_or_eyre("Child has no stdin")?,
+        ));
+        self.stdout = Some(BufReader::new(
+            child.stdout.take().ok_or_eyre("Child has no stdout")?,
+        ));
+        self.stderr = Some(BufReader::new(
+            child.stderr.take().ok_or_eyre("Child has no stderr")?,
+        ));
+        self.child = Some(child);
+
+        // Perform an initial, empty task so that any startup messages
+        // (e.g. about the compilation cache) are consumed and the instance
+        // only becomes ready when `evcxr` is able to evaluate code
+        self.send_receive("").await?;
+
+        self.set_status(KernelStatus::Ready)
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        tracing::trace!("Stopping Rust kernel instance");
+
+        if let Some(mut child) = self.child.take() {
+            child.kill().await?;
+        }
+        self.stdin = None;
+        self.stdout = None;
+        self.stderr = None;
+
+        self.set_status(KernelStatus::Stopped)
+    }
+
+    async fn execute(&mut self, code: &str) -> Result<(Vec<Node>, Vec<ExecutionMessage>)> {
+        tracing::trace!("Executing Rust code");
+
+        self.set_status(KernelStatus::Busy)?;
+
+        let (lines, messages) = self.send_receive(code).await?;
+
+        self.set_status(KernelStatus::Ready)?;
+
+        let outputs = lines
+            .into_iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| parse_output(&line))
+            .collect();
+
+        Ok((outputs, messages))
+    }
+
+    async fn evaluate(&mut self, code: &str) -> Result<(Node, Vec<ExecutionMessage>)> {
+        tracing::trace!("Evaluating Rust expression");
+
+        if code.trim().is_empty() {
+            return Ok((Node::Null(Null), Vec::new()));
+        }
+
+        // Wrap in parentheses so that the expression is evaluated as a
+        // single input and its value echoed by `evcxr`
+        let (mut outputs, messages) = self.execute(&["(", code.trim(), ")"].concat()).await?;
+
+        Ok((outputs.pop().unwrap_or(Node::Null(Null)), messages))
+    }
+
+    async fn info(&mut self) -> Result<SoftwareApplication> {
+        tracing::trace!("Getting Rust runtime info");
+
+        let version = Command::new("evcxr")
+            .arg("--version")
+            .output()
+            .await
+            .ok()
+            .and_then(|output| {
+                String::from_utf8(output.stdout)
+                    .ok()
+                    .map(|stdout| stdout.trim().trim_start_matches("evcxr ").to_string())
+            });
+
+        Ok(SoftwareApplication {
+            name: "Rust".to_string(),
+            options: Box::new(SoftwareApplicationOptions {
+                software_version: version,
+                operating_system: Some(std::env::consts::OS.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    async fn packages(&mut self) -> Result<Vec<SoftwareSourceCode>> {
+        tracing::trace!("Getting Rust packages");
+
+        // Crates added with the `:dep` command are listed by `:show_deps`
+        self.set_status(KernelStatus::Busy)?;
+        let (lines, ..) = self.send_receive(":show_deps").await?;
+        self.set_status(KernelStatus::Ready)?;
+
+        static DEP_REGEX: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r#"^(\w[\w-]*)\s*=\s*"([^"]+)""#).expect("invalid regex"));
+
+        Ok(lines
+            .iter()
+            .filter_map(|line| DEP_REGEX.captures(line.trim()))
+            .map(|captures| SoftwareSourceCode {
+                name: captures[1].to_string(),
+                programming_language: "Rust".to_string(),
+                version: Some(StringOrNumber::String(captures[2].to_string())),
+                ..Default::default()
+            })
+            .collect())
+    }
+
+    async fn list(&mut self) -> Result<Vec<Variable>> {
+        tracing::trace!("Listing Rust variables");
+
+        self.set_status(KernelStatus::Busy)?;
+        let (lines, ..) = self.send_receive(":vars").await?;
+        self.set_status(KernelStatus::Ready)?;
+
+        Ok(lines
+            .iter()
+            .filter_map(|line| {
+                let (name, native_type) = line.split_once(':')?;
+                let (name, native_type) = (name.trim(), native_type.trim());
+                if name.is_empty() || native_type.is_empty() {
+                    return None;
+                }
+
+                Some(Variable {
+                    name: name.to_string(),
+                    programming_language: Some("Rust".to_string()),
+                    native_type: Some(native_type.to_string()),
+                    node_type: Some(native_type_to_node_type(native_type).to_string()),
+                    ..Default::default()
+                })
+            })
+            .collect())
+    }
+
+    async fn get(&mut self, name: &str) -> Result<Option<Node>> {
+        tracing::trace!("Getting Rust variable");
+
+        self.set_status(KernelStatus::Busy)?;
+        let (mut lines, messages) = self
+            .send_receive(&format!("println!(\"{{:?}}\", {name});"))
+            .await?;
+        self.set_status(KernelStatus::Ready)?;
+
+        if !messages.is_empty() {
+            // Variable is not defined (or not printable)
+            return Ok(None);
+        }
+
+        Ok(lines.pop().map(|line| parse_output(&line)))
+    }
+
+    async fn set(&mut self, name: &str, node: &Node) -> Result<()> {
+        tracing::trace!("Setting Rust variable");
+
+        let code = match node {
+            Node::Boolean(value) => format!("let {name} = {value};"),
+            Node::Integer(value) => format!("let {name} = {value}i64;"),
+            Node::Number(value) => format!("let {name} = {value}f64;"),
+            Node::String(value) => {
+                format!("let {name} = String::from({});", serde_json::to_string(value)?)
+            }
+            _ => bail!(
+                "Setting variables of type `{}` is not supported by Rust kernels",
+                node.node_type()
+            ),
+        };
+
+        self.set_status(KernelStatus::Busy)?;
+        self.send_receive(&code).await?;
+        self.set_status(KernelStatus::Ready)
+    }
+
+    async fn remove(&mut self, name: &str) -> Result<()> {
+        tracing::trace!("Removing Rust variable");
+
+        // `evcxr` has no way to unbind a variable so shadow it with the
+        // unit type (which is not included in variable listings)
+        self.set_status(KernelStatus::Busy)?;
+        self.send_receive(&format!("let {name} = ();")).await?;
+        self.set_status(KernelStatus::Ready)
+    }
+}
+
+impl RustKernelInstance {
+    /// Create a new kernel instance
+    fn new() -> Self {
+        let status = Arc::new(AtomicU8::new(KernelStatus::Pending.into()));
+        let (status_sender, ..) = watch::channel(KernelStatus::Pending);
+
+        let (signal_sender, mut signal_receiver) = mpsc::channel(1);
+
+        // Start a task to handle signals
+        let status_clone = status.clone();
+        tokio::spawn(async move {
+            while let Some(kernel_signal) = signal_receiver.recv().await {
+                if matches!(kernel_signal, KernelSignal::Terminate | KernelSignal::Kill) {
+                    status_clone.store(KernelStatus::Stopped.into(), Ordering::SeqCst);
+                }
+            }
+        });
+
+        Self {
+            id: generate_id(NAME),
+            child: None,
+            stdin: None,
+            stdout: None,
+            stderr: None,
+            status,
+            status_sender,
+            signal_sender,
+        }
+    }
+
+    /// Get the status of the kernel instance
+    fn get_status(&self) -> KernelStatus {
+        self.status.load(Ordering::SeqCst).into()
+    }
+
+    /// Set the status of the kernel instance and notify watchers if there was a change
+    fn set_status(&mut self, status: KernelStatus) -> Result<()> {
+        self.status.store(status.into(), Ordering::SeqCst);
+
+        self.status_sender.send_if_modified(|previous| {
+            if status != *previous {
+                tracing::trace!(
+                    "Status of `{}` kernel changed from `{previous}` to `{status}`",
+                    self.id()
+                );
+                *previous = status;
+                true
+            } else {
+                false
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Send a task to the `evcxr` process and receive its outputs and messages
+    ///
+    /// After the task, sentinel lines are printed to both standard output
+    /// and standard error. Because `evcxr` evaluates inputs sequentially
+    /// the sentinels are only printed once the task has completed (or
+    /// failed to compile) so both streams can be read up to them.
+    async fn send_receive(&mut self, code: &str) -> Result<(Vec<String>, Vec<ExecutionMessage>)> {
+        let stdin = self.stdin.as_mut().ok_or_eyre("Kernel not started")?;
+        let stdout = self.stdout.as_mut().ok_or_eyre("Kernel not started")?;
+        let stderr = self.stderr.as_mut().ok_or_eyre("Kernel not started")?;
+
+        let mut task = code.trim_end().to_string();
+        if !task.is_empty() {
+            task.push('\n');
+        }
+        task.push_str(&format!(
+            "println!(\"{SENTINEL}\");\neprintln!(\"{SENTINEL}\");\n"
+        ));
+
+        stdin.write_all(task.as_bytes()).await?;
+        stdin.flush().await?;
+
+        let mut outputs = Vec::new();
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if stdout.read_line(&mut line).await? == 0 {
+                bail!("The `evcxr` process ended unexpectedly");
+            }
+            let line = line.trim_end();
+            if line == SENTINEL {
+                break;
+            }
+            outputs.push(line.to_string());
+        }
+
+        let mut errors = Vec::new();
+        loop {
+            line.clear();
+            if stderr.read_line(&mut line).await? == 0 {
+                bail!("The `evcxr` process ended unexpectedly");
+            }
+            let line = line.trim_end();
+            if line == SENTINEL {
+                break;
+            }
+            errors.push(line.to_string());
+        }
+
+        Ok((outputs, errors_to_messages(&errors)))
+    }
+}
+
+/// Parse a line of output into a [`Node`]
+///
+/// The Debug representation that `evcxr` prints for values is close enough
+/// to JSON for primitives (numbers, booleans, strings, vectors of those)
+/// that parsing as JSON is attempted first, with a fallback to a string.
+fn parse_output(line: &str) -> Node {
+    match serde_json::from_str(line) {
+        Ok(node) => node,
+        Err(..) => Node::String(line.to_string()),
+    }
+}
+
+/// Convert error lines from `evcxr` into [`ExecutionMessage`]s
+///
+/// Compiler errors are reported with a header line such as
+///
+///   [E0308] Error: mismatched types
+///
+/// followed by an annotated source snippet which includes the location of
+/// the error as `command:<line>:<column>`.
+fn errors_to_messages(lines: &[String]) -> Vec<ExecutionMessage> {
+    static HEADER_REGEX: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(r"^(?:\[(E\d{4})\]\s*)?(Error|Warning):\s*(.+)$").expect("invalid regex")
+    });
+    static LOCATION_REGEX: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"command(?:_\d+)?:(\d+):(\d+)").expect("invalid regex"));
+
+    let mut messages: Vec<ExecutionMessage> = Vec::new();
+    for line in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(captures) = HEADER_REGEX.captures(line) {
+            let level = match &captures[2] {
+                "Warning" => MessageLevel::Warning,
+                _ => MessageLevel::Error,
+            };
+            messages.push(ExecutionMessage {
+                level,
+                message: captures[3].to_string(),
+                error_type: captures.get(1).map(|code| code.as_str().to_string()),
+                ..Default::default()
+            });
+        } else if let Some(message) = messages.last_mut() {
+            // Add the line to the stack trace of the current message and
+            // extract any code location from it
+            if let Some(captures) = LOCATION_REGEX.captures(line) {
+                if message.code_location.is_none() {
+                    message.code_location = Some(CodeLocation {
+                        start_line: captures[1].parse().ok().map(|line: u64| line - 1),
+                        start_column: captures[2].parse().ok().map(|column: u64| column - 1),
+                        ..Default::default()
+                    });
+                }
+            }
+            let stack_trace = message.stack_trace.get_or_insert_with(String::new);
+            stack_trace.push_str(line);
+            stack_trace.push('\n');
+        } else {
+            messages.push(ExecutionMessage::new(MessageLevel::Error, line.to_string()));
+        }
+    }
+
+    messages
+}
+
+/// Infer the Stencila node type from the name of a Rust type
+fn native_type_to_node_type(native_type: &str) -> &'static str {
+    match native_type {
+        "bool" => "Boolean",
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+        | "u128" | "usize" => "Integer",
+        "f32" | "f64" => "Number",
+        "String" | "&str" | "str" => "String",
+        native_type if native_type.starts_with("Vec") || native_type.starts_with('[') => "Array",
+        _ => "Object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_dev::pretty_assertions::assert_eq;
+    use kernel::{
+        common::tokio,
+        schema::Node,
+        tests::{create_instance, start_instance},
+    };
+
+    use super::*;
+
+    // Pro-tip! Use get logs for these tests use:
+    //
+    // ```sh
+    // RUST_LOG=trace cargo test -p kernel-rust -- --nocapture
+    // ```
+    //
+    // These tests are slow (10s of seconds each) because `evcxr` compiles
+    // each input. They are skipped if `evcxr` is not installed.
+
+    /// Standard kernel test for execution of code
+    #[test_log::test(tokio::test)]
+    async fn execution() -> Result<()> {
+        let Some(instance) = create_instance::<RustKernel>().await? else {
+            return Ok(());
+        };
+
+        kernel::tests::execution(
+            instance,
+            vec![
+                // Empty code: no outputs
+                ("", vec![], vec![]),
+                (" ", vec![], vec![]),
+                // Only an expression: one output
+                ("1 + 1", vec![Node::Integer(2)], vec![]),
+                // Variables set in one chunk are available in the next
+                ("let a = 21;\nlet b = 2;", vec![], vec![]),
+                ("a * b", vec![Node::Integer(42)], vec![]),
+            ],
+        )
+        .await
+    }
+
+    /// Standard kernel test for getting and setting variables
+    #[test_log::test(tokio::test)]
+    async fn var_get_set() -> Result<()> {
+        let Some(mut instance) = start_instance::<RustKernel>().await? else {
+            return Ok(());
+        };
+
+        instance.set("a", &Node::Integer(123)).await?;
+        assert_eq!(instance.get("a").await?, Some(Node::Integer(123)));
+
+        Ok(())
+    }
+
+    /// Custom test for mapping of compiler errors to messages
+    #[test_log::test(tokio::test)]
+    async fn messages() -> Result<()> {
+        let Some(mut instance) = start_instance::<RustKernel>().await? else {
+            return Ok(());
+        };
+
+        let (outputs, messages) = instance.execute("let x: i32 = \"str\";").await?;
+        assert_eq!(outputs, vec![]);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].level, MessageLevel::Error);
+        assert_eq!(messages[0].error_type.as_deref(), Some("E0308"));
+
+        Ok(())
+    }
+}
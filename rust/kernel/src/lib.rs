@@ -17,7 +17,10 @@ use format::Format;
 pub use common;
 pub use format;
 pub use schema;
-use schema::{ExecutionMessage, Node, Null, SoftwareApplication, SoftwareSourceCode, Variable};
+use schema::{
+    CompilationMessage, ExecutionMessage, Node, Null, SoftwareApplication, SoftwareSourceCode,
+    Variable,
+};
 
 /// A kernel for executing code in some language
 ///
@@ -64,6 +67,28 @@ pub trait Kernel: Sync + Send {
         self.supports_languages().contains(format)
     }
 
+    /// Lint some code in one of the kernel's languages
+    ///
+    /// Returns a [`CompilationMessage`] for each diagnostic emitted by any
+    /// linters or type checkers available on the current machine. The
+    /// `directory` is the directory of the document so that project level
+    /// linter configuration is respected. The default implementation returns
+    /// no messages.
+    #[allow(unused_variables)]
+    fn lint(&self, code: &str, directory: &Path) -> Result<Vec<CompilationMessage>> {
+        Ok(Vec::new())
+    }
+
+    /// Generate code to seed the random number generators of the kernel
+    ///
+    /// Used so that documents can be executed reproducibly (e.g. for
+    /// regression testing). The default implementation returns `None` for
+    /// kernels which have no random number generator that can be seeded.
+    #[allow(unused_variables)]
+    fn seed_code(&self, seed: u64) -> Option<String> {
+        None
+    }
+
     /// Does the kernel support the interrupt signal?
     fn supports_interrupt(&self) -> KernelInterrupt {
         KernelInterrupt::No
@@ -100,6 +125,7 @@ pub enum KernelType {
     // Note that the order here influences how kernels are displayed
     // in the `stencila kernels list` command. So change with intent.
     Programming,
+    Database,
     Templating,
     Diagrams,
     Math,
@@ -259,6 +285,21 @@ pub trait KernelInstance: Sync + Send {
     /// Execute code, possibly with side effects, in the kernel instance
     async fn execute(&mut self, code: &str) -> Result<(Vec<Node>, Vec<ExecutionMessage>)>;
 
+    /// Execute code in the kernel instance, streaming outputs as they are produced
+    ///
+    /// Each output is sent on `sender` as soon as it is emitted by the kernel
+    /// (e.g. by a `print` statement), rather than being delivered only in a
+    /// single batch at the end. The complete outputs and messages are returned
+    /// as for [`KernelInstance::execute`]. The default implementation does not
+    /// stream.
+    async fn execute_stream(
+        &mut self,
+        code: &str,
+        sender: mpsc::UnboundedSender<Node>,
+    ) -> Result<(Vec<Node>, Vec<ExecutionMessage>)> {
+        self.execute(code).await
+    }
+
     /// Evaluate a code expression, without side effects, in the kernel instance
     async fn evaluate(&mut self, code: &str) -> Result<(Node, Vec<ExecutionMessage>)> {
         let (nodes, messages) = self.execute(code).await?;
@@ -298,6 +339,37 @@ pub trait KernelInstance: Sync + Send {
         Ok(())
     }
 
+    /// Snapshot the state of the kernel instance to a file
+    ///
+    /// Allows the state of a kernel (e.g. its variables) to be persisted,
+    /// for example in a document's `.stencila` directory, and later
+    /// [`KernelInstance::restore`]d so that expensive setup code does not
+    /// need to be re-run between sessions.
+    async fn snapshot(&mut self, path: &Path) -> Result<()> {
+        bail!("Kernel `{}` does not support snapshots", self.id())
+    }
+
+    /// Restore the state of the kernel instance from a snapshot file
+    async fn restore(&mut self, path: &Path) -> Result<()> {
+        bail!("Kernel `{}` does not support snapshots", self.id())
+    }
+
+    /// Write a variable to a file in Arrow IPC format
+    ///
+    /// Used, with [`KernelInstance::set_arrow`], to transfer tabular
+    /// variables (e.g. data frames) between kernels without the overhead of
+    /// serializing them to and from a JSON `Datatable`. Errors if the kernel
+    /// does not support Arrow, or the variable is not tabular, in which case
+    /// the caller should fall back to [`KernelInstance::get`].
+    async fn get_arrow(&mut self, name: &str, path: &Path) -> Result<()> {
+        bail!("Kernel `{}` does not support Arrow", self.id())
+    }
+
+    /// Read a variable from a file in Arrow IPC format
+    async fn set_arrow(&mut self, name: &str, path: &Path) -> Result<()> {
+        bail!("Kernel `{}` does not support Arrow", self.id())
+    }
+
     /// Create a fork of the kernel instance
     async fn fork(&mut self) -> Result<Box<dyn KernelInstance>> {
         bail!("Kernel `{}` does not support forks", self.id())
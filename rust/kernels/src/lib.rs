@@ -2,11 +2,13 @@ use std::{
     env, fmt,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use kernel::{
     common::{
         eyre::{bail, Result},
+        tempfile::tempdir,
         tokio::{
             self,
             sync::{broadcast, mpsc, Mutex, RwLock},
@@ -14,26 +16,31 @@ use kernel::{
         tracing,
     },
     format::Format,
-    schema::{ExecutionMessage, Node},
-    Kernel, KernelForks, KernelInstance, KernelVariableRequest, KernelVariableRequester,
-    KernelVariableResponse,
+    schema::{ExecutionMessage, MessageLevel, Node, Null},
+    Kernel, KernelForks, KernelInstance, KernelSignal, KernelStatus, KernelVariableRequest,
+    KernelVariableRequester, KernelVariableResponse,
 };
 use kernel_asciimath::AsciiMathKernel;
 use kernel_bash::BashKernel;
+use kernel_duckdb::DuckDbKernel;
+use kernel_go::GoKernel;
 use kernel_graphviz::GraphvizKernel;
 use kernel_jinja::JinjaKernel;
 use kernel_mermaid::MermaidKernel;
 use kernel_nodejs::NodeJsKernel;
+use kernel_pyodide::PyodideKernel;
 use kernel_python::PythonKernel;
 use kernel_quickjs::QuickJsKernel;
 use kernel_r::RKernel;
 use kernel_rhai::RhaiKernel;
+use kernel_rust::RustKernel;
 use kernel_style::StyleKernel;
 use kernel_tex::TexKernel;
 
 pub use kernel::{KernelAvailability, KernelProvider, KernelType};
 
 pub mod cli;
+pub mod pool;
 
 /// Get a list of available kernels
 pub async fn list() -> Vec<Box<dyn Kernel>> {
@@ -42,13 +49,17 @@ pub async fn list() -> Vec<Box<dyn Kernel>> {
         Box::<QuickJsKernel>::default() as Box<dyn Kernel>,
         Box::<AsciiMathKernel>::default() as Box<dyn Kernel>,
         Box::<BashKernel>::default() as Box<dyn Kernel>,
+        Box::<DuckDbKernel>::default() as Box<dyn Kernel>,
+        Box::<GoKernel>::default() as Box<dyn Kernel>,
         Box::<GraphvizKernel>::default() as Box<dyn Kernel>,
         Box::<JinjaKernel>::default() as Box<dyn Kernel>,
         Box::<MermaidKernel>::default() as Box<dyn Kernel>,
         Box::<NodeJsKernel>::default() as Box<dyn Kernel>,
         Box::<PythonKernel>::default() as Box<dyn Kernel>,
+        Box::<PyodideKernel>::default() as Box<dyn Kernel>,
         Box::<RKernel>::default() as Box<dyn Kernel>,
         Box::<RhaiKernel>::default() as Box<dyn Kernel>,
+        Box::<RustKernel>::default() as Box<dyn Kernel>,
         Box::<StyleKernel>::default() as Box<dyn Kernel>,
         Box::<TexKernel>::default() as Box<dyn Kernel>,
     ];
@@ -77,6 +88,19 @@ pub fn default() -> Box<dyn Kernel> {
     Box::<QuickJsKernel>::default() as Box<dyn Kernel>
 }
 
+/// Create an [`ExecutionMessage`] for an execution task that timed out
+fn timeout_message(timeout: Duration) -> ExecutionMessage {
+    ExecutionMessage {
+        level: MessageLevel::Error,
+        message: format!(
+            "Execution timed out after {seconds} seconds",
+            seconds = timeout.as_secs()
+        ),
+        error_type: Some("Timeout".to_string()),
+        ..Default::default()
+    }
+}
+
 /// An entry for a kernel instance
 struct KernelInstanceEntry {
     /// The kernel that the instance is an instance of
@@ -88,6 +112,20 @@ struct KernelInstanceEntry {
 
     /// The instance itself
     instance: Arc<Mutex<Box<dyn KernelInstance>>>,
+
+    /// Code that has been executed on the instance without error
+    ///
+    /// Recorded so that it can be replayed if the kernel process dies
+    /// and the instance needs to be restarted.
+    history: Arc<RwLock<Vec<String>>>,
+
+    /// A sender for signals to the instance
+    ///
+    /// Captured when the instance is created so that a running task can be
+    /// interrupted without needing to take a lock on the instance (which is
+    /// held while the task is executing). `None` for kernels that do not
+    /// support signals.
+    signaller: Option<mpsc::Sender<KernelSignal>>,
 }
 
 type KernelInstances = Arc<RwLock<Vec<KernelInstanceEntry>>>;
@@ -107,6 +145,12 @@ pub struct Kernels {
 
     /// A sender for responses to kernels for variables
     variable_response_sender: broadcast::Sender<KernelVariableResponse>,
+
+    /// A seed for the random number generators of kernel instances
+    ///
+    /// When set, each newly created kernel instance has its kernel's
+    /// seeding code executed so that documents render reproducibly.
+    seed: Option<u64>,
 }
 
 impl fmt::Debug for Kernels {
@@ -151,9 +195,18 @@ impl Kernels {
             instances,
             variable_request_sender,
             variable_response_sender,
+            seed: None,
         }
     }
 
+    /// Set the seed for the random number generators of kernel instances
+    ///
+    /// Applies to kernel instances created after this is called, not to
+    /// existing instances.
+    pub fn seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+
     /// Create a new set of kernels in the current working directory
     pub fn new_here() -> Self {
         let path = std::env::current_dir().expect("should always be a current dir");
@@ -231,7 +284,19 @@ impl Kernels {
             None => default(),
         };
 
-        let mut instance = kernel.create_instance()?;
+        let kernel = Arc::new(kernel);
+
+        // Use a warm instance from the pool if available, otherwise create
+        // and start a new instance
+        let mut instance = match pool::take(&kernel.name(), &self.home).await {
+            Some(instance) => instance,
+            None => {
+                let mut instance = kernel.create_instance()?;
+                instance.start(&self.home).await?;
+                instance
+            }
+        };
+
         let id = instance.id().to_string();
         if kernel.supports_variable_requests() {
             instance.variable_channel(
@@ -239,14 +304,31 @@ impl Kernels {
                 self.variable_response_sender.subscribe(),
             );
         }
-        instance.start(&self.home).await?;
+
+        // Seed the kernel's random number generators if a seed has been set
+        if let Some(seed) = self.seed {
+            if let Some(code) = kernel.seed_code(seed) {
+                let (.., messages) = instance.execute(&code).await?;
+                for message in messages {
+                    tracing::warn!("While seeding `{id}` kernel: {}", message.message);
+                }
+            }
+        }
+
+        // Start warming spare instances for next time (no-op if pooling
+        // is not enabled)
+        pool::replenish(kernel.clone(), self.home.clone());
+
+        let signaller = instance.signal_sender().ok();
         let instance = Arc::new(Mutex::new(instance));
 
         let mut instances = self.instances.write().await;
         instances.push(KernelInstanceEntry {
-            kernel: Arc::new(kernel),
+            kernel,
             id,
             instance: instance.clone(),
+            history: Arc::new(RwLock::new(Vec::new())),
+            signaller,
         });
 
         Ok(instance)
@@ -270,6 +352,7 @@ impl Kernels {
         }
 
         let id = instance.id().to_string();
+        let signaller = instance.signal_sender().ok();
         let instance = Arc::new(Mutex::new(instance));
 
         let mut instances = self.instances.write().await;
@@ -277,6 +360,8 @@ impl Kernels {
             kernel,
             id,
             instance,
+            history: Arc::new(RwLock::new(Vec::new())),
+            signaller,
         });
 
         Ok(())
@@ -348,11 +433,113 @@ impl Kernels {
             .collect()
     }
 
+    /// Check that a kernel instance is still alive and restart it if its process has died
+    ///
+    /// When restarting, code that was previously executed on the instance
+    /// without error is replayed so that variables and imports are restored
+    /// and downstream code does not fail unnecessarily.
+    async fn ensure_alive(&mut self, instance: &Arc<Mutex<Box<dyn KernelInstance>>>) -> Result<()> {
+        let status = instance.lock().await.status().await?;
+        if !matches!(status, KernelStatus::Failed) {
+            return Ok(());
+        }
+
+        let entries = self.instances.read().await;
+        let Some(entry) = entries
+            .iter()
+            .find(|entry| Arc::ptr_eq(&entry.instance, instance))
+        else {
+            bail!("Kernel instance has failed and can not be restarted")
+        };
+        let kernel = entry.kernel.clone();
+        let history = entry.history.clone();
+        let old_id = entry.id.clone();
+        drop(entries);
+
+        let history = history.read().await;
+        tracing::warn!(
+            "Kernel instance `{old_id}` died; restarting and replaying {} previous executions",
+            history.len()
+        );
+
+        let mut new_instance = kernel.create_instance()?;
+        new_instance.start(&self.home).await?;
+        if kernel.supports_variable_requests() {
+            new_instance.variable_channel(
+                self.variable_request_sender.clone(),
+                self.variable_response_sender.subscribe(),
+            );
+        }
+
+        for code in history.iter() {
+            if let Err(error) = new_instance.execute(code).await {
+                tracing::warn!("While replaying code on restarted kernel: {error}");
+            }
+        }
+
+        let new_id = new_instance.id().to_string();
+        let new_signaller = new_instance.signal_sender().ok();
+        *instance.lock().await = new_instance;
+
+        let mut entries = self.instances.write().await;
+        if let Some(entry) = entries
+            .iter_mut()
+            .find(|entry| Arc::ptr_eq(&entry.instance, instance))
+        {
+            entry.id = new_id;
+            entry.signaller = new_signaller;
+        }
+
+        Ok(())
+    }
+
+    /// Record code that was executed on a kernel instance without error
+    ///
+    /// The code is added to the instance's replay history, used by
+    /// [`Kernels::ensure_alive`] when restarting a dead kernel.
+    async fn record_execution(
+        &self,
+        instance: &Arc<Mutex<Box<dyn KernelInstance>>>,
+        code: &str,
+        messages: &[ExecutionMessage],
+    ) {
+        if messages.iter().any(|message| {
+            matches!(
+                message.level,
+                MessageLevel::Error | MessageLevel::Exception
+            )
+        }) {
+            return;
+        }
+
+        let entries = self.instances.read().await;
+        if let Some(entry) = entries
+            .iter()
+            .find(|entry| Arc::ptr_eq(&entry.instance, instance))
+        {
+            entry.history.write().await.push(code.to_string());
+        }
+    }
+
     /// Execute some code in a kernel instance
     pub async fn execute(
         &mut self,
         code: &str,
         language: Option<&str>,
+    ) -> Result<(Vec<Node>, Vec<ExecutionMessage>, String)> {
+        self.execute_with_timeout(code, language, None).await
+    }
+
+    /// Execute some code in a kernel instance with an optional timeout
+    ///
+    /// If execution takes longer than `timeout`, interrupts the kernel
+    /// instance (if it supports interrupts) and returns a timeout
+    /// [`ExecutionMessage`] instead of hanging indefinitely.
+    pub async fn execute_with_timeout(
+        &mut self,
+        code: &str,
+        language: Option<&str>,
+        timeout: Option<Duration>,
     ) -> Result<(Vec<Node>, Vec<ExecutionMessage>, String)> {
         let instance = match language {
             Some(language) => match self.get_instance_for(language).await? {
@@ -362,11 +549,88 @@ impl Kernels {
             None => self.get_instance_programming().await?,
         };
 
-        let mut instance = instance.lock().await;
-        let (nodes, messages) = instance.execute(code).await?;
-        let id = instance.id().to_string();
+        self.ensure_alive(&instance).await?;
+
+        let mut guard = instance.lock().await;
+        let id = guard.id().to_string();
+
+        let Some(timeout) = timeout else {
+            let (nodes, messages) = guard.execute(code).await?;
+            drop(guard);
+            self.record_execution(&instance, code, &messages).await;
+            return Ok((nodes, messages, id));
+        };
+
+        let signaller = guard.signal_sender().ok();
+        match tokio::time::timeout(timeout, guard.execute(code)).await {
+            Ok(result) => {
+                let (nodes, messages) = result?;
+                drop(guard);
+                self.record_execution(&instance, code, &messages).await;
+                Ok((nodes, messages, id))
+            }
+            Err(..) => {
+                if let Some(signaller) = signaller {
+                    if let Err(error) = signaller.send(KernelSignal::Interrupt).await {
+                        tracing::warn!("While interrupting kernel instance: {error}");
+                    }
+                }
+
+                Ok((Vec::new(), vec![timeout_message(timeout)], id))
+            }
+        }
+    }
+
+    /// Execute some code in a kernel instance, streaming outputs as they are produced
+    ///
+    /// As for [`Kernels::execute_with_timeout`] but with each output sent on
+    /// `sender` as soon as it is emitted by the kernel, so that outputs of
+    /// long-running code can be displayed incrementally.
+    pub async fn execute_stream(
+        &mut self,
+        code: &str,
+        language: Option<&str>,
+        timeout: Option<Duration>,
+        sender: mpsc::UnboundedSender<Node>,
+    ) -> Result<(Vec<Node>, Vec<ExecutionMessage>, String)> {
+        let instance = match language {
+            Some(language) => match self.get_instance_for(language).await? {
+                Some(instance) => instance,
+                None => self.create_instance(Some(language)).await?,
+            },
+            None => self.get_instance_programming().await?,
+        };
+
+        self.ensure_alive(&instance).await?;
+
+        let mut guard = instance.lock().await;
+        let id = guard.id().to_string();
 
-        Ok((nodes, messages, id))
+        let Some(timeout) = timeout else {
+            let (nodes, messages) = guard.execute_stream(code, sender).await?;
+            drop(guard);
+            self.record_execution(&instance, code, &messages).await;
+            return Ok((nodes, messages, id));
+        };
+
+        let signaller = guard.signal_sender().ok();
+        match tokio::time::timeout(timeout, guard.execute_stream(code, sender)).await {
+            Ok(result) => {
+                let (nodes, messages) = result?;
+                drop(guard);
+                self.record_execution(&instance, code, &messages).await;
+                Ok((nodes, messages, id))
+            }
+            Err(..) => {
+                if let Some(signaller) = signaller {
+                    if let Err(error) = signaller.send(KernelSignal::Interrupt).await {
+                        tracing::warn!("While interrupting kernel instance: {error}");
+                    }
+                }
+
+                Ok((Vec::new(), vec![timeout_message(timeout)], id))
+            }
+        }
     }
 
     /// Evaluate a code expression in a kernel instance
@@ -374,6 +638,18 @@ impl Kernels {
         &mut self,
         code: &str,
         language: Option<&str>,
+    ) -> Result<(Node, Vec<ExecutionMessage>, String)> {
+        self.evaluate_with_timeout(code, language, None).await
+    }
+
+    /// Evaluate a code expression in a kernel instance with an optional timeout
+    ///
+    /// As for [`Kernels::execute_with_timeout`] but for evaluating an expression.
+    pub async fn evaluate_with_timeout(
+        &mut self,
+        code: &str,
+        language: Option<&str>,
+        timeout: Option<Duration>,
     ) -> Result<(Node, Vec<ExecutionMessage>, String)> {
         let instance = match language {
             Some(language) => match self.get_instance_for(language).await? {
@@ -384,10 +660,29 @@ impl Kernels {
         };
 
         let mut instance = instance.lock().await;
-        let (node, messages) = instance.evaluate(code).await?;
         let id = instance.id().to_string();
 
-        Ok((node, messages, id))
+        let Some(timeout) = timeout else {
+            let (node, messages) = instance.evaluate(code).await?;
+            return Ok((node, messages, id));
+        };
+
+        let signaller = instance.signal_sender().ok();
+        match tokio::time::timeout(timeout, instance.evaluate(code)).await {
+            Ok(result) => {
+                let (node, messages) = result?;
+                Ok((node, messages, id))
+            }
+            Err(..) => {
+                if let Some(signaller) = signaller {
+                    if let Err(error) = signaller.send(KernelSignal::Interrupt).await {
+                        tracing::warn!("While interrupting kernel instance: {error}");
+                    }
+                }
+
+                Ok((Node::Null(Null), vec![timeout_message(timeout)], id))
+            }
+        }
     }
 
     /// Get a variable from the kernels
@@ -420,6 +715,109 @@ impl Kernels {
         instance.remove(name).await
     }
 
+    /// Transfer a variable to the kernel instance for a language
+    ///
+    /// Finds the kernel instance that has the variable and sets it in the
+    /// kernel instance for `language`. Uses Arrow IPC (via
+    /// [`KernelInstance::get_arrow`] and [`KernelInstance::set_arrow`]) so
+    /// that large tabular variables, such as data frames, do not need to be
+    /// round-tripped through a JSON `Datatable`. Falls back to JSON-based
+    /// `get`/`set` if either kernel does not support Arrow, or the variable
+    /// is not tabular.
+    pub async fn transfer(&mut self, name: &str, language: Option<&str>) -> Result<()> {
+        // Find the instance that has the variable
+        let mut source = None;
+        for entry in self.instances.read().await.iter() {
+            let mut instance = entry.instance.lock().await;
+            if instance
+                .list()
+                .await
+                .iter()
+                .flatten()
+                .any(|variable| variable.name == name)
+            {
+                drop(instance);
+                source = Some(entry.instance.clone());
+                break;
+            }
+        }
+        let Some(source) = source else {
+            bail!("Unable to find variable `{name}` in any kernel")
+        };
+
+        let destination = match language {
+            Some(language) => match self.get_instance_for(language).await? {
+                Some(instance) => instance,
+                None => self.create_instance(Some(language)).await?,
+            },
+            None => self.get_instance_programming().await?,
+        };
+
+        // Attempt Arrow-based transfer first
+        let temp_dir = tempdir()?;
+        let path = temp_dir.path().join(format!("{name}.arrow"));
+        if let Err(error) = source.lock().await.get_arrow(name, &path).await {
+            tracing::debug!("Unable to transfer variable `{name}` as Arrow: {error}");
+        } else {
+            return destination.lock().await.set_arrow(name, &path).await;
+        }
+
+        // Fall back to JSON-based transfer
+        let Some(value) = source.lock().await.get(name).await? else {
+            bail!("Unable to get variable `{name}` from kernel")
+        };
+        destination.lock().await.set(name, &value).await
+    }
+
+    /// Interrupt the kernel instance that a node is executing in
+    ///
+    /// Sends the interrupt signal to the instance with `id`, falling back to
+    /// instances of kernels that support `language`. Only the currently
+    /// running task is cancelled: the instance's state is left intact and
+    /// other pending nodes remain runnable.
+    pub async fn interrupt_instance(
+        &self,
+        id: Option<&str>,
+        language: Option<&str>,
+    ) -> Result<()> {
+        let format = language.map(Format::from_name);
+        let entries = self.instances.read().await;
+
+        // Prefer the instance with `id`, falling back to instances of
+        // kernels that support `language`
+        let mut interrupted = false;
+        for by_id in [true, false] {
+            for entry in entries.iter() {
+                let matches = if by_id {
+                    id.is_some_and(|id| entry.id == id)
+                } else {
+                    format
+                        .as_ref()
+                        .is_some_and(|format| entry.kernel.supports_language(format))
+                };
+                if !matches {
+                    continue;
+                }
+
+                let Some(signaller) = &entry.signaller else {
+                    bail!("Kernel instance `{}` does not support interrupts", entry.id)
+                };
+                signaller.send(KernelSignal::Interrupt).await?;
+                interrupted = true;
+            }
+
+            if interrupted {
+                break;
+            }
+        }
+
+        if !interrupted {
+            bail!("No kernel instance found to interrupt")
+        }
+
+        Ok(())
+    }
+
     /// Whether all kernels in the set support forking
     pub async fn supports_forks(&self) -> bool {
         self.instances
@@ -435,6 +833,7 @@ impl Kernels {
     /// Errors if any of the forks fails (i.e. a complete fork is not possible).
     pub async fn fork(&self) -> Result<Self> {
         let mut kernels = Self::new(&self.home);
+        kernels.seed = self.seed;
         for entry in self.instances.read().await.iter() {
             let kernel = entry.kernel.clone();
             let instance = entry.instance.lock().await.fork().await?;
@@ -442,6 +841,46 @@ impl Kernels {
         }
         Ok(kernels)
     }
+
+    /// Snapshot the state of the kernels to a directory
+    ///
+    /// Writes a snapshot file for each kernel instance into `dir` (e.g. the
+    /// `.stencila` directory of a document) named after the kernel. Kernels
+    /// that do not support snapshots are skipped with a log message.
+    pub async fn snapshot(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+
+        for entry in self.instances.read().await.iter() {
+            let path = dir.join(entry.kernel.name());
+            if let Err(error) = entry.instance.lock().await.snapshot(&path).await {
+                tracing::debug!("While snapshotting kernel `{}`: {error}", entry.id);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restore the state of kernels from a directory of snapshot files
+    ///
+    /// For each file in `dir` (named after a kernel, as written by
+    /// [`Kernels::snapshot`]) creates an instance of the kernel and restores
+    /// its state from the file.
+    pub async fn restore(&mut self, dir: &Path) -> Result<()> {
+        for entry in std::fs::read_dir(dir)?.flatten() {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+
+            let name = entry.file_name().to_string_lossy().to_string();
+            let instance = self.create_instance(Some(&name)).await?;
+            if let Err(error) = instance.lock().await.restore(&path).await {
+                tracing::warn!("While restoring kernel `{name}`: {error}");
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
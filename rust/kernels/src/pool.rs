@@ -0,0 +1,114 @@
+//! A pool of pre-started, warm kernel instances
+//!
+//! Cold-starting kernels such as Python and R can take seconds which adds
+//! up when rendering many documents (e.g. in CI pipelines). When pooling is
+//! enabled, each time a kernel instance is created, spare instances are
+//! started in the background so that subsequent instances of the same kernel
+//! can be drawn from the pool rather than cold-started.
+//!
+//! Pooling is disabled by default and enabled by setting the
+//! `STENCILA_KERNEL_POOL_SIZE` environment variable to the number of warm
+//! instances to keep per kernel.
+
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use kernel::{
+    common::{
+        once_cell::sync::Lazy,
+        tokio::{self, sync::Mutex},
+        tracing,
+    },
+    Kernel, KernelInstance,
+};
+
+/// The environment variable used to configure the size of the pool
+const POOL_SIZE_VAR: &str = "STENCILA_KERNEL_POOL_SIZE";
+
+/// Warm instances, keyed by kernel name, with the directory each was started in
+///
+/// The directory is recorded because instances are started in the home
+/// directory of a document and should only be reused for documents with
+/// the same home.
+type Pool = HashMap<String, Vec<(PathBuf, Box<dyn KernelInstance>)>>;
+
+static POOL: Lazy<Mutex<Pool>> = Lazy::new(Mutex::default);
+
+/// Get the configured number of warm instances to keep per kernel
+///
+/// Zero (the default) disables pooling.
+pub fn size() -> usize {
+    env::var(POOL_SIZE_VAR)
+        .ok()
+        .and_then(|size| size.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Take a warm instance of a kernel from the pool, if any
+///
+/// Only returns an instance that was started in `home` since kernels are
+/// sensitive to the directory they are started in.
+pub(crate) async fn take(kernel_name: &str, home: &Path) -> Option<Box<dyn KernelInstance>> {
+    if size() == 0 {
+        return None;
+    }
+
+    let mut pool = POOL.lock().await;
+    let instances = pool.get_mut(kernel_name)?;
+    let index = instances.iter().position(|(dir, ..)| dir == home)?;
+    let (.., instance) = instances.swap_remove(index);
+
+    tracing::debug!("Using warm `{kernel_name}` kernel instance from pool");
+
+    Some(instance)
+}
+
+/// Replenish the pool of warm instances for a kernel in the background
+///
+/// Starts instances until the pool for the kernel has the configured number
+/// of instances started in `home`. Does nothing if pooling is disabled.
+pub(crate) fn replenish(kernel: Arc<Box<dyn Kernel>>, home: PathBuf) {
+    let size = size();
+    if size == 0 {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let kernel_name = kernel.name();
+        loop {
+            {
+                let pool = POOL.lock().await;
+                let count = pool
+                    .get(&kernel_name)
+                    .map(|instances| {
+                        instances.iter().filter(|(dir, ..)| dir == &home).count()
+                    })
+                    .unwrap_or(0);
+                if count >= size {
+                    break;
+                }
+            }
+
+            let mut instance = match kernel.create_instance() {
+                Ok(instance) => instance,
+                Err(error) => {
+                    tracing::warn!("While creating warm `{kernel_name}` kernel instance: {error}");
+                    break;
+                }
+            };
+            if let Err(error) = instance.start(&home).await {
+                tracing::warn!("While starting warm `{kernel_name}` kernel instance: {error}");
+                break;
+            }
+
+            let mut pool = POOL.lock().await;
+            pool.entry(kernel_name.clone())
+                .or_default()
+                .push((home.clone(), instance));
+        }
+    });
+}
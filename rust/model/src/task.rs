@@ -3,6 +3,7 @@ use common::{
     serde_with::skip_serializing_none,
     smart_default::SmartDefault,
     strum::Display,
+    tokio::sync::mpsc,
 };
 use format::Format;
 use schema::{InstructionMessage, InstructionModel, InstructionType};
@@ -195,6 +196,14 @@ pub struct ModelTask {
     /// at the last possible moment before generation (usually just before an API request is made).
     #[serde(default)]
     pub dry_run: bool,
+
+    /// A sender for streaming chunks of generated content
+    ///
+    /// When set, model implementations that support streaming generation should
+    /// send chunks of content as they are generated, in addition to returning
+    /// the complete content in the `ModelOutput`.
+    #[serde(skip)]
+    pub content_sender: Option<mpsc::UnboundedSender<String>>,
 }
 
 impl ModelTask {
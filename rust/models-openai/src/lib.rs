@@ -18,6 +18,7 @@ use model::{
     common::{
         async_trait::async_trait,
         eyre::{bail, Result},
+        futures::StreamExt,
         inflector::Inflector,
         itertools::Itertools,
         tracing,
@@ -270,16 +271,40 @@ impl OpenAIModel {
             return ModelOutput::empty(self);
         }
 
-        // Send the request
+        // Send the request, streaming the response if the task has a content sender
         let client = Self::client()?;
-        let mut response = client.chat().create(request).await?;
-
-        // Get the content of the first message
-        let text = response
-            .choices
-            .pop()
-            .and_then(|choice| choice.message.content)
-            .unwrap_or_default();
+        let text = if let Some(sender) = &task.content_sender {
+            let mut request = request;
+            request.stream = Some(true);
+
+            let mut stream = client.chat().create_stream(request).await?;
+
+            let mut text = String::new();
+            while let Some(response) = stream.next().await {
+                let response = response?;
+                if let Some(delta) = response
+                    .choices
+                    .first()
+                    .and_then(|choice| choice.delta.content.clone())
+                {
+                    text.push_str(&delta);
+                    if sender.send(delta).is_err() {
+                        // Receiver dropped (e.g. generation cancelled) so stop streaming
+                        break;
+                    }
+                }
+            }
+            text
+        } else {
+            let mut response = client.chat().create(request).await?;
+
+            // Get the content of the first message
+            response
+                .choices
+                .pop()
+                .and_then(|choice| choice.message.content)
+                .unwrap_or_default()
+        };
 
         ModelOutput::from_text(self, &task.format, text).await
     }
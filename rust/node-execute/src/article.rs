@@ -1,6 +1,8 @@
-use schema::{diff, Article, PatchSlot};
+use schema::{
+    diff, Article, Author, CreativeWorkType, CreativeWorkTypeOrText, PatchSlot, Person, ThingType,
+};
 
-use crate::{interrupt_impl, prelude::*, HeadingInfo};
+use crate::{interrupt_impl, prelude::*, GlossaryTerm, HeadingInfo};
 
 impl Executable for Article {
     #[tracing::instrument(skip_all)]
@@ -11,6 +13,31 @@ impl Executable for Article {
         // Clear the executor's headings
         executor.headings.clear();
 
+        // Collect the article's references so that citations to them can
+        // be rendered
+        executor.references.clear();
+        for (index, reference) in self.references.iter().flatten().enumerate() {
+            if let Some((id, label)) = reference_id_and_label(reference) {
+                executor.references.insert(id, (index + 1, label));
+            }
+        }
+
+        // Collect the article's glossary terms, from `DefinedTerm`s amongst
+        // its `about` property, so that uses of the terms can be expanded
+        // and linked
+        executor.glossary.clear();
+        for thing in self.options.about.iter().flatten() {
+            if let ThingType::DefinedTerm(term) = thing {
+                executor.glossary.push(GlossaryTerm {
+                    name: term.name.clone(),
+                    code: term.options.term_code.clone(),
+                    description: term.options.description.clone(),
+                    node_id: term.node_id(),
+                    used: false,
+                });
+            }
+        }
+
         // Compile the `content` and `title` (could include math)
         if let Err(error) = async {
             self.title.walk_async(executor).await?;
@@ -91,6 +118,9 @@ impl Executable for Article {
         };
 
         let ended = Timestamp::now();
+        executor
+            .profile_span(self.node_type(), &node_id, &started, &ended)
+            .await;
 
         // TODO: set status based on the execution status of
         // child executable nodes
@@ -126,3 +156,41 @@ impl Executable for Article {
         WalkControl::Continue
     }
 }
+
+/// Get the id of a reference, and an author-date label for it
+///
+/// Returns `None` if the reference does not have an id, in which case it
+/// can not be the target of a citation.
+fn reference_id_and_label(reference: &CreativeWorkTypeOrText) -> Option<(String, String)> {
+    let CreativeWorkTypeOrText::CreativeWorkType(CreativeWorkType::Article(article)) = reference
+    else {
+        return None;
+    };
+
+    let id = article.id.clone()?;
+
+    let mut family_names = article.authors.iter().flatten().filter_map(|author| {
+        match author {
+            Author::Person(Person {
+                family_names: Some(names),
+                ..
+            }) => names.first().cloned(),
+            Author::Organization(org) => org.name.clone(),
+            _ => None,
+        }
+    });
+    let author = match (family_names.next(), family_names.next(), family_names.next()) {
+        (Some(first), None, ..) => first,
+        (Some(first), Some(second), None) => format!("{first} & {second}"),
+        (Some(first), Some(..), Some(..)) => format!("{first} et al."),
+        _ => "Anon".to_string(),
+    };
+
+    let year = article
+        .date_published
+        .as_ref()
+        .and_then(|date| date.value.get(..4))
+        .map_or_else(|| "n.d.".to_string(), String::from);
+
+    Some((id, format!("{author}, {year}")))
+}
@@ -0,0 +1,91 @@
+//! An on-disk cache of code chunk outputs
+//!
+//! Stored in the `.stencila` directory of the workspace (alongside other
+//! workspace level files such as the embeddings index) so that unchanged
+//! chunks can have their outputs restored without running a kernel,
+//! similar to `knitr`'s chunk cache.
+//!
+//! To avoid restoring a chunk whose side effects (i.e. the variables it
+//! defines in the kernel) are needed by downstream chunks, only chunks
+//! which do not appear to write any variables are cached.
+
+use std::{
+    collections::HashMap,
+    fs::{create_dir_all, read_to_string, write},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use common::{
+    seahash::SeaHasher,
+    serde::{Deserialize, Serialize},
+    serde_json, tracing,
+};
+use schema::{Hint, Node};
+
+/// An on-disk cache of the outputs of code chunks
+#[derive(Default, Serialize, Deserialize)]
+#[serde(crate = "common::serde")]
+pub(crate) struct ExecutionCache {
+    /// The path of the cache file
+    #[serde(skip)]
+    path: PathBuf,
+
+    /// Entries in the cache, keyed by a hash of the code, language,
+    /// and input variables of each chunk
+    entries: HashMap<String, Vec<Node>>,
+}
+
+impl ExecutionCache {
+    /// Read the cache, if any, in a workspace directory
+    pub fn read(dir: &Path) -> Self {
+        let path = dir.join(".stencila").join("execution-cache.json");
+
+        let mut cache: Self = read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        cache.path = path;
+
+        cache
+    }
+
+    /// Calculate the cache key for a chunk
+    ///
+    /// The key is a hash of the code, the language, and the hints of the
+    /// variables read by the code, so that a chunk is re-executed, rather
+    /// than restored, when any of its inputs change.
+    pub fn key(code: &str, language: &str, inputs: &[(String, Option<Hint>)]) -> String {
+        let mut hash = SeaHasher::new();
+        code.hash(&mut hash);
+        language.hash(&mut hash);
+        for (name, hint) in inputs {
+            name.hash(&mut hash);
+            serde_json::to_string(hint)
+                .unwrap_or_default()
+                .hash(&mut hash);
+        }
+        format!("{:x}", hash.finish())
+    }
+
+    /// Get the outputs for a key
+    pub fn get(&self, key: &str) -> Option<Vec<Node>> {
+        self.entries.get(key).cloned()
+    }
+
+    /// Insert the outputs for a key and write the cache to disk
+    pub fn insert(&mut self, key: &str, outputs: &[Node]) {
+        self.entries.insert(key.to_string(), outputs.to_vec());
+
+        if let Some(dir) = self.path.parent() {
+            if let Err(error) = create_dir_all(dir).and_then(|()| {
+                write(
+                    &self.path,
+                    serde_json::to_string(self).unwrap_or_default(),
+                )
+            }) {
+                tracing::warn!("While writing execution cache: {error}");
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use codec_text_trait::to_text;
+use schema::{shortcuts::t, CitationMode, Cite, CiteGroup, NodeProperty};
+
+use crate::{prelude::*, CitationStyle};
+
+impl Executable for Cite {
+    #[tracing::instrument(skip_all)]
+    async fn compile(&mut self, executor: &mut Executor) -> WalkControl {
+        let node_id = self.node_id();
+        tracing::trace!("Compiling Cite {node_id}");
+
+        let Some(text) = citation_text(executor, self, true) else {
+            return WalkControl::Continue;
+        };
+
+        // Only set the content if it differs, so that patches are not sent
+        // unnecessarily
+        if to_text(&self.options.content).trim() != text {
+            let content = vec![t(text)];
+            self.options.content = Some(content.clone());
+            executor.patch(&node_id, [set(NodeProperty::Content, content)]);
+        }
+
+        WalkControl::Continue
+    }
+}
+
+impl Executable for CiteGroup {
+    #[tracing::instrument(skip_all)]
+    async fn compile(&mut self, executor: &mut Executor) -> WalkControl {
+        let node_id = self.node_id();
+        tracing::trace!("Compiling CiteGroup {node_id}");
+
+        // A `CiteGroup` has no content of its own so render each item without
+        // enclosing brackets and add group level punctuation, according to the
+        // citation style, to the content of the items
+        let (open, separator, close) = match executor.citation_style {
+            CitationStyle::AuthorDate => ("(", "; ", ")"),
+            CitationStyle::Numeric => ("[", ",", "]"),
+        };
+
+        let last = self.items.len().saturating_sub(1);
+        for (index, cite) in self.items.iter_mut().enumerate() {
+            let Some(text) = citation_text(executor, cite, false) else {
+                continue;
+            };
+
+            let text = [
+                if index == 0 { open } else { "" },
+                &text,
+                if index == last { close } else { separator },
+            ]
+            .concat();
+
+            if to_text(&cite.options.content) != text {
+                let content = vec![t(text)];
+                cite.options.content = Some(content.clone());
+                executor.patch(&cite.node_id(), [set(NodeProperty::Content, content)]);
+            }
+        }
+
+        // Break walk because items have already been rendered
+        WalkControl::Break
+    }
+}
+
+/// Render the text of a citation according to the executor's citation style
+///
+/// Returns `None` if the citation's target is not amongst the references of
+/// the root node (in which case the content is left unchanged).
+fn citation_text(executor: &mut Executor, cite: &Cite, enclose: bool) -> Option<String> {
+    let (index, author_date) = executor.references.get(&cite.target)?.clone();
+
+    let text = match executor.citation_style {
+        CitationStyle::AuthorDate => match cite.citation_mode {
+            // e.g. Smith (2023)
+            CitationMode::Narrative => {
+                if let Some((author, date)) = author_date.rsplit_once(", ") {
+                    format!("{author} ({date})")
+                } else {
+                    author_date.clone()
+                }
+            }
+            // e.g. (Smith, 2023)
+            _ => {
+                if enclose {
+                    ["(", &author_date, ")"].concat()
+                } else {
+                    author_date.clone()
+                }
+            }
+        },
+        CitationStyle::Numeric => {
+            let index = executor.citation_index(&cite.target, index);
+            if enclose {
+                format!("[{index}]")
+            } else {
+                index.to_string()
+            }
+        }
+    };
+
+    let prefix = cite.options.citation_prefix.as_deref().unwrap_or_default();
+    let suffix = cite.options.citation_suffix.as_deref().unwrap_or_default();
+
+    Some([prefix, &text, suffix].concat())
+}
@@ -1,4 +1,6 @@
-use schema::{CodeChunk, ExecutionKind, LabelType, NodeProperty};
+use codecs::Format;
+use common::tokio::{self, sync::mpsc};
+use schema::{CodeChunk, ExecutionKind, LabelType, NodeProperty, NodeType, Patch, PatchPath};
 
 use crate::{interrupt_impl, prelude::*};
 
@@ -9,18 +11,31 @@ impl Executable for CodeChunk {
         tracing::trace!("Compiling CodeChunk {node_id}");
 
         if let Some(label_type) = &self.label_type {
-            let label = match label_type {
+            let (label, label_node_type) = match label_type {
                 LabelType::FigureLabel => {
                     executor.figure_count += 1;
-                    executor.figure_count.to_string()
+                    (executor.node_label(executor.figure_count), NodeType::Figure)
                 }
                 LabelType::TableLabel => {
                     executor.table_count += 1;
-                    executor.table_count.to_string()
+                    (executor.node_label(executor.table_count), NodeType::Table)
                 }
             };
-            if self.label_automatically.unwrap_or(true) && Some(&label) != self.label.as_ref() {
-                executor.patch(&node_id, [set(NodeProperty::Label, label)]);
+            let label = if self.label_automatically.unwrap_or(true) {
+                if Some(&label) != self.label.as_ref() {
+                    executor.patch(&node_id, [set(NodeProperty::Label, label.clone())]);
+                }
+                Some(label)
+            } else {
+                self.label.clone()
+            };
+            if let Some(label) = label {
+                executor.register_label(
+                    self.id.clone().unwrap_or_else(|| node_id.to_string()),
+                    &node_id,
+                    label_node_type,
+                    label,
+                );
             }
         }
 
@@ -56,6 +71,34 @@ impl Executable for CodeChunk {
             ],
         );
 
+        // Honour an `@include` tag (equivalent to knitr's `include=FALSE`)
+        // by setting the visibility of the chunk and its outputs
+        if let Some(include) = execution_tag_bool(&self.options.execution_tags, "include") {
+            let is_invisible = !include;
+            if self.is_invisible != Some(is_invisible) {
+                self.is_invisible = Some(is_invisible);
+                executor.patch(&node_id, [set(NodeProperty::IsInvisible, is_invisible)]);
+            }
+        }
+
+        // Lint the code using any linters available for the language
+        if !self.code.trim().is_empty() {
+            let directory = executor
+                .directory_stack
+                .last()
+                .cloned()
+                .unwrap_or_default();
+            let messages = kernels::list()
+                .await
+                .iter()
+                .find(|kernel| kernel.supports_language(&Format::from_name(lang)))
+                .and_then(|kernel| kernel.lint(&self.code, &directory).ok())
+                .unwrap_or_default();
+
+            let messages = (!messages.is_empty()).then_some(messages);
+            executor.patch(&node_id, [set(NodeProperty::CompilationMessages, messages)]);
+        }
+
         // Some code chunks should be executed during "compile" phase to
         // enable live updates (e.g. Graphviz, Mermaid)
         // TODO: consider having a way to specify which code chunks and/or
@@ -84,14 +127,39 @@ impl Executable for CodeChunk {
         // Add code chunk to document context
         executor.document_context.code_chunks.push((&*self).into());
 
-        // Set execution status
-        if let Some(status) = executor.node_execution_status(
+        let info = parsers::parse(
+            &self.code,
+            self.programming_language.as_deref().unwrap_or_default(),
+        );
+
+        // Set execution status, also marking the chunk as pending if it reads
+        // any variable written by an upstream node that is pending
+        let mut status = executor.node_execution_status(
             self.node_type(),
             &node_id,
             &self.execution_mode,
             &self.options.compilation_digest,
             &self.options.execution_digest,
+        );
+        if status.is_none()
+            && !self.code.trim().is_empty()
+            && executor.reads_stale_variables(&info.variables_read)
+        {
+            status = Some(ExecutionStatus::Pending);
+        }
+
+        // Honour an `@eval false` tag (equivalent to knitr's `eval=FALSE`)
+        // by skipping execution of the chunk
+        if matches!(
+            execution_tag_bool(&self.options.execution_tags, "eval"),
+            Some(false)
         ) {
+            status = Some(ExecutionStatus::Skipped);
+        }
+        if matches!(status, Some(ExecutionStatus::Pending)) {
+            executor.will_write_variables(info.variables_written);
+        }
+        if let Some(status) = status {
             self.options.execution_status = Some(status.clone());
             executor.patch(&node_id, [set(NodeProperty::ExecutionStatus, status)]);
         }
@@ -119,6 +187,13 @@ impl Executable for CodeChunk {
             return WalkControl::Break;
         }
 
+        // Skip the chunk if the wall-clock execution budget has been exceeded
+        if executor.execution_budget_exceeded(&node_id) {
+            executor.document_context.code_chunks.exit();
+
+            return WalkControl::Break;
+        }
+
         tracing::debug!("Executing CodeChunk {node_id}");
 
         executor.patch(
@@ -126,6 +201,7 @@ impl Executable for CodeChunk {
             [
                 set(NodeProperty::ExecutionStatus, ExecutionStatus::Running),
                 none(NodeProperty::ExecutionMessages),
+                none(NodeProperty::Outputs),
             ],
         );
 
@@ -134,10 +210,93 @@ impl Executable for CodeChunk {
         if !self.code.trim().is_empty() {
             let started = Timestamp::now();
 
+            let timeout = execution_timeout(&self.options.execution_tags, executor);
+
+            // Attempt to restore outputs from the execution cache rather than
+            // executing the code. Only chunks that do not write variables are
+            // cached, since restoring does not recreate variables in the kernel.
+            let lang = self.programming_language.as_deref().unwrap_or_default();
+            let parse_info = parsers::parse(&self.code, lang);
+            let cache_key = if parse_info.variables_written.is_empty() {
+                executor
+                    .execution_cache_key(&self.code, lang, &parse_info.variables_read)
+                    .await
+            } else {
+                None
+            };
+            if let Some(key) = &cache_key {
+                if let Some(outputs) = executor.execution_cache_get(key).await {
+                    tracing::debug!("Restoring outputs of CodeChunk {node_id} from cache");
+
+                    let ended = Timestamp::now();
+                    executor
+                        .profile_span(self.node_type(), &node_id, &started, &ended)
+                        .await;
+                    let duration = execution_duration(&started, &ended);
+                    let count = self.options.execution_count.unwrap_or_default() + 1;
+
+                    let outputs = (!outputs.is_empty()).then_some(outputs);
+                    self.outputs = outputs.clone();
+                    self.options.execution_messages = None;
+
+                    executor.patch(
+                        &node_id,
+                        [
+                            set(NodeProperty::Outputs, outputs),
+                            set(NodeProperty::ExecutionStatus, ExecutionStatus::Succeeded),
+                            set(NodeProperty::ExecutionRequired, ExecutionRequired::No),
+                            none(NodeProperty::ExecutionMessages),
+                            set(NodeProperty::ExecutionDuration, duration),
+                            set(NodeProperty::ExecutionEnded, ended),
+                            set(NodeProperty::ExecutionCount, count),
+                            set(NodeProperty::ExecutionDigest, compilation_digest),
+                        ],
+                    );
+
+                    // Exit the code chunk context
+                    executor.document_context.code_chunks.exit();
+
+                    return WalkControl::Break;
+                }
+            }
+
+            // Run any hooks registered to run before each code chunk
+            executor.run_hooks_before_chunk().await;
+
+            // Forward outputs to the document as they are produced so that
+            // long-running chunks show outputs incrementally
+            let (sender, mut receiver) = mpsc::unbounded_channel();
+            let patch_sender = executor.patch_sender.clone();
+            let patch_node_id = node_id.clone();
+            let forwarder = tokio::spawn(async move {
+                while let Some(output) = receiver.recv().await {
+                    let Some(patch_sender) = &patch_sender else {
+                        continue;
+                    };
+
+                    let patch = Patch {
+                        node_id: Some(patch_node_id.clone()),
+                        ops: vec![(
+                            PatchPath::from(NodeProperty::Outputs),
+                            PatchOp::Push(output.to_value().unwrap_or_default()),
+                        )],
+                        ..Default::default()
+                    };
+                    if let Err(error) = patch_sender.send(patch) {
+                        tracing::error!("When sending output patch: {error}");
+                    }
+                }
+            });
+
             let (outputs, messages, instance) = executor
                 .kernels()
                 .await
-                .execute(&self.code, self.programming_language.as_deref())
+                .execute_stream(
+                    &self.code,
+                    self.programming_language.as_deref(),
+                    timeout,
+                    sender,
+                )
                 .await
                 .unwrap_or_else(|error| {
                     (
@@ -147,10 +306,35 @@ impl Executable for CodeChunk {
                     )
                 });
 
+            forwarder.await.ok();
+
+            // Run any hooks registered to run after each code chunk
+            executor.run_hooks_after_chunk().await;
+
             let outputs = (!outputs.is_empty()).then_some(outputs);
-            let messages = (!messages.is_empty()).then_some(messages);
+            let mut messages = (!messages.is_empty()).then_some(messages);
+
+            // Honour an `@error true` tag (equivalent to knitr's `error=TRUE`)
+            // by demoting error messages to warnings so that the chunk is not
+            // treated as failed
+            if matches!(
+                execution_tag_bool(&self.options.execution_tags, "error"),
+                Some(true)
+            ) {
+                for message in messages.iter_mut().flatten() {
+                    if matches!(
+                        message.level,
+                        MessageLevel::Error | MessageLevel::Exception
+                    ) {
+                        message.level = MessageLevel::Warning;
+                    }
+                }
+            }
 
             let ended = Timestamp::now();
+            executor
+                .profile_span(self.node_type(), &node_id, &started, &ended)
+                .await;
 
             let status = execution_status(&messages);
             let kind = execution_kind(executor);
@@ -158,6 +342,14 @@ impl Executable for CodeChunk {
             let duration = execution_duration(&started, &ended);
             let count = self.options.execution_count.unwrap_or_default() + 1;
 
+            // Update the execution cache with the new outputs if execution
+            // succeeded without messages
+            if let (Some(key), None) = (&cache_key, &messages) {
+                executor
+                    .execution_cache_insert(key, outputs.as_deref().unwrap_or_default())
+                    .await;
+            }
+
             // Set properties that may be using in rendering
             self.outputs = outputs.clone();
             self.options.execution_messages = messages.clone();
@@ -216,6 +408,28 @@ impl Executable for CodeChunk {
         let node_id = self.node_id();
         tracing::debug!("Interrupting CodeChunk {node_id}");
 
+        // If the chunk is currently running, send the interrupt signal to the
+        // kernel instance executing it so that only its task is cancelled and
+        // the kernel's state is left intact
+        if executor.node_should_interrupt(&node_id)
+            && matches!(
+                self.options.execution_status,
+                Some(ExecutionStatus::Running)
+            )
+        {
+            if let Err(error) = executor
+                .kernels()
+                .await
+                .interrupt_instance(
+                    self.options.execution_instance.as_deref(),
+                    self.programming_language.as_deref(),
+                )
+                .await
+            {
+                tracing::warn!("While interrupting kernel instance: {error}");
+            }
+        }
+
         interrupt_impl!(self, executor, &node_id);
 
         WalkControl::Break
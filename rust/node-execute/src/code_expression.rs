@@ -31,14 +31,25 @@ impl Executable for CodeExpression {
         let node_id = self.node_id();
         tracing::trace!("Preparing CodeExpression {node_id}");
 
-        // Set execution status
-        if let Some(status) = executor.node_execution_status(
+        // Set execution status, also marking the expression as pending if it
+        // reads any variable written by an upstream node that is pending
+        let mut status = executor.node_execution_status(
             self.node_type(),
             &node_id,
             &self.execution_mode.clone().or(Some(ExecutionMode::Always)),
             &self.options.compilation_digest,
             &self.options.execution_digest,
-        ) {
+        );
+        if status.is_none() && !self.code.trim().is_empty() {
+            let info = parsers::parse(
+                &self.code,
+                self.programming_language.as_deref().unwrap_or_default(),
+            );
+            if executor.reads_stale_variables(&info.variables_read) {
+                status = Some(ExecutionStatus::Pending);
+            }
+        }
+        if let Some(status) = status {
             self.options.execution_status = Some(status.clone());
             executor.patch(&node_id, [set(NodeProperty::ExecutionStatus, status)]);
         }
@@ -59,6 +70,11 @@ impl Executable for CodeExpression {
             return WalkControl::Break;
         }
 
+        // Skip the expression if the wall-clock execution budget has been exceeded
+        if executor.execution_budget_exceeded(&node_id) {
+            return WalkControl::Break;
+        }
+
         tracing::debug!("Executing CodeExpression {node_id}");
 
         executor.patch(
@@ -74,11 +90,13 @@ impl Executable for CodeExpression {
         if !self.code.trim().is_empty() {
             let started = Timestamp::now();
 
+            let timeout = execution_timeout(&self.options.execution_tags, executor);
+
             let (output, messages, instance) = executor
                 .kernels
                 .write()
                 .await
-                .evaluate(&self.code, self.programming_language.as_deref())
+                .evaluate_with_timeout(&self.code, self.programming_language.as_deref(), timeout)
                 .await
                 .unwrap_or_else(|error| {
                     (
@@ -94,6 +112,9 @@ impl Executable for CodeExpression {
             let messages = (!messages.is_empty()).then_some(messages);
 
             let ended = Timestamp::now();
+            executor
+                .profile_span(self.node_type(), &node_id, &started, &ended)
+                .await;
 
             let status = execution_status(&messages);
             let required = execution_required_status(&status);
@@ -10,11 +10,22 @@ impl Executable for Figure {
 
         executor.figure_count += 1;
 
-        if self.label_automatically.unwrap_or(true) {
-            let label = executor.figure_count.to_string();
+        let label = if self.label_automatically.unwrap_or(true) {
+            let label = executor.node_label(executor.figure_count);
             if Some(&label) != self.label.as_ref() {
-                executor.patch(&node_id, [set(NodeProperty::Label, label)]);
+                executor.patch(&node_id, [set(NodeProperty::Label, label.clone())]);
             }
+            Some(label)
+        } else {
+            self.label.clone()
+        };
+        if let Some(label) = label {
+            executor.register_label(
+                self.id.clone().unwrap_or_else(|| node_id.to_string()),
+                &node_id,
+                self.node_type(),
+                label,
+            );
         }
 
         WalkControl::Continue
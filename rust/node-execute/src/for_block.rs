@@ -51,14 +51,35 @@ impl Executable for ForBlock {
         let node_id = self.node_id();
         tracing::trace!("Preparing ForBlock {node_id}");
 
-        // Set execution status
-        if let Some(status) = executor.node_execution_status(
+        let info = parsers::parse(
+            &self.code,
+            self.programming_language.as_deref().unwrap_or_default(),
+        );
+
+        // Set execution status, also marking the block as pending if its code
+        // reads any variable written by an upstream node that is pending
+        let mut status = executor.node_execution_status(
             self.node_type(),
             &node_id,
             &self.execution_mode,
             &self.options.compilation_digest,
             &self.options.execution_digest,
-        ) {
+        );
+        if status.is_none()
+            && !self.code.trim().is_empty()
+            && executor.reads_stale_variables(&info.variables_read)
+        {
+            status = Some(ExecutionStatus::Pending);
+        }
+        if matches!(status, Some(ExecutionStatus::Pending)) {
+            // The loop variable, and any variables written by the code, may change
+            executor.will_write_variables(
+                [self.variable.trim().to_string()]
+                    .into_iter()
+                    .chain(info.variables_written),
+            );
+        }
+        if let Some(status) = status {
             self.options.execution_status = Some(status.clone());
             executor.patch(&node_id, [set(NodeProperty::ExecutionStatus, status)]);
         }
@@ -79,6 +100,11 @@ impl Executable for ForBlock {
             return WalkControl::Break;
         }
 
+        // Skip the block if the wall-clock execution budget has been exceeded
+        if executor.execution_budget_exceeded(&node_id) {
+            return WalkControl::Break;
+        }
+
         tracing::debug!("Executing ForBlock {node_id}");
 
         executor.patch(
@@ -173,8 +199,16 @@ impl Executable for ForBlock {
             };
             executor.patch(&node_id, [reset]);
 
+            // Limit the number of iterations rendered into the document so that
+            // large iterables do not materialize every iteration's blocks at once
+            let total = iterator.len();
+            let limit = executor
+                .options
+                .max_iterations
+                .map_or(usize::MAX, |max| max as usize);
+
             // Iterate over iterable, and iterations, setting the variable and executing each iteration.
-            for node in iterator.iter() {
+            for node in iterator.iter().take(limit) {
                 has_iterations = true;
 
                 // Replicate the content, rather than clone it so it has different
@@ -216,6 +250,17 @@ impl Executable for ForBlock {
                 iterations.push(iteration)
             }
 
+            // Warn if any iterations were not rendered due to the limit
+            if total > limit {
+                messages.push(ExecutionMessage {
+                    level: MessageLevel::Warning,
+                    message: format!(
+                        "Rendered only the first {limit} of {total} iterations; use `max_iterations` to render more"
+                    ),
+                    ..Default::default()
+                });
+            }
+
             // Remove the loop's variable (if it was set)
             if has_iterations {
                 if let Err(error) = executor.kernels.write().await.remove(&self.variable).await {
@@ -240,6 +285,9 @@ impl Executable for ForBlock {
         }
 
         let ended = Timestamp::now();
+        executor
+            .profile_span(self.node_type(), &node_id, &started, &ended)
+            .await;
 
         let messages = (!messages.is_empty()).then_some(messages);
 
@@ -1,3 +1,4 @@
+use codec_text_trait::to_text;
 use schema::Heading;
 
 use crate::{prelude::*, HeadingInfo};
@@ -5,6 +6,26 @@ use crate::{prelude::*, HeadingInfo};
 impl Executable for Heading {
     #[tracing::instrument(skip_all)]
     async fn compile(&mut self, executor: &mut Executor) -> WalkControl {
+        // Track top level sections, and appendices, for per-section numbering,
+        // resetting counts at the start of each
+        if self.level <= 1 {
+            executor.section_count += 1;
+            let is_appendix = to_text(&self.content)
+                .trim()
+                .to_lowercase()
+                .starts_with(&executor.appendix_prefix().to_lowercase());
+            if is_appendix {
+                executor.appendix_count += 1;
+            }
+            if executor.numbering_per_section()
+                || (is_appendix && executor.numbering_appendix_reset())
+            {
+                executor.table_count = 0;
+                executor.figure_count = 0;
+                executor.equation_count = 0;
+            }
+        }
+
         // If necessary, collapse previous headings into their parents
         HeadingInfo::collapse(self.level, &mut executor.headings);
 
@@ -44,14 +44,31 @@ impl Executable for IfBlock {
         let node_id = self.node_id();
         tracing::trace!("Preparing IfBlock {node_id}");
 
-        // Set execution status
-        if let Some(status) = executor.node_execution_status(
+        // Set execution status, also marking the block as pending if the
+        // condition of any clause reads a variable written by an upstream
+        // node that is pending
+        let mut status = executor.node_execution_status(
             self.node_type(),
             &node_id,
             &self.execution_mode,
             &self.options.compilation_digest,
             &self.options.execution_digest,
-        ) {
+        );
+        if status.is_none() {
+            for clause in &self.clauses {
+                let info = parsers::parse(
+                    &clause.code,
+                    clause.programming_language.as_deref().unwrap_or_default(),
+                );
+                if !clause.code.trim().is_empty()
+                    && executor.reads_stale_variables(&info.variables_read)
+                {
+                    status = Some(ExecutionStatus::Pending);
+                    break;
+                }
+            }
+        }
+        if let Some(status) = status {
             self.options.execution_status = Some(status.clone());
             executor.patch(&node_id, [set(NodeProperty::ExecutionStatus, status)]);
         }
@@ -73,6 +90,11 @@ impl Executable for IfBlock {
             return WalkControl::Break;
         }
 
+        // Skip the block if the wall-clock execution budget has been exceeded
+        if executor.execution_budget_exceeded(&node_id) {
+            return WalkControl::Break;
+        }
+
         tracing::debug!("Executing IfBlock {node_id}");
 
         executor.patch(
@@ -88,18 +110,11 @@ impl Executable for IfBlock {
         if !self.clauses.is_empty() {
             let started = Timestamp::now();
 
-            // Explicitly re-set all clauses to inactive so it is possible to shortcut
-            // evaluation by breaking on the first truthy clause. Because of the short
-            // cutting a patch needs to be sent here too
-            for clause in self.clauses.iter_mut() {
-                clause.is_active = Some(false);
-                executor.patch(&clause.node_id(), [set(NodeProperty::IsActive, false)]);
-            }
-
             // Iterate over clauses breaking on the first that is active
             // and determine execution status based on highest status of executed clauses
             let mut status = ExecutionStatus::Succeeded;
             let last_index = self.clauses.len() - 1;
+            let mut active_index = None;
             for (index, clause) in self.clauses.iter_mut().enumerate() {
                 executor.is_last = index == last_index;
 
@@ -116,11 +131,40 @@ impl Executable for IfBlock {
                 }
 
                 if clause.is_active.unwrap_or_default() {
+                    active_index = Some(index);
                     break;
                 }
             }
 
+            // Re-set any clauses after the active clause to inactive. This is done
+            // after, rather than before, evaluation so that unchanged clauses are
+            // able to reuse the result of their previous evaluation (issue #2562).
+            // The execution digest of clauses that were previously active is
+            // invalidated so that they are re-evaluated, rather than reusing the
+            // re-set `is_active`, next time.
+            if let Some(active_index) = active_index {
+                for clause in self.clauses.iter_mut().skip(active_index + 1) {
+                    let clause_id = clause.node_id();
+                    if clause.is_active.unwrap_or_default() {
+                        clause.options.execution_digest = None;
+                        executor.patch(
+                            &clause_id,
+                            [
+                                set(NodeProperty::IsActive, false),
+                                none(NodeProperty::ExecutionDigest),
+                            ],
+                        );
+                    } else {
+                        executor.patch(&clause_id, [set(NodeProperty::IsActive, false)]);
+                    }
+                    clause.is_active = Some(false);
+                }
+            }
+
             let ended = Timestamp::now();
+            executor
+                .profile_span(self.node_type(), &node_id, &started, &ended)
+                .await;
 
             let required = execution_required_status(&status);
             let duration = execution_duration(&started, &ended);
@@ -224,7 +268,37 @@ impl Executable for IfBlockClause {
         let started = Timestamp::now();
 
         let is_empty = self.code.trim().is_empty();
-        let (is_active, mut status) = if !is_empty {
+
+        // If the clause's condition, and the variables it reads, are unchanged
+        // since it was last evaluated then reuse the previous result rather than
+        // re-evaluating it, so that only stale nodes in `content` are re-executed
+        // (issue #2562)
+        let info = parsers::parse(
+            &self.code,
+            self.programming_language.as_deref().unwrap_or_default(),
+        );
+        let unchanged = !executor.options.force_all
+            && !is_empty
+            && self.options.compilation_digest.is_some()
+            && self.options.compilation_digest == self.options.execution_digest
+            && !executor.reads_stale_variables(&info.variables_read);
+
+        let (is_active, mut status) = if unchanged {
+            let truthy = self.is_active.unwrap_or_default();
+
+            // Execute any stale nodes in `content` if the clause is still active
+            if truthy {
+                tracing::trace!("Executing if clause content");
+                if let Err(error) = executor.compile_prepare_execute(&mut self.content).await {
+                    messages.push(error_to_execution_message(
+                        "While executing if clause content",
+                        error,
+                    ))
+                };
+            }
+
+            (truthy, ExecutionStatus::Running)
+        } else if !is_empty {
             // Evaluate code in kernels
             let (output, mut code_messages, ..) = executor
                 .kernels
@@ -285,6 +359,9 @@ impl Executable for IfBlockClause {
         let messages = (!messages.is_empty()).then_some(messages);
 
         let ended = Timestamp::now();
+        executor
+            .profile_span(self.node_type(), &node_id, &started, &ended)
+            .await;
 
         if status != ExecutionStatus::Skipped {
             status = execution_status(&messages)
@@ -90,6 +90,11 @@ impl Executable for IncludeBlock {
             return WalkControl::Break;
         }
 
+        // Skip the block if the wall-clock execution budget has been exceeded
+        if executor.execution_budget_exceeded(&node_id) {
+            return WalkControl::Break;
+        }
+
         tracing::debug!("Executing IncludeBlock {node_id}: {}", self.source);
 
         executor.patch(
@@ -113,6 +118,9 @@ impl Executable for IncludeBlock {
             let messages = (!messages.is_empty()).then_some(messages);
 
             let ended = Timestamp::now();
+            executor
+                .profile_span(self.node_type(), &node_id, &started, &ended)
+                .await;
 
             let status = execution_status(&messages);
             let required = execution_required_status(&status);
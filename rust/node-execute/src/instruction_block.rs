@@ -1,16 +1,19 @@
-use std::ops::Deref;
+use std::{ops::Deref, sync::Arc};
 
 use codec_cbor::r#trait::CborCodec;
 use codec_markdown_trait::{MarkdownCodec, MarkdownEncodeContext};
-use codecs::Format;
+use codecs::{DecodeOptions, Format};
 use common::{
     futures::stream::{FuturesUnordered, StreamExt},
     itertools::Itertools,
-    tokio,
+    tokio::{
+        self,
+        sync::{mpsc, Mutex},
+    },
 };
 use schema::{
-    Author, AuthorRole, AuthorRoleAuthor, AuthorRoleName, CompilationDigest, InstructionBlock,
-    InstructionModel, PromptBlock, SoftwareApplication,
+    Article, Author, AuthorRole, AuthorRoleAuthor, AuthorRoleName, CompilationDigest,
+    InstructionBlock, InstructionModel, PromptBlock, SoftwareApplication, SuggestionBlock,
 };
 
 use crate::{interrupt_impl, prelude::*};
@@ -132,6 +135,11 @@ impl Executable for InstructionBlock {
             return WalkControl::Continue;
         }
 
+        // Skip the instruction if the wall-clock execution budget has been exceeded
+        if executor.execution_budget_exceeded(&node_id) {
+            return WalkControl::Break;
+        }
+
         tracing::debug!("Executing InstructionBlock {node_id}");
 
         executor.patch(
@@ -224,6 +232,63 @@ impl Executable for InstructionBlock {
             executor.patch(&node_id, [none(NodeProperty::Suggestions)]);
         }
 
+        // If there is a single replicate, stream the generated content into a
+        // placeholder suggestion so that the user sees it appear progressively
+        // (the placeholder is replaced with the completed suggestion at the end)
+        let (content_sender, placeholder_index) = if replicates == 1 && !executor.options.dry_run {
+            let placeholder_index = if executor.options.retain_suggestions {
+                self.suggestions.iter().flatten().count()
+            } else {
+                0
+            };
+
+            let placeholder = SuggestionBlock::new(Vec::new());
+            let placeholder_id = placeholder.node_id();
+            executor.patch(&node_id, [push(NodeProperty::Suggestions, placeholder)]);
+
+            let (sender, mut receiver) = mpsc::unbounded_channel::<String>();
+            let patcher = executor.fork_for_all();
+            tokio::spawn(async move {
+                while let Some(mut content) = receiver.recv().await {
+                    // Use only the most recent content if several have arrived
+                    while let Ok(more) = receiver.try_recv() {
+                        content = more;
+                    }
+
+                    // Decode the partial content and patch it into the placeholder
+                    if let Ok(Node::Article(Article { content, .. })) = codecs::from_str(
+                        &content,
+                        Some(DecodeOptions {
+                            format: Some(Format::Markdown),
+                            ..Default::default()
+                        }),
+                    )
+                    .await
+                    {
+                        patcher.patch(&placeholder_id, [set(NodeProperty::Content, content)]);
+                    }
+                }
+            });
+
+            (Some(sender), Some(placeholder_index))
+        } else {
+            (None, None)
+        };
+
+        // Fork the kernels so that the model can execute code snippets
+        // (e.g. to inspect a dataframe) without affecting the document's kernels
+        let kernels = if executor.kernels().await.supports_forks().await {
+            match executor.kernels().await.fork().await {
+                Ok(kernels) => Some(Arc::new(Mutex::new(kernels))),
+                Err(error) => {
+                    tracing::debug!("Unable to fork kernels for instruction: {error}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         // Create a future for each replicate
         let mut futures = FuturesUnordered::new();
         for _ in 0..replicates {
@@ -233,6 +298,9 @@ impl Executable for InstructionBlock {
             let instructors = instructors.clone();
             let prompter = prompter.clone();
             let system_prompt = system_prompt.to_string();
+            let expected_node_types = prompt.node_types.clone();
+            let kernels = kernels.clone();
+            let content_sender = content_sender.clone();
             let mut instruction = self.clone();
             let dry_run = executor.options.dry_run;
             if let Some(id_pattern) = model_id_pattern.clone() {
@@ -254,6 +322,9 @@ impl Executable for InstructionBlock {
                     instructors,
                     prompter,
                     &system_prompt,
+                    &expected_node_types,
+                    kernels,
+                    content_sender,
                     &instruction,
                     dry_run,
                 )
@@ -268,10 +339,18 @@ impl Executable for InstructionBlock {
         while let Some(result) = futures.next().await {
             match result {
                 Ok(mut suggestion) => {
-                    executor.patch(
-                        &node_id,
-                        [push(NodeProperty::Suggestions, suggestion.clone())],
-                    );
+                    if let Some(index) = placeholder_index {
+                        // Replace the placeholder suggestion with the completed suggestion
+                        executor.patch(
+                            &node_id,
+                            [replace(NodeProperty::Suggestions, index, suggestion.clone())],
+                        );
+                    } else {
+                        executor.patch(
+                            &node_id,
+                            [push(NodeProperty::Suggestions, suggestion.clone())],
+                        );
+                    }
 
                     if run {
                         let mut fork = executor.fork_for_all();
@@ -283,16 +362,30 @@ impl Executable for InstructionBlock {
                         });
                     }
                 }
-                Err(error) => messages.push(error_to_execution_message(
-                    "While executing instruction",
-                    error,
-                )),
+                Err(error) => {
+                    // Remove any placeholder suggestion
+                    if let Some(index) = placeholder_index {
+                        executor.patch(
+                            &node_id,
+                            [(NodeProperty::Suggestions, PatchOp::Remove(vec![index]))],
+                        );
+                    }
+
+                    messages.push(error_to_execution_message(
+                        "While executing instruction",
+                        error,
+                    ))
+                }
             }
         }
 
         let messages = (!messages.is_empty()).then_some(messages);
 
         let ended = Timestamp::now();
+        executor
+            .profile_span(self.node_type(), &node_id, &started, &ended)
+            .await;
+
         let status = execution_status(&messages);
         let required = execution_required_status(&status);
         let duration = execution_duration(&started, &ended);
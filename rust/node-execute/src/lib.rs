@@ -1,21 +1,31 @@
 #![recursion_limit = "256"]
 
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use common::{
     clap::{self, Args},
-    eyre::Result,
+    eyre::{bail, Result},
     itertools::Itertools,
     serde::{Deserialize, Serialize},
-    tokio::sync::{mpsc::UnboundedSender, RwLock, RwLockWriteGuard},
+    serde_json,
+    tokio::{
+        process::Command,
+        sync::{mpsc::UnboundedSender, RwLock, RwLockWriteGuard},
+    },
     tracing,
 };
 use kernels::Kernels;
 use prompts::prompt::{DocumentContext, InstructionContext};
 use schema::{
-    AuthorRole, AuthorRoleName, Block, CompilationDigest, ExecutionKind, ExecutionMode,
-    ExecutionStatus, Inline, Link, List, ListItem, ListOrder, Node, NodeId, NodeProperty, NodeType,
-    Paragraph, Patch, PatchOp, PatchPath, Timestamp, VisitorAsync, WalkControl, WalkNode,
+    shortcuts::t, AuthorRole, AuthorRoleName, Block, CompilationDigest, ExecutionKind,
+    ExecutionMessage, ExecutionMode, ExecutionStatus, Inline, Link, List, ListItem, ListOrder,
+    MessageLevel, Node, NodeId, NodeProperty, NodeType, Paragraph, Patch, PatchOp, PatchPath,
+    Strong, Timestamp, VisitorAsync, WalkControl, WalkNode,
 };
 
 type NodeIds = Vec<NodeId>;
@@ -23,7 +33,9 @@ type NodeIds = Vec<NodeId>;
 mod prelude;
 
 mod article;
+mod cache;
 mod call_block;
+mod cite;
 mod code_chunk;
 mod code_expression;
 mod figure;
@@ -33,17 +45,25 @@ mod if_block;
 mod include_block;
 mod instruction_block;
 mod instruction_inline;
+mod link;
 mod math_block;
 mod math_inline;
 mod paragraph;
 mod parameter;
+mod plan;
+mod profile;
 mod prompt_block;
 mod raw_block;
 mod section;
 mod styled_block;
 mod styled_inline;
 mod suggestion_block;
+mod summary;
 mod table;
+mod upstream;
+
+pub use plan::{ExecutionPlan, ExecutionReason, ExecutionStep};
+pub use summary::{ExecutionSummary, NodeSummary};
 
 /// Walk over a root node and compile it and child nodes
 pub async fn compile(
@@ -59,6 +79,34 @@ pub async fn compile(
     executor.compile(&mut root).await
 }
 
+/// Walk over a root node and plan which nodes would be executed
+///
+/// Runs the prepare phase on a clone of the root node, without a patch
+/// sender, so has no side effects on the document or its kernels. The
+/// returned plan lists the nodes that would be executed, in document order,
+/// with the reason that each would be executed.
+pub async fn plan(
+    home: PathBuf,
+    root: Arc<RwLock<Node>>,
+    kernels: Arc<RwLock<Kernels>>,
+    node_ids: Option<NodeIds>,
+    options: Option<ExecuteOptions>,
+) -> Result<ExecutionPlan> {
+    let mut root = root.read().await.clone();
+    let mut executor = Executor::new(home, kernels, None, node_ids, options);
+    executor.prepare(&mut root).await?;
+    Ok(executor.plan(&root))
+}
+
+/// Walk over a root node and summarize the execution status of its nodes
+///
+/// Should be called after the root node has been executed. See the
+/// [`summary`](mod@summary) module.
+pub async fn summary(root: Arc<RwLock<Node>>) -> ExecutionSummary {
+    let root = root.read().await;
+    summary::summarize(&root)
+}
+
 /// Walk over a root node and execute it and child nodes
 pub async fn execute(
     home: PathBuf,
@@ -71,7 +119,8 @@ pub async fn execute(
     let mut root = root.read().await.clone();
     let mut executor = Executor::new(home, kernels, patch_sender, node_ids, options);
     executor.prepare(&mut root).await?;
-    executor.execute(&mut root).await
+    executor.execute(&mut root).await?;
+    executor.write_profile().await
 }
 
 /// Walk over a root node and interrupt it and child nodes
@@ -175,12 +224,107 @@ pub struct Executor {
     /// The count of `MathBlock`s
     equation_count: u32,
 
+    /// The count of top level sections, used for per-section numbering
+    section_count: u32,
+
+    /// The count of appendices, used for lettering appendix labels
+    appendix_count: u32,
+
+    /// Options for numbering figures, tables and equations
+    ///
+    /// Loaded from the workspace config at the start of the compile phase.
+    numbering: Option<config::NumberingConfig>,
+
+    /// The labels of figures, tables and equations in the document
+    ///
+    /// A map of node id (or `id` property, if any) to the type and label of
+    /// the node, used to render references to the node in `Link`s.
+    labels: HashMap<String, (NodeType, String)>,
+
+    /// The citation style to render citations with
+    ///
+    /// Loaded from the workspace config at the start of the compile phase.
+    citation_style: CitationStyle,
+
+    /// The references of the document's root node
+    ///
+    /// A map of reference id to the one-based index of the reference, and an
+    /// author-date label for it, used to render `Cite` and `CiteGroup` nodes.
+    references: HashMap<String, (usize, String)>,
+
+    /// Whether supplements maintain their own reference list and numbering
+    ///
+    /// Loaded from the workspace config at the start of the compile phase.
+    supplement_references: bool,
+
+    /// The citation numbering within the current supplement
+    ///
+    /// `None` unless compiling within a supplement that maintains its own
+    /// citation numbering, in which case indices are assigned in order of
+    /// first appearance within the supplement.
+    supplement_citations: Option<HashMap<String, usize>>,
+
+    /// The glossary terms of the document's root node
+    ///
+    /// Collected from `DefinedTerm`s in the `about` property of the root node
+    /// and used to expand the first use of each term, link subsequent uses,
+    /// and render a glossary list.
+    glossary: Vec<GlossaryTerm>,
+
+    /// The index terms marked in the document
+    ///
+    /// Collected, in document order, from `StyledInline`s with the style
+    /// `index` and used to render a grouped, alphabetized index with links
+    /// back to each marked location.
+    index_terms: Vec<(String, NodeId)>,
+
+    /// The within-document targets of `Link`s in the document
+    ///
+    /// Collected during the compile phase and validated against `labels` at
+    /// the end of the phase, when all labels have been registered, so that
+    /// broken cross-references are reported.
+    link_targets: Vec<(NodeId, String)>,
+
+    /// The node ids and ids of nodes that registered an already registered label
+    ///
+    /// Recorded so that duplicate labels are reported at the end of the
+    /// compile phase.
+    duplicate_labels: Vec<(NodeId, String)>,
+
     /// Whether the current node is the last in a set
     ///
     /// Used for `IfBlock` (and possibly others) to control behavior of execution
     /// of child nodes.
     is_last: bool,
 
+    /// An on-disk cache of code chunk outputs for the workspace
+    ///
+    /// `None` when caching has been disabled with the `no_cache` option.
+    execution_cache: Option<Arc<RwLock<cache::ExecutionCache>>>,
+
+    /// A profile of the execution of the document
+    ///
+    /// `None` unless profiling has been enabled with the `profile` option.
+    execution_profile: Option<Arc<RwLock<profile::ExecutionProfile>>>,
+
+    /// Hooks, from the workspace config, to run around execution
+    ///
+    /// Loaded from `stencila.toml` at the start of the execute phase.
+    hooks: Option<config::ExecutionHooks>,
+
+    /// The time after which no more nodes should be executed
+    ///
+    /// `None` unless a wall-clock budget has been set with the `max_duration`
+    /// option, in which case it is set at the start of the execute phase.
+    execution_deadline: Option<Instant>,
+
+    /// The names of variables written by nodes that are pending execution
+    ///
+    /// Accumulated during [`Phase::Prepare`] in document order so that nodes
+    /// which read any of these variables are also marked as pending, even if
+    /// their own code is unchanged.
+    stale_variables: HashSet<String>,
+
     /// Options for execution
     options: ExecuteOptions,
 }
@@ -255,6 +399,55 @@ impl HeadingInfo {
     }
 }
 
+/// Records information about a term defined in a document's glossary
+/// in order to expand and link uses of the term and to render a
+/// sorted glossary list.
+#[derive(Debug, Clone)]
+pub struct GlossaryTerm {
+    /// The name of the term
+    name: String,
+
+    /// The abbreviation for the term (e.g. "HTML"), if any
+    code: Option<String>,
+
+    /// The description of the term
+    description: Option<String>,
+
+    /// The node id of the `DefinedTerm` (used to create a link to it)
+    node_id: NodeId,
+
+    /// Whether the term has been used yet in the walk over the document
+    used: bool,
+}
+
+impl GlossaryTerm {
+    /// Create a [`ListItem`] from a [`GlossaryTerm`]
+    fn into_list_item(self) -> ListItem {
+        let mut inlines = vec![Inline::Strong(Strong::new(vec![t(match &self.code {
+            Some(code) => format!("{} ({code})", self.name),
+            None => self.name.clone(),
+        })]))];
+
+        if let Some(description) = self.description {
+            inlines.push(t(format!(": {description}")));
+        }
+
+        ListItem::new(vec![Block::Paragraph(Paragraph::new(inlines))])
+    }
+
+    /// Create a [`List`] from a vector of [`GlossaryTerm`], sorted by name
+    fn into_list(terms: Vec<GlossaryTerm>) -> List {
+        List::new(
+            terms
+                .into_iter()
+                .sorted_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+                .map(|term| term.into_list_item())
+                .collect_vec(),
+            ListOrder::Unordered,
+        )
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Args)]
 #[serde(default, crate = "common::serde")]
 pub struct ExecuteOptions {
@@ -312,6 +505,102 @@ pub struct ExecuteOptions {
     /// rendering of prompts without making a potentially slow generative model API request.
     #[arg(long)]
     pub dry_run: bool,
+
+    /// Do not use the on-disk execution cache
+    ///
+    /// By default, the outputs of code chunks that do not assign variables are
+    /// cached in the workspace's `.stencila` directory, keyed by the code,
+    /// language, and input variables, so that they can be restored without
+    /// re-execution. Use this flag to neither read, nor write to, the cache.
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Refresh the on-disk execution cache
+    ///
+    /// Executes code chunks even if there is an entry for them in the execution
+    /// cache and overwrites those entries with the new outputs.
+    #[arg(long)]
+    pub refresh: bool,
+
+    /// Set the value of a parameter in the document
+    ///
+    /// Each value should be in the format `name=value`. The value is parsed as
+    /// JSON, falling back to a string, so that `--param year=2024` sets a
+    /// number and `--param country=NZ` sets a string. Repeat the option to set
+    /// several parameters.
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    pub params: Vec<String>,
+
+    /// Seed the random number generators of kernels
+    ///
+    /// Sets the seed of the random number generators of each kernel (e.g.
+    /// Python's `random` and `numpy`, R's `set.seed`) before execution so
+    /// that documents can be rendered reproducibly (e.g. for regression
+    /// testing).
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Record an execution profile
+    ///
+    /// Records the start and end timestamps of each executed node and writes
+    /// an HTML timeline report, showing where execution time was spent, to the
+    /// workspace's `.stencila` directory.
+    #[arg(long)]
+    pub profile: bool,
+
+    /// The maximum number of seconds to wait for a single node to execute
+    ///
+    /// By default, there is no limit on how long the execution of an individual
+    /// node (e.g. a `CodeChunk`) can take. Use this option to interrupt the kernel
+    /// and record a timeout message if execution exceeds this many seconds. Can be
+    /// overridden for individual code chunks using an `@timeout <SECONDS>` tag in
+    /// a comment.
+    #[arg(long)]
+    pub timeout: Option<u64>,
+
+    /// The maximum number of seconds for the execution of the whole document
+    ///
+    /// By default, there is no limit on how long the execution of a document
+    /// can take. Use this option to set a wall-clock budget for execution, for
+    /// example in continuous integration environments with hard time limits.
+    /// Once the budget is exceeded, any nodes that have not yet been executed
+    /// are marked as skipped with a message.
+    #[arg(long)]
+    pub max_duration: Option<u64>,
+
+    /// The maximum number of iterations rendered by each `ForBlock`
+    ///
+    /// By default, a `ForBlock` renders every iteration of its iterable into
+    /// the document. For large iterables (e.g. the rows of a large datatable)
+    /// use this option to limit the number of rendered iterations. When the
+    /// limit is exceeded, a warning message, with the number of remaining
+    /// iterations, is added to the block.
+    #[arg(long)]
+    pub max_iterations: Option<u64>,
+
+    /// Also execute the upstream dependencies of the target nodes
+    ///
+    /// When executing specific nodes (e.g. a single code chunk), use this
+    /// option to also execute the nodes that write the variables read,
+    /// directly or transitively, by the target nodes, like "Run all above"
+    /// in notebook interfaces. Has no effect when the whole document is
+    /// executed.
+    #[arg(long)]
+    pub upstream: bool,
+}
+
+/// The citation style to render `Cite` and `CiteGroup` nodes with
+///
+/// Only the citation format of a style is currently supported, not the
+/// details of punctuation, ordering etc of individual CSL styles.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum CitationStyle {
+    /// Author-date citations e.g. "(Smith, 2023)"
+    #[default]
+    AuthorDate,
+
+    /// Numeric citations e.g. "[1]"
+    Numeric,
 }
 
 /// A phase of an [`Executor`]
@@ -335,6 +624,15 @@ impl Executor {
         node_ids: Option<NodeIds>,
         options: Option<ExecuteOptions>,
     ) -> Self {
+        let options = options.unwrap_or_default();
+
+        let execution_cache = (!options.no_cache)
+            .then(|| Arc::new(RwLock::new(cache::ExecutionCache::read(&home))));
+
+        let execution_profile = options
+            .profile
+            .then(|| Arc::new(RwLock::new(profile::ExecutionProfile::default())));
+
         Self {
             directory_stack: vec![home],
             kernels,
@@ -349,8 +647,25 @@ impl Executor {
             table_count: 0,
             figure_count: 0,
             equation_count: 0,
+            section_count: 0,
+            appendix_count: 0,
+            numbering: None,
+            labels: HashMap::new(),
+            citation_style: CitationStyle::default(),
+            references: HashMap::new(),
+            supplement_references: false,
+            supplement_citations: None,
+            glossary: Vec::new(),
+            index_terms: Vec::new(),
+            link_targets: Vec::new(),
+            duplicate_labels: Vec::new(),
             is_last: false,
-            options: options.unwrap_or_default(),
+            execution_cache,
+            execution_profile,
+            hooks: None,
+            execution_deadline: None,
+            stale_variables: HashSet::new(),
+            options,
         }
     }
 
@@ -395,6 +710,18 @@ impl Executor {
         }
     }
 
+    /// Create a fork of the executor for compiling a supplement
+    ///
+    /// The fork restarts citation numbering so that supplements which are
+    /// required to maintain their own reference list render citations
+    /// numbered from one, in order of first appearance within the supplement.
+    fn fork_for_supplement(&self) -> Self {
+        Self {
+            supplement_citations: Some(HashMap::new()),
+            ..self.clone()
+        }
+    }
+
     /// Create a fork of the executor for [`Phase::Execute`]
     ///
     /// Create a clone of the executor, except for having a fork of its [`Kernels`].
@@ -415,28 +742,86 @@ impl Executor {
 
     /// Run [`Phase::Compile`]
     async fn compile(&mut self, root: &mut Node) -> Result<()> {
+        self.load_compile_config().await;
+
         self.phase = Phase::Compile;
         self.table_count = 0;
         self.figure_count = 0;
         self.equation_count = 0;
-        root.walk_async(self).await
+        self.section_count = 0;
+        self.appendix_count = 0;
+        self.labels.clear();
+        self.index_terms.clear();
+        self.link_targets.clear();
+        self.duplicate_labels.clear();
+        root.walk_async(self).await?;
+
+        // Validate cross-references now that all labels have been registered
+        for (node_id, id) in &self.duplicate_labels {
+            tracing::warn!("Node `{node_id}` has a duplicate label id `{id}`");
+        }
+        for (node_id, target) in &self.link_targets {
+            if !self.labels.contains_key(target.as_str()) {
+                tracing::warn!(
+                    "Link `{node_id}` targets `#{target}` which is not a label in the document"
+                );
+            }
+        }
+
+        Ok(())
     }
 
     /// Run [`Phase::Prepare`]
     async fn prepare(&mut self, root: &mut Node) -> Result<()> {
+        // Expand the target node ids to include their upstream dependencies
+        if self.options.upstream {
+            if let Some(node_ids) = &self.node_ids {
+                self.node_ids = Some(upstream::expand(root, node_ids));
+            }
+        }
+
         // Create a new context before walking the tree to avoid
         // having hangover information from the last time the prepare
         // phase was run.
         self.document_context = DocumentContext::default();
+        self.stale_variables.clear();
 
         self.phase = Phase::Prepare;
         root.walk_async(self).await
     }
 
+    /// Create an execution plan from a prepared root node
+    ///
+    /// Should be called after [`Executor::prepare`] so that the execution
+    /// status of nodes has been determined. See the [`plan`] function.
+    fn plan(&self, root: &Node) -> ExecutionPlan {
+        plan::Planner::plan(root, self)
+    }
+
     /// Run [`Phase::Execute`]
     async fn execute(&mut self, root: &mut Node) -> Result<()> {
+        self.execution_deadline = self
+            .options
+            .max_duration
+            .map(|seconds| Instant::now() + Duration::from_secs(seconds));
+
+        if let Some(seed) = self.options.seed {
+            self.kernels.write().await.seed(seed);
+        }
+
+        self.load_hooks().await;
+        if let Some(hooks) = self.hooks.clone() {
+            self.run_hooks(hooks.before.as_ref()).await?;
+        }
+
         self.phase = Phase::Execute;
-        root.walk_async(self).await
+        let result = root.walk_async(self).await;
+
+        if let Some(hooks) = self.hooks.clone() {
+            self.run_hooks(hooks.after.as_ref()).await?;
+        }
+
+        result
     }
 
     /// Run [`Phase::Interrupt`]
@@ -524,6 +909,559 @@ impl Executor {
         }
     }
 
+    /// Load numbering and citation options from the workspace config
+    ///
+    /// Called at the start of the compile phase so that the labels of
+    /// figures, tables and equations, and the rendering of citations,
+    /// honour the workspace's configuration.
+    async fn load_compile_config(&mut self) {
+        let home = self.directory_stack.first().cloned().unwrap_or_default();
+        match config::for_path(&home).await {
+            Ok(config) => {
+                self.numbering = config.numbering;
+                let citations = config.citations;
+                self.supplement_references = citations
+                    .as_ref()
+                    .and_then(|citations| citations.supplement_references)
+                    .unwrap_or_default();
+                self.citation_style = citations
+                    .and_then(|citations| citations.style)
+                    .map(|style| Self::citation_style_named(&style, &home))
+                    .unwrap_or_default();
+            }
+            Err(error) => {
+                tracing::warn!("While reading config: {error}");
+                self.numbering = None;
+                self.citation_style = CitationStyle::default();
+            }
+        };
+    }
+
+    /// Resolve a citation style from its name, or the path of a CSL file
+    ///
+    /// For CSL files, only the `citation-format` of the style is derived
+    /// from the file, defaulting to author-date.
+    fn citation_style_named(style: &str, home: &Path) -> CitationStyle {
+        match style.to_lowercase().as_str() {
+            "apa" | "chicago" | "harvard" | "author-date" => CitationStyle::AuthorDate,
+            "vancouver" | "ieee" | "numeric" => CitationStyle::Numeric,
+            _ => {
+                let path = home.join(style);
+                match std::fs::read_to_string(&path) {
+                    Ok(csl) => {
+                        if csl.contains("citation-format=\"numeric\"") {
+                            CitationStyle::Numeric
+                        } else {
+                            CitationStyle::AuthorDate
+                        }
+                    }
+                    Err(error) => {
+                        tracing::warn!(
+                            "While reading citation style `{}`: {error}",
+                            path.display()
+                        );
+                        CitationStyle::default()
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether figures, tables and equations are numbered within sections
+    pub fn numbering_per_section(&self) -> bool {
+        self.numbering
+            .as_ref()
+            .and_then(|numbering| numbering.per_section)
+            .unwrap_or_default()
+    }
+
+    /// Whether figure, table and equation counts reset at each appendix
+    pub fn numbering_appendix_reset(&self) -> bool {
+        self.numbering
+            .as_ref()
+            .and_then(|numbering| numbering.appendix_reset)
+            .unwrap_or_default()
+    }
+
+    /// Get the prefix used to detect, and label, appendix headings
+    pub fn appendix_prefix(&self) -> String {
+        self.numbering
+            .as_ref()
+            .and_then(|numbering| numbering.appendix_prefix.as_deref())
+            .unwrap_or("Appendix")
+            .to_string()
+    }
+
+    /// Create a label for a figure, table or equation from its count
+    ///
+    /// When per-section numbering is enabled the label includes the number
+    /// of the current top level section (e.g. "2.3"), or the letter of the
+    /// current appendix (e.g. "A.1") if appendix lettering is enabled.
+    pub fn node_label(&self, count: u32) -> String {
+        let per_appendix = self.appendix_count > 0 && self.numbering_appendix_reset();
+        if !(self.numbering_per_section() || per_appendix) || self.section_count == 0 {
+            return count.to_string();
+        }
+
+        let appendix_letters = self
+            .numbering
+            .as_ref()
+            .and_then(|numbering| numbering.appendix_letters)
+            .unwrap_or_default();
+
+        let section = if self.appendix_count > 0 && appendix_letters {
+            // 1 -> A, 2 -> B etc, falling back to numbers beyond Z
+            match char::from_u32('A' as u32 + self.appendix_count - 1) {
+                Some(letter) if self.appendix_count <= 26 => letter.to_string(),
+                _ => self.appendix_count.to_string(),
+            }
+        } else {
+            self.section_count.to_string()
+        };
+
+        format!("{section}.{count}")
+    }
+
+    /// Register the label of a figure, table or equation so that `Link`s
+    /// which reference the node can be rendered with the label
+    ///
+    /// Records a duplicate if the id has already been registered in this
+    /// compile phase, so that it can be reported at the end of the phase.
+    pub fn register_label(
+        &mut self,
+        id: String,
+        node_id: &NodeId,
+        node_type: NodeType,
+        label: String,
+    ) {
+        if self.labels.insert(id.clone(), (node_type, label)).is_some() {
+            self.duplicate_labels.push((node_id.clone(), id));
+        }
+    }
+
+    /// Get the type and label of a registered figure, table or equation
+    pub fn get_label(&self, id: &str) -> Option<&(NodeType, String)> {
+        self.labels.get(id)
+    }
+
+    /// Get the numeric index to render a citation with
+    ///
+    /// Within a supplement that maintains its own citation numbering,
+    /// indices are assigned in order of first appearance within the
+    /// supplement. Elsewhere, the index of the reference in the reference
+    /// list is used.
+    pub fn citation_index(&mut self, target: &str, default: usize) -> usize {
+        match self.supplement_citations.as_mut() {
+            Some(indices) => {
+                let next = indices.len() + 1;
+                *indices.entry(target.to_string()).or_insert(next)
+            }
+            None => default,
+        }
+    }
+
+    /// Get the prefix used when rendering references to a node type
+    pub fn reference_prefix(&self, node_type: &NodeType) -> String {
+        let numbering = self.numbering.as_ref();
+        let prefix = match node_type {
+            NodeType::Table => numbering
+                .and_then(|numbering| numbering.table_prefix.as_deref())
+                .unwrap_or("Table"),
+            NodeType::MathBlock => numbering
+                .and_then(|numbering| numbering.equation_prefix.as_deref())
+                .unwrap_or("Equation"),
+            _ => numbering
+                .and_then(|numbering| numbering.figure_prefix.as_deref())
+                .unwrap_or("Figure"),
+        };
+        prefix.to_string()
+    }
+
+    /// Expand and link uses of glossary terms in a text value
+    ///
+    /// Splits the text around word bounded matches of each term's code (or
+    /// name, if it has no code) and pushes the resulting inlines onto
+    /// `inlines`: the first use of a term is expanded to include its name,
+    /// and subsequent uses are linked to the `DefinedTerm`. Returns whether
+    /// any expansion or linking was done.
+    pub fn glossary_expand(&mut self, text: &str, inlines: &mut Vec<Inline>) -> bool {
+        let mut changed = false;
+        let mut current = String::new();
+        let mut rest = text;
+        while !rest.is_empty() {
+            // Find the earliest, word bounded, match of any term's code or name
+            let mut earliest: Option<(usize, usize, usize)> = None;
+            for (index, term) in self.glossary.iter().enumerate() {
+                let pattern = term.code.as_deref().unwrap_or(&term.name);
+                if pattern.is_empty() {
+                    continue;
+                }
+
+                let mut from = 0;
+                while let Some(position) = rest[from..].find(pattern) {
+                    let start = from + position;
+                    let end = start + pattern.len();
+                    let bounded = rest[..start]
+                        .chars()
+                        .next_back()
+                        .map_or(true, |char| !char.is_alphanumeric())
+                        && rest[end..]
+                            .chars()
+                            .next()
+                            .map_or(true, |char| !char.is_alphanumeric());
+                    if bounded {
+                        if earliest.map_or(true, |(earliest_start, ..)| start < earliest_start) {
+                            earliest = Some((start, index, pattern.len()));
+                        }
+                        break;
+                    }
+                    from = end;
+                }
+            }
+
+            let Some((start, index, length)) = earliest else {
+                current.push_str(rest);
+                break;
+            };
+            let end = start + length;
+
+            let term = &mut self.glossary[index];
+            if !term.used {
+                term.used = true;
+                if rest[..start].ends_with('(') && rest[end..].starts_with(')') {
+                    // Already expanded on a previous compile so leave as is
+                    current.push_str(&rest[..end]);
+                } else if let Some(code) = &term.code {
+                    // Expand the first use of the term to include its name
+                    current.push_str(&rest[..start]);
+                    current.push_str(&term.name);
+                    current.push_str(" (");
+                    current.push_str(code);
+                    current.push(')');
+                    changed = true;
+                } else {
+                    current.push_str(&rest[..end]);
+                }
+            } else {
+                // Link subsequent uses of the term to its definition
+                current.push_str(&rest[..start]);
+                if !current.is_empty() {
+                    inlines.push(t(std::mem::take(&mut current)));
+                }
+                inlines.push(Inline::Link(Link::new(
+                    vec![t(rest[start..end].to_string())],
+                    ["#", &term.node_id.to_string()].concat(),
+                )));
+                changed = true;
+            }
+            rest = &rest[end..];
+        }
+
+        if !current.is_empty() {
+            inlines.push(t(current));
+        }
+
+        changed
+    }
+
+    /// Render a grouped, alphabetized index of the terms marked in the document
+    ///
+    /// Occurrences of the same term are merged into a single entry with a
+    /// numbered link for each marked location. Entries are grouped by first
+    /// letter, with each group rendered as the letter followed by a list of
+    /// the entries beginning with it.
+    pub fn index_blocks(&self) -> Vec<Block> {
+        // Merge occurrences of the same term, using a lowercased key so that
+        // entries are merged, and sorted, case insensitively
+        let mut terms: BTreeMap<String, (String, Vec<NodeId>)> = BTreeMap::new();
+        for (term, node_id) in &self.index_terms {
+            let term = term.trim();
+            if term.is_empty() {
+                continue;
+            }
+            terms
+                .entry(term.to_lowercase())
+                .or_insert_with(|| (term.to_string(), Vec::new()))
+                .1
+                .push(node_id.clone());
+        }
+
+        let mut blocks = Vec::new();
+        let mut letter = None;
+        let mut items: Vec<ListItem> = Vec::new();
+        for (key, (term, node_ids)) in terms {
+            let first = key
+                .chars()
+                .next()
+                .map(|char| char.to_ascii_uppercase())
+                .filter(|char| char.is_ascii_alphabetic())
+                .unwrap_or('#');
+            if letter != Some(first) {
+                if !items.is_empty() {
+                    blocks.push(Block::List(List::new(
+                        std::mem::take(&mut items),
+                        ListOrder::Unordered,
+                    )));
+                }
+                blocks.push(Block::Paragraph(Paragraph::new(vec![Inline::Strong(
+                    Strong::new(vec![t(first.to_string())]),
+                )])));
+                letter = Some(first);
+            }
+
+            let mut inlines = vec![t(term)];
+            for (index, node_id) in node_ids.iter().enumerate() {
+                inlines.push(t(if index == 0 { " " } else { ", " }));
+                inlines.push(Inline::Link(Link::new(
+                    vec![t((index + 1).to_string())],
+                    ["#", &node_id.to_string()].concat(),
+                )));
+            }
+            items.push(ListItem::new(vec![Block::Paragraph(Paragraph::new(
+                inlines,
+            ))]));
+        }
+        if !items.is_empty() {
+            blocks.push(Block::List(List::new(items, ListOrder::Unordered)));
+        }
+
+        blocks
+    }
+
+    /// Load execution hooks from the workspace config
+    ///
+    /// Called at the start of the execute phase so that hooks registered in
+    /// `stencila.toml` apply to both document and code chunk execution.
+    async fn load_hooks(&mut self) {
+        let home = self.directory_stack.first().cloned().unwrap_or_default();
+        self.hooks = match config::for_path(&home).await {
+            Ok(config) => config.execution.and_then(|execution| execution.hooks),
+            Err(error) => {
+                tracing::warn!("While reading execution hooks from config: {error}");
+                None
+            }
+        };
+    }
+
+    /// Run a set of execution hooks
+    ///
+    /// Shell commands are run in the workspace directory and kernel snippets
+    /// are run in the document's kernels. Errors if a command has a non-zero
+    /// exit status, or a snippet produces an error message.
+    async fn run_hooks(&self, hooks: Option<&Vec<config::Hook>>) -> Result<()> {
+        for hook in hooks.iter().flat_map(|hooks| hooks.iter()) {
+            match hook {
+                config::Hook::Shell(command) => {
+                    let home = self.directory_stack.first().cloned().unwrap_or_default();
+                    let status = Command::new("sh")
+                        .args(["-c", command])
+                        .current_dir(home)
+                        .status()
+                        .await?;
+                    if !status.success() {
+                        bail!("Execution hook `{command}` failed with {status}");
+                    }
+                }
+                config::Hook::Kernel { language, code } => {
+                    let (.., messages, _) =
+                        self.kernels.write().await.execute(code, Some(language)).await?;
+                    for message in messages {
+                        if matches!(
+                            message.level,
+                            MessageLevel::Error | MessageLevel::Exception
+                        ) {
+                            bail!("Execution hook failed: {}", message.message);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run any `before-each` hooks before a code chunk is executed
+    pub async fn run_hooks_before_chunk(&self) {
+        if let Some(hooks) = &self.hooks {
+            if let Err(error) = self.run_hooks(hooks.before_each.as_ref()).await {
+                tracing::error!("While running `before-each` execution hook: {error}");
+            }
+        }
+    }
+
+    /// Run any `after-each` hooks after a code chunk has been executed
+    pub async fn run_hooks_after_chunk(&self) {
+        if let Some(hooks) = &self.hooks {
+            if let Err(error) = self.run_hooks(hooks.after_each.as_ref()).await {
+                tracing::error!("While running `after-each` execution hook: {error}");
+            }
+        }
+    }
+
+    /// Record a span in the execution profile, if profiling is enabled
+    pub async fn profile_span(
+        &self,
+        node_type: NodeType,
+        node_id: &NodeId,
+        started: &Timestamp,
+        ended: &Timestamp,
+    ) {
+        if let Some(profile) = &self.execution_profile {
+            profile
+                .write()
+                .await
+                .record(node_type, node_id, started, ended);
+        }
+    }
+
+    /// Write the execution profile report, if profiling is enabled
+    async fn write_profile(&self) -> Result<()> {
+        if let Some(profile) = &self.execution_profile {
+            let home = self.directory_stack.first().cloned().unwrap_or_default();
+            let path = profile.read().await.write(&home)?;
+            tracing::info!("Wrote execution profile to {}", path.display());
+        }
+
+        Ok(())
+    }
+
+    /// Calculate the execution cache key for some code
+    ///
+    /// Collects the hints of the variables read by the code from the current
+    /// kernel instances so that the key, and thus the cache entry, changes
+    /// when any of the inputs change. Returns `None` when caching is disabled.
+    pub async fn execution_cache_key(
+        &self,
+        code: &str,
+        language: &str,
+        variables_read: &[String],
+    ) -> Option<String> {
+        self.execution_cache.as_ref()?;
+
+        let mut inputs = Vec::new();
+        for instance in self.kernels.read().await.instances().await {
+            let Ok(variables) = instance.lock().await.list().await else {
+                continue;
+            };
+            for variable in variables {
+                if variables_read.contains(&variable.name) {
+                    inputs.push((variable.name, variable.hint));
+                }
+            }
+        }
+        inputs.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+
+        Some(cache::ExecutionCache::key(code, language, &inputs))
+    }
+
+    /// Get the outputs of a code chunk from the execution cache
+    ///
+    /// Returns `None` when caching is disabled, when the `refresh` option
+    /// is used, or when there is no entry for the key.
+    pub async fn execution_cache_get(&self, key: &str) -> Option<Vec<Node>> {
+        if self.options.refresh {
+            return None;
+        }
+
+        let cache = self.execution_cache.as_ref()?;
+        let outputs = cache.read().await.get(key)?;
+        Some(outputs)
+    }
+
+    /// Insert the outputs of a code chunk into the execution cache
+    pub async fn execution_cache_insert(&self, key: &str, outputs: &[Node]) {
+        if let Some(cache) = &self.execution_cache {
+            cache.write().await.insert(key, outputs);
+        }
+    }
+
+    /// Check whether the wall-clock execution budget has been exceeded
+    ///
+    /// When the `max_duration` option has been set and exceeded, patches the
+    /// node as skipped, with a message explaining why, and returns `true` so
+    /// that the node's `execute` method can return early.
+    pub fn execution_budget_exceeded(&self, node_id: &NodeId) -> bool {
+        let Some(deadline) = self.execution_deadline else {
+            return false;
+        };
+
+        if Instant::now() < deadline {
+            return false;
+        }
+
+        let max_duration = self.options.max_duration.unwrap_or_default();
+        let messages = vec![ExecutionMessage {
+            level: MessageLevel::Warning,
+            message: format!(
+                "Skipped because the maximum execution duration of {max_duration} seconds was exceeded"
+            ),
+            ..Default::default()
+        }];
+
+        self.patch(
+            node_id,
+            [
+                prelude::set(NodeProperty::ExecutionStatus, ExecutionStatus::Skipped),
+                prelude::set(NodeProperty::ExecutionMessages, messages),
+            ],
+        );
+
+        true
+    }
+
+    /// Whether a node should be interrupted
+    ///
+    /// When the executor has `node_ids`, only those nodes are interrupted so
+    /// that a single long-running node can be cancelled while other pending
+    /// nodes remain runnable.
+    pub fn node_should_interrupt(&self, node_id: &NodeId) -> bool {
+        match &self.node_ids {
+            Some(node_ids) => node_ids.contains(node_id),
+            None => true,
+        }
+    }
+
+    /// Get the value for a parameter set on the command line
+    ///
+    /// Values are parsed as JSON, falling back to a string, so that
+    /// `--param year=2024` is a number and `--param country=NZ` is a string.
+    pub fn parameter_value(&self, name: &str) -> Option<Node> {
+        for param in &self.options.params {
+            if let Some((param_name, value)) = param.split_once('=') {
+                if param_name == name {
+                    return Some(
+                        serde_json::from_str(value)
+                            .unwrap_or_else(|_| Node::String(value.to_string())),
+                    );
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Record that a node pending execution writes variables
+    ///
+    /// Nodes downstream in the document which read any of these variables
+    /// will also be marked as pending (see [`Executor::reads_stale_variables`]).
+    pub fn will_write_variables<I>(&mut self, variables: I)
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.stale_variables.extend(variables);
+    }
+
+    /// Check whether any of the variables read by a node are written by
+    /// an upstream node that is pending execution
+    ///
+    /// Used during [`Phase::Prepare`] to mark nodes as pending when their
+    /// inputs may change, even if their own code is unchanged.
+    pub fn reads_stale_variables(&self, variables: &[String]) -> bool {
+        variables
+            .iter()
+            .any(|variable| self.stale_variables.contains(variable))
+    }
+
     /// Get the [`AuthorRole`] for the kernel instance if it is different from the current
     pub async fn node_execution_instance_author(
         &self,
@@ -657,8 +1595,11 @@ impl VisitorAsync for Executor {
     async fn visit_inline(&mut self, inline: &mut Inline) -> Result<WalkControl> {
         use Inline::*;
         Ok(match inline {
+            Cite(node) => self.visit_executable(node).await,
+            CiteGroup(node) => self.visit_executable(node).await,
             CodeExpression(node) => self.visit_executable(node).await,
             InstructionInline(node) => self.visit_executable(node).await,
+            Link(node) => self.visit_executable(node).await,
             MathInline(node) => self.visit_executable(node).await,
             Parameter(node) => self.visit_executable(node).await,
             StyledInline(node) => self.visit_executable(node).await,
@@ -0,0 +1,50 @@
+use std::str::FromStr;
+
+use codec_text_trait::to_text;
+use common::{once_cell::sync::Lazy, regex::Regex};
+use schema::{shortcuts::t, Link, NodeId, NodeProperty};
+
+use crate::prelude::*;
+
+impl Executable for Link {
+    #[tracing::instrument(skip_all)]
+    async fn compile(&mut self, executor: &mut Executor) -> WalkControl {
+        let node_id = self.node_id();
+        tracing::trace!("Compiling Link {node_id}");
+
+        // Only render content for within-document links to labelled nodes
+        let Some(target) = self.target.strip_prefix('#') else {
+            return WalkControl::Continue;
+        };
+
+        // Record the target, unless it is a node id (e.g. a link in a table
+        // of contents), so that cross-references can be validated at the end
+        // of the compile phase when all labels have been registered
+        if NodeId::from_str(target).is_err() {
+            executor
+                .link_targets
+                .push((node_id.clone(), target.to_string()));
+        }
+
+        let Some((node_type, label)) = executor.get_label(target) else {
+            return WalkControl::Continue;
+        };
+
+        let text = format!("{} {}", executor.reference_prefix(node_type), label);
+
+        // Only set the content if it is empty, or looks like a previously
+        // generated reference, so that authored content is not overwritten
+        static REFERENCE_REGEX: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"^[A-Za-z ]+ [A-Z0-9][0-9.]*$").expect("invalid regex")
+        });
+        let current = to_text(&self.content);
+        let current = current.trim();
+        if current != text && (current.is_empty() || REFERENCE_REGEX.is_match(current)) {
+            let content = vec![t(text)];
+            self.content = content.clone();
+            executor.patch(&node_id, [set(NodeProperty::Content, content)]);
+        }
+
+        WalkControl::Continue
+    }
+}
@@ -16,6 +16,17 @@ impl Executable for MathBlock {
         if Some(&compilation_digest) == self.options.compilation_digest.as_ref() {
             tracing::trace!("Skipping compiling MathBlock {node_id}");
 
+            // Still register the existing label so that links to the equation
+            // can be rendered
+            if let Some(label) = &self.label {
+                executor.register_label(
+                    self.id.clone().unwrap_or_else(|| node_id.to_string()),
+                    &node_id,
+                    self.node_type(),
+                    label.clone(),
+                );
+            }
+
             return WalkControl::Break;
         }
 
@@ -23,11 +34,22 @@ impl Executable for MathBlock {
 
         executor.equation_count += 1;
 
-        if self.label_automatically.unwrap_or(true) {
-            let label = executor.equation_count.to_string();
+        let label = if self.label_automatically.unwrap_or(true) {
+            let label = executor.node_label(executor.equation_count);
             if Some(&label) != self.label.as_ref() {
-                executor.patch(&node_id, [set(NodeProperty::Label, label)]);
+                executor.patch(&node_id, [set(NodeProperty::Label, label.clone())]);
             }
+            Some(label)
+        } else {
+            self.label.clone()
+        };
+        if let Some(label) = label {
+            executor.register_label(
+                self.id.clone().unwrap_or_else(|| node_id.to_string()),
+                &node_id,
+                self.node_type(),
+                label,
+            );
         }
 
         if !self.code.trim().is_empty() {
@@ -1,8 +1,34 @@
-use schema::Paragraph;
+use schema::{Inline, Paragraph};
 
 use crate::prelude::*;
 
 impl Executable for Paragraph {
+    #[tracing::instrument(skip_all)]
+    async fn compile(&mut self, executor: &mut Executor) -> WalkControl {
+        let node_id = self.node_id();
+        tracing::trace!("Compiling Paragraph {node_id}");
+
+        // Expand and link any uses of glossary terms in the paragraph
+        if !executor.glossary.is_empty() {
+            let mut content = Vec::with_capacity(self.content.len());
+            let mut changed = false;
+            for inline in &self.content {
+                if let Inline::Text(text) = inline {
+                    changed |= executor.glossary_expand(&text.value, &mut content);
+                } else {
+                    content.push(inline.clone());
+                }
+            }
+            if changed {
+                self.content = content.clone();
+                executor.patch(&node_id, [set(NodeProperty::Content, content)]);
+            }
+        }
+
+        // Continue walk over content
+        WalkControl::Continue
+    }
+
     #[tracing::instrument(skip_all)]
     async fn prepare(&mut self, executor: &mut Executor) -> WalkControl {
         tracing::trace!("Preparing Paragraph {}", self.node_id());
@@ -1,4 +1,4 @@
-use schema::Parameter;
+use schema::{Node, Parameter};
 
 use crate::prelude::*;
 
@@ -9,15 +9,52 @@ impl Executable for Parameter {
 
         tracing::debug!("Executing Parameter {node_id}");
 
+        // Resolve the value of the parameter: a value set on the command line,
+        // falling back to the current value, falling back to the default
+        let value = executor
+            .parameter_value(&self.name)
+            .or_else(|| self.value.as_deref().cloned())
+            .or_else(|| self.options.default.as_deref().cloned());
+
+        let Some(value) = value else {
+            executor.patch(
+                &node_id,
+                [set(
+                    NodeProperty::ExecutionMessages,
+                    vec![ExecutionMessage::new(
+                        MessageLevel::Warning,
+                        format!("Parameter `{}` has no value or default", self.name),
+                    )],
+                )],
+            );
+            return WalkControl::Break;
+        };
+
+        // Set the corresponding kernel variable so that executable code
+        // can use the parameter
+        let messages = match executor.kernels().await.set(&self.name, &value).await {
+            Ok(..) => None,
+            Err(error) => Some(vec![error_to_execution_message(
+                "While setting parameter",
+                error,
+            )]),
+        };
+
+        let status = execution_status(&messages);
+        let required = execution_required_status(&status);
+
+        // Set the value here because it may be used in rendering before the
+        // following patch is applied
+        self.value = Some(Box::new(value.clone()));
+
         executor.patch(
             &node_id,
-            [set(
-                NodeProperty::ExecutionMessages,
-                vec![ExecutionMessage::new(
-                    MessageLevel::Warning,
-                    "Execution of parameters is not yet implemented".to_string(),
-                )],
-            )],
+            [
+                set(NodeProperty::Value, value),
+                set(NodeProperty::ExecutionStatus, status),
+                set(NodeProperty::ExecutionRequired, required),
+                set(NodeProperty::ExecutionMessages, messages),
+            ],
         );
 
         WalkControl::Break
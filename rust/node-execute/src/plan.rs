@@ -0,0 +1,215 @@
+//! An execution plan for a document
+//!
+//! A plan lists the nodes that would be executed, in document order, with the
+//! kernel that each would be executed in and the reason that it would be
+//! executed. Because planning only runs the prepare phase, on a clone of the
+//! root node and without a patch sender, it has no side effects on the
+//! document or its kernels. Useful for debugging why a node keeps re-running.
+
+use std::fmt::{self, Display};
+
+use common::{serde::Serialize, strum};
+use schema::{
+    Block, CompilationDigest, ExecutionMode, ExecutionStatus, Inline, Node, NodeId, NodeType,
+    Visitor, WalkControl,
+};
+
+use crate::{ExecuteOptions, Executor};
+
+/// A plan of the nodes that would be executed in a document
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase", crate = "common::serde")]
+pub struct ExecutionPlan {
+    /// The nodes that would be executed, in document order
+    pub steps: Vec<ExecutionStep>,
+}
+
+/// A node that would be executed as part of an [`ExecutionPlan`]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase", crate = "common::serde")]
+pub struct ExecutionStep {
+    /// The type of the node
+    pub node_type: NodeType,
+
+    /// The id of the node
+    pub node_id: NodeId,
+
+    /// The programming language of the node, used to determine the kernel
+    /// that the node would be executed in
+    pub programming_language: Option<String>,
+
+    /// The reason that the node would be executed
+    pub reason: ExecutionReason,
+}
+
+/// The reason that a node would be executed
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, strum::Display)]
+#[serde(rename_all = "camelCase", crate = "common::serde")]
+#[strum(serialize_all = "lowercase")]
+pub enum ExecutionReason {
+    /// Execution has been forced (e.g. with the `force_all` option or an
+    /// `always` execution mode)
+    Forced,
+
+    /// The node has never been executed
+    Never,
+
+    /// The node's code (or other properties in its digest) changed since
+    /// it was last executed
+    Stale,
+
+    /// The node reads a variable written by an upstream node that would
+    /// be executed
+    Dependency,
+}
+
+impl Display for ExecutionPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.steps.is_empty() {
+            return writeln!(f, "No nodes would be executed");
+        }
+
+        for (index, step) in self.steps.iter().enumerate() {
+            writeln!(
+                f,
+                "{index}. {node_type} {node_id} ({language}): {reason}",
+                index = index + 1,
+                node_type = step.node_type,
+                node_id = step.node_id,
+                language = step.programming_language.as_deref().unwrap_or("default"),
+                reason = step.reason
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A visitor that collects the nodes that are pending execution into a plan
+pub(crate) struct Planner<'options> {
+    /// The options of the executor, used to determine the reason for execution
+    options: &'options ExecuteOptions,
+
+    /// The plan being collected
+    plan: ExecutionPlan,
+}
+
+impl<'options> Planner<'options> {
+    /// Walk over a prepared root node and collect the plan
+    pub fn plan(root: &Node, executor: &'options Executor) -> ExecutionPlan {
+        let mut planner = Planner {
+            options: &executor.options,
+            plan: ExecutionPlan::default(),
+        };
+        root.walk(&mut planner);
+        planner.plan
+    }
+
+    /// Record a step for a node if its execution status is pending
+    #[allow(clippy::too_many_arguments)]
+    fn record(
+        &mut self,
+        node_type: NodeType,
+        node_id: NodeId,
+        programming_language: Option<String>,
+        execution_mode: &Option<ExecutionMode>,
+        execution_status: &Option<ExecutionStatus>,
+        compilation_digest: &Option<CompilationDigest>,
+        execution_digest: &Option<CompilationDigest>,
+    ) {
+        if !matches!(execution_status, Some(ExecutionStatus::Pending)) {
+            return;
+        }
+
+        let reason = if self.options.force_all
+            || matches!(execution_mode, Some(ExecutionMode::Always))
+        {
+            ExecutionReason::Forced
+        } else if execution_digest.is_none() {
+            ExecutionReason::Never
+        } else if compilation_digest != execution_digest {
+            ExecutionReason::Stale
+        } else {
+            ExecutionReason::Dependency
+        };
+
+        self.plan.steps.push(ExecutionStep {
+            node_type,
+            node_id,
+            programming_language,
+            reason,
+        });
+    }
+}
+
+impl Visitor for Planner<'_> {
+    fn visit_block(&mut self, block: &Block) -> WalkControl {
+        match block {
+            Block::CodeChunk(node) => self.record(
+                node.node_type(),
+                node.node_id(),
+                node.programming_language.clone(),
+                &node.execution_mode,
+                &node.options.execution_status,
+                &node.options.compilation_digest,
+                &node.options.execution_digest,
+            ),
+            Block::ForBlock(node) => self.record(
+                node.node_type(),
+                node.node_id(),
+                node.programming_language.clone(),
+                &node.execution_mode,
+                &node.options.execution_status,
+                &node.options.compilation_digest,
+                &node.options.execution_digest,
+            ),
+            Block::IfBlock(node) => self.record(
+                node.node_type(),
+                node.node_id(),
+                None,
+                &node.execution_mode,
+                &node.options.execution_status,
+                &node.options.compilation_digest,
+                &node.options.execution_digest,
+            ),
+            Block::IncludeBlock(node) => self.record(
+                node.node_type(),
+                node.node_id(),
+                None,
+                &node.execution_mode,
+                &node.options.execution_status,
+                &node.options.compilation_digest,
+                &node.options.execution_digest,
+            ),
+            Block::InstructionBlock(node) => self.record(
+                node.node_type(),
+                node.node_id(),
+                None,
+                &node.execution_mode,
+                &node.options.execution_status,
+                &node.options.compilation_digest,
+                &node.options.execution_digest,
+            ),
+            _ => {}
+        }
+
+        WalkControl::Continue
+    }
+
+    fn visit_inline(&mut self, inline: &Inline) -> WalkControl {
+        match inline {
+            Inline::CodeExpression(node) => self.record(
+                node.node_type(),
+                node.node_id(),
+                node.programming_language.clone(),
+                &node.execution_mode,
+                &node.options.execution_status,
+                &node.options.compilation_digest,
+                &node.options.execution_digest,
+            ),
+            _ => {}
+        }
+
+        WalkControl::Continue
+    }
+}
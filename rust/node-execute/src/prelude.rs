@@ -8,7 +8,7 @@ pub use schema::{
     NodeProperty, Null, PatchNode, PatchOp, PatchValue, Primitive, Timestamp, WalkControl,
     WalkNode,
 };
-use schema::{CompilationDigest, CompilationMessage, ExecutionKind};
+use schema::{CompilationDigest, CompilationMessage, ExecutionKind, ExecutionTag};
 
 pub(crate) use crate::{Executable, Executor};
 
@@ -116,6 +116,43 @@ pub fn execution_required_status(status: &ExecutionStatus) -> ExecutionRequired
     }
 }
 
+/// Get the execution timeout for a node, if any
+///
+/// An `@timeout <SECONDS>` tag on the node takes precedence over the
+/// executor's `timeout` option.
+pub fn execution_timeout(
+    execution_tags: &Option<Vec<ExecutionTag>>,
+    executor: &Executor,
+) -> Option<std::time::Duration> {
+    execution_tags
+        .iter()
+        .flatten()
+        .find(|tag| tag.name == "timeout")
+        .and_then(|tag| tag.value.parse().ok())
+        .or(executor.options.timeout)
+        .map(std::time::Duration::from_secs)
+}
+
+/// Get the boolean value of an execution tag (e.g. `@eval false`)
+///
+/// The values `false`, `no` and `0` are treated as false and any other
+/// value as true. Returns `None` if the tag is not present.
+pub fn execution_tag_bool(
+    execution_tags: &Option<Vec<ExecutionTag>>,
+    name: &str,
+) -> Option<bool> {
+    execution_tags
+        .iter()
+        .flatten()
+        .find(|tag| tag.name == name)
+        .map(|tag| {
+            !matches!(
+                tag.value.trim().to_lowercase().as_str(),
+                "false" | "no" | "0"
+            )
+        })
+}
+
 /// Create a value for `execution_duration` from start and end timestamps
 pub fn execution_duration(started: &Timestamp, ended: &Timestamp) -> Duration {
     ended
@@ -176,6 +213,18 @@ pub fn append<T: PatchNode>(
     )
 }
 
+/// Replace an item in a vector property
+pub fn replace<T: PatchNode>(
+    node_property: NodeProperty,
+    index: usize,
+    value: T,
+) -> (NodeProperty, PatchOp) {
+    (
+        node_property,
+        PatchOp::Replace(vec![(index, value.to_value().unwrap_or_default())]),
+    )
+}
+
 /// Clear a vector property
 pub fn clear(node_property: NodeProperty) -> (NodeProperty, PatchOp) {
     (node_property, PatchOp::Clear)
@@ -185,14 +234,16 @@ pub fn clear(node_property: NodeProperty) -> (NodeProperty, PatchOp) {
 #[macro_export]
 macro_rules! interrupt_impl {
     ($node: expr, $executor: expr, $node_id: expr) => {
-        if let Some((status, required)) = interruption(&$node.options.execution_status) {
-            $executor.patch(
-                $node_id,
-                [
-                    set(NodeProperty::ExecutionStatus, status),
-                    set(NodeProperty::ExecutionRequired, required),
-                ],
-            );
+        if $executor.node_should_interrupt($node_id) {
+            if let Some((status, required)) = interruption(&$node.options.execution_status) {
+                $executor.patch(
+                    $node_id,
+                    [
+                        set(NodeProperty::ExecutionStatus, status),
+                        set(NodeProperty::ExecutionRequired, required),
+                    ],
+                );
+            }
         }
     };
 }
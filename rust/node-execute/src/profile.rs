@@ -0,0 +1,115 @@
+//! Profiling of document execution
+//!
+//! When the `--profile` option is used, the start and end timestamps of each
+//! executed node are recorded and, when execution has finished, an HTML
+//! timeline report showing where time was spent is written to the
+//! workspace's `.stencila` directory.
+
+use std::{
+    fs::{create_dir_all, write},
+    path::{Path, PathBuf},
+};
+
+use common::eyre::Result;
+use schema::{NodeId, NodeType, Timestamp};
+
+/// A profile of the execution of a document
+#[derive(Default)]
+pub(crate) struct ExecutionProfile {
+    /// The spans recorded for executed nodes, in order of completion
+    spans: Vec<ExecutionSpan>,
+}
+
+/// The execution of a single node
+struct ExecutionSpan {
+    /// The type of the node
+    node_type: NodeType,
+
+    /// The id of the node
+    node_id: NodeId,
+
+    /// When execution of the node started
+    started: i64,
+
+    /// When execution of the node ended
+    ended: i64,
+}
+
+impl ExecutionProfile {
+    /// Record a span for the execution of a node
+    pub fn record(
+        &mut self,
+        node_type: NodeType,
+        node_id: &NodeId,
+        started: &Timestamp,
+        ended: &Timestamp,
+    ) {
+        self.spans.push(ExecutionSpan {
+            node_type,
+            node_id: node_id.clone(),
+            started: started.value,
+            ended: ended.value,
+        });
+    }
+
+    /// Write the profile as an HTML timeline in a workspace directory
+    ///
+    /// Each span is rendered as a horizontal bar positioned according to its
+    /// start and end time relative to the whole execution.
+    pub fn write(&self, dir: &Path) -> Result<PathBuf> {
+        let first = self.spans.iter().map(|span| span.started).min().unwrap_or(0);
+        let last = self
+            .spans
+            .iter()
+            .map(|span| span.ended)
+            .max()
+            .unwrap_or(first);
+        let total = (last - first).max(1) as f64;
+
+        let mut rows = String::new();
+        for span in &self.spans {
+            let left = (span.started - first) as f64 / total * 100.;
+            let width = ((span.ended - span.started) as f64 / total * 100.).max(0.1);
+            let duration = span.ended - span.started;
+            rows.push_str(&format!(
+                r#"<div class="row"><span class="label">{node_type} {node_id}</span><span class="track"><span class="bar" style="left:{left:.2}%;width:{width:.2}%" title="{duration}ms"></span></span><span class="duration">{duration}ms</span></div>
+"#,
+                node_type = span.node_type,
+                node_id = span.node_id,
+            ));
+        }
+
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Stencila execution profile</title>
+<style>
+  body {{ font-family: monospace; margin: 1em; }}
+  .row {{ display: flex; align-items: center; margin: 2px 0; }}
+  .label {{ flex: 0 0 24em; overflow: hidden; text-overflow: ellipsis; white-space: nowrap; }}
+  .track {{ flex: 1; position: relative; height: 1em; background: #eee; }}
+  .bar {{ position: absolute; height: 100%; background: #66f; }}
+  .duration {{ flex: 0 0 6em; text-align: right; }}
+</style>
+</head>
+<body>
+<h1>Execution profile</h1>
+<p>Total: {total}ms over {count} nodes</p>
+{rows}</body>
+</html>
+"#,
+            total = last - first,
+            count = self.spans.len(),
+        );
+
+        let dir = dir.join(".stencila");
+        create_dir_all(&dir)?;
+
+        let path = dir.join("execution-profile.html");
+        write(&path, html)?;
+
+        Ok(path)
+    }
+}
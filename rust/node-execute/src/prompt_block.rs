@@ -132,6 +132,9 @@ impl Executable for PromptBlock {
         };
 
         let ended = Timestamp::now();
+        executor
+            .profile_span(self.node_type(), &node_id, &started, &ended)
+            .await;
         let messages = (!messages.is_empty()).then_some(messages);
 
         let status = execution_status(&messages);
@@ -1,8 +1,69 @@
-use schema::Section;
+use codec_text_trait::to_text;
+use schema::{diff, Block, PatchSlot, Section, SectionType};
 
-use crate::prelude::*;
+use crate::{prelude::*, GlossaryTerm};
 
 impl Executable for Section {
+    #[tracing::instrument(skip_all)]
+    async fn compile(&mut self, executor: &mut Executor) -> WalkControl {
+        let node_id = self.node_id();
+        tracing::trace!("Compiling Section {node_id}");
+
+        // If this is a supplementary materials section, and supplements
+        // maintain their own reference list, then compile the content with a
+        // supplement fork of the executor so that citation numbering restarts
+        if matches!(self.section_type, Some(SectionType::SupplementaryMaterials))
+            && executor.supplement_references
+        {
+            let mut fork = executor.fork_for_supplement();
+            if let Err(error) = self.content.walk_async(&mut fork).await {
+                tracing::error!("While compiling supplement `content`: {error}")
+            }
+
+            // Break walk because content compiled above
+            return WalkControl::Break;
+        }
+
+        // If this is a glossary or index section then replace any content
+        // after the heading with the generated blocks
+        if let Some(Block::Heading(heading)) = self.content.first() {
+            let title = to_text(&heading.content).trim().to_lowercase();
+            let generated = if title == "glossary" && !executor.glossary.is_empty() {
+                Some(vec![Block::List(GlossaryTerm::into_list(
+                    executor.glossary.clone(),
+                ))])
+            } else if title == "index" && !executor.index_terms.is_empty() {
+                Some(executor.index_blocks())
+            } else {
+                None
+            };
+
+            if let Some(generated) = generated {
+                let mut content = vec![self.content[0].clone()];
+                content.extend(generated);
+
+                // Diff the new content with the current so that a patch is
+                // only sent, and node ids only change, when necessary
+                match diff(&self.content, &content, None, None) {
+                    Ok(mut patch) => {
+                        if !patch.ops.is_empty() {
+                            self.content = content;
+                            patch.node_id = Some(node_id);
+                            patch.prepend_paths(vec![PatchSlot::Property(NodeProperty::Content)]);
+                            executor.send_patch(patch);
+                        }
+                    }
+                    Err(error) => {
+                        tracing::error!("While diffing generated section content: {error}")
+                    }
+                }
+            }
+        }
+
+        // Continue walk over content
+        WalkControl::Continue
+    }
+
     #[tracing::instrument(skip_all)]
     async fn prepare(&mut self, executor: &mut Executor) -> WalkControl {
         tracing::trace!("Preparing Section {}", self.node_id());
@@ -1,3 +1,4 @@
+use codec_text_trait::to_text;
 use schema::{CompilationMessage, StyledInline};
 
 use crate::prelude::*;
@@ -7,6 +8,15 @@ impl Executable for StyledInline {
     async fn compile(&mut self, executor: &mut Executor) -> WalkControl {
         let node_id = self.node_id();
 
+        // Record a marked index term so that an index section, if any, can
+        // be rendered. Done before the digest check below because the
+        // executor's index terms are rebuilt on each compile.
+        if self.code.trim() == "index" {
+            executor
+                .index_terms
+                .push((to_text(&self.content), node_id.clone()));
+        }
+
         let compilation_digest = parsers::parse(
             &self.code,
             self.style_language.as_deref().unwrap_or_default(),
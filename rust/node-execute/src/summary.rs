@@ -0,0 +1,179 @@
+//! A summary of the execution status of a document
+//!
+//! A summary counts the nodes that succeeded, failed, and were skipped in the
+//! last execution of a document, and lists the status, duration and messages
+//! of each executed node. Useful for checking the result of an execution,
+//! for example in continuous integration, without grepping logs.
+
+use common::serde::Serialize;
+use schema::{
+    Block, Duration, ExecutionMessage, ExecutionStatus, Inline, Node, NodeId, NodeType, TimeUnit,
+    Visitor, WalkControl,
+};
+
+/// A summary of the execution status of the nodes in a document
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase", crate = "common::serde")]
+pub struct ExecutionSummary {
+    /// The number of nodes that succeeded, including with warnings
+    pub succeeded: usize,
+
+    /// The number of nodes that failed with errors or exceptions
+    pub failed: usize,
+
+    /// The number of nodes that were skipped, locked or rejected
+    pub skipped: usize,
+
+    /// The sum of the execution durations of the nodes, in milliseconds
+    pub duration: u64,
+
+    /// The execution status of each node, in document order
+    pub nodes: Vec<NodeSummary>,
+}
+
+/// A summary of the execution status of a node
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase", crate = "common::serde")]
+pub struct NodeSummary {
+    /// The type of the node
+    pub node_type: NodeType,
+
+    /// The id of the node
+    pub node_id: NodeId,
+
+    /// The execution status of the node
+    pub status: ExecutionStatus,
+
+    /// The duration of the last execution of the node
+    pub duration: Option<Duration>,
+
+    /// Any messages generated while executing the node
+    pub messages: Vec<ExecutionMessage>,
+}
+
+/// Summarize the execution status of the nodes in a root node
+///
+/// Should be called after the root node has been executed. Nodes that have
+/// no execution status (e.g. because they have never been executed) are not
+/// included in the summary.
+pub(crate) fn summarize(root: &Node) -> ExecutionSummary {
+    let mut summarizer = Summarizer::default();
+    root.walk(&mut summarizer);
+    summarizer.summary
+}
+
+/// A visitor that collects the execution status of nodes into a summary
+#[derive(Default)]
+struct Summarizer {
+    /// The summary being collected
+    summary: ExecutionSummary,
+}
+
+impl Summarizer {
+    /// Record the execution status of a node, if it has one
+    fn record(
+        &mut self,
+        node_type: NodeType,
+        node_id: NodeId,
+        status: &Option<ExecutionStatus>,
+        duration: &Option<Duration>,
+        messages: &Option<Vec<ExecutionMessage>>,
+    ) {
+        let Some(status) = status else {
+            return;
+        };
+
+        match status {
+            ExecutionStatus::Succeeded | ExecutionStatus::Warnings => {
+                self.summary.succeeded += 1;
+            }
+            ExecutionStatus::Errors | ExecutionStatus::Exceptions => {
+                self.summary.failed += 1;
+            }
+            ExecutionStatus::Skipped | ExecutionStatus::Locked | ExecutionStatus::Rejected => {
+                self.summary.skipped += 1;
+            }
+            _ => {}
+        }
+
+        if let Some(duration) = duration {
+            let milliseconds = match duration.time_unit {
+                TimeUnit::Second => duration.value.saturating_mul(1_000),
+                TimeUnit::Millisecond => duration.value,
+                TimeUnit::Microsecond => duration.value / 1_000,
+                TimeUnit::Nanosecond => duration.value / 1_000_000,
+                // Larger and smaller units are not used for execution durations
+                _ => 0,
+            };
+            self.summary.duration += milliseconds.max(0) as u64;
+        }
+
+        self.summary.nodes.push(NodeSummary {
+            node_type,
+            node_id,
+            status: status.clone(),
+            duration: duration.clone(),
+            messages: messages.clone().unwrap_or_default(),
+        });
+    }
+}
+
+impl Visitor for Summarizer {
+    fn visit_block(&mut self, block: &Block) -> WalkControl {
+        match block {
+            Block::CodeChunk(node) => self.record(
+                node.node_type(),
+                node.node_id(),
+                &node.options.execution_status,
+                &node.options.execution_duration,
+                &node.options.execution_messages,
+            ),
+            Block::ForBlock(node) => self.record(
+                node.node_type(),
+                node.node_id(),
+                &node.options.execution_status,
+                &node.options.execution_duration,
+                &node.options.execution_messages,
+            ),
+            Block::IfBlock(node) => self.record(
+                node.node_type(),
+                node.node_id(),
+                &node.options.execution_status,
+                &node.options.execution_duration,
+                &node.options.execution_messages,
+            ),
+            Block::IncludeBlock(node) => self.record(
+                node.node_type(),
+                node.node_id(),
+                &node.options.execution_status,
+                &node.options.execution_duration,
+                &node.options.execution_messages,
+            ),
+            Block::InstructionBlock(node) => self.record(
+                node.node_type(),
+                node.node_id(),
+                &node.options.execution_status,
+                &node.options.execution_duration,
+                &node.options.execution_messages,
+            ),
+            _ => {}
+        }
+
+        WalkControl::Continue
+    }
+
+    fn visit_inline(&mut self, inline: &Inline) -> WalkControl {
+        match inline {
+            Inline::CodeExpression(node) => self.record(
+                node.node_type(),
+                node.node_id(),
+                &node.options.execution_status,
+                &node.options.execution_duration,
+                &node.options.execution_messages,
+            ),
+            _ => {}
+        }
+
+        WalkControl::Continue
+    }
+}
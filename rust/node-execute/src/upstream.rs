@@ -0,0 +1,132 @@
+//! Expansion of target node ids to include their upstream dependencies
+//!
+//! Used for "run-to-node" execution: when specific nodes are executed with
+//! the `upstream` option, the nodes that write the variables read, directly
+//! or transitively, by the target nodes are also executed, like "Run all
+//! above" in notebook interfaces.
+
+use std::collections::HashSet;
+
+use schema::{Block, Inline, Node, NodeId, Visitor, WalkControl};
+
+/// Expand a set of target node ids to include their upstream dependencies
+///
+/// Walks over the root node collecting the variables read and written by each
+/// executable code node, in document order, and then walks backwards over
+/// those nodes marking any that write a variable needed by a node already
+/// marked. Returns the target node ids plus the ids of their upstream
+/// dependencies, in document order.
+pub(crate) fn expand(root: &Node, node_ids: &[NodeId]) -> Vec<NodeId> {
+    let mut collector = Collector::default();
+    root.walk(&mut collector);
+
+    let targets: HashSet<&NodeId> = node_ids.iter().collect();
+    let mut needed_variables: HashSet<String> = HashSet::new();
+    let mut needed_nodes: HashSet<NodeId> = HashSet::new();
+
+    for usage in collector.usages.iter().rev() {
+        let needed = targets.contains(&usage.node_id)
+            || usage
+                .writes
+                .iter()
+                .any(|variable| needed_variables.contains(variable));
+        if needed {
+            needed_nodes.insert(usage.node_id.clone());
+            needed_variables.extend(usage.reads.iter().cloned());
+        }
+    }
+
+    collector
+        .usages
+        .into_iter()
+        .map(|usage| usage.node_id)
+        .filter(|node_id| needed_nodes.contains(node_id))
+        .chain(
+            // Retain any target node ids that are not code nodes (e.g. an
+            // `InstructionBlock`) so that they are still executed
+            node_ids
+                .iter()
+                .filter(|node_id| !needed_nodes.contains(node_id))
+                .cloned(),
+        )
+        .collect()
+}
+
+/// The variables read and written by an executable code node
+struct Usage {
+    /// The id of the node
+    node_id: NodeId,
+
+    /// The names of the variables read by the node
+    reads: Vec<String>,
+
+    /// The names of the variables written by the node
+    writes: Vec<String>,
+}
+
+/// A visitor that collects variable usage by executable code nodes
+#[derive(Default)]
+struct Collector {
+    /// The variable usages of nodes, in document order
+    usages: Vec<Usage>,
+}
+
+impl Collector {
+    /// Record the variable usage of a node with code
+    fn record(&mut self, node_id: NodeId, code: &str, language: Option<&str>) {
+        let info = parsers::parse(code, language.unwrap_or_default());
+        self.usages.push(Usage {
+            node_id,
+            reads: info.variables_read,
+            writes: info.variables_written,
+        });
+    }
+}
+
+impl Visitor for Collector {
+    fn visit_block(&mut self, block: &Block) -> WalkControl {
+        match block {
+            Block::CodeChunk(node) => self.record(
+                node.node_id(),
+                &node.code,
+                node.programming_language.as_deref(),
+            ),
+            Block::ForBlock(node) => {
+                let mut usage = {
+                    let info = parsers::parse(
+                        &node.code,
+                        node.programming_language.as_deref().unwrap_or_default(),
+                    );
+                    Usage {
+                        node_id: node.node_id(),
+                        reads: info.variables_read,
+                        writes: info.variables_written,
+                    }
+                };
+                usage.writes.push(node.variable.trim().to_string());
+                self.usages.push(usage);
+            }
+            _ => {}
+        }
+
+        WalkControl::Continue
+    }
+
+    fn visit_inline(&mut self, inline: &Inline) -> WalkControl {
+        match inline {
+            Inline::CodeExpression(node) => self.record(
+                node.node_id(),
+                &node.code,
+                node.programming_language.as_deref(),
+            ),
+            Inline::Parameter(node) => self.usages.push(Usage {
+                node_id: node.node_id(),
+                reads: Vec::new(),
+                writes: vec![node.name.clone()],
+            }),
+            _ => {}
+        }
+
+        WalkControl::Continue
+    }
+}
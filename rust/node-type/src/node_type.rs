@@ -2,10 +2,10 @@
 
 use common::{
     serde::Serialize,
-    strum::{Display, EnumIter, EnumString},
+    strum::{Display, EnumIter, EnumString, IntoStaticStr},
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Display, EnumString, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Display, EnumString, EnumIter, IntoStaticStr)]
 #[serde(crate = "common::serde")]
 #[strum(crate = "common::strum")]
 pub enum NodeType {
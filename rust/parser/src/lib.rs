@@ -17,6 +17,15 @@ pub struct ParseInfo {
 
     /// Tags parsed from comments in the code
     pub execution_tags: Option<Vec<ExecutionTag>>,
+
+    /// The names of variables read by the code
+    ///
+    /// Used to determine dependencies between executable nodes so that
+    /// nodes downstream of a change can also be re-executed.
+    pub variables_read: Vec<String>,
+
+    /// The names of variables written by the code
+    pub variables_written: Vec<String>,
 }
 
 /// A parser of code in a programming language
@@ -46,7 +55,7 @@ pub trait Parser: Sync + Send {
     fn execution_tags(&self, code: &str) -> Option<Vec<ExecutionTag>> {
         static REGEX: Lazy<Regex> = Lazy::new(|| {
             Regex::new(
-                r"@(pure|impure|assigns|changes|uses|imports|reads|writes|watches)\s*([^\n]*)",
+                r"@(pure|impure|assigns|changes|uses|imports|reads|writes|watches|timeout|eval|error|include)\s*([^\n]*)",
             )
             .expect("Invalid regex")
         });
@@ -71,6 +80,51 @@ pub trait Parser: Sync + Send {
         }
     }
 
+    /// Extract the names of variables read by some code
+    ///
+    /// This default implementation is deliberately coarse: it matches all
+    /// identifiers in the code, including function names and other identifiers
+    /// that are not variables. Because only identifiers that are written by
+    /// another node create a dependency, the extra identifiers have no effect.
+    /// Language specific parsers should override this method.
+    fn variables_read(&self, code: &str) -> Vec<String> {
+        static REGEX: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"[a-zA-Z_][a-zA-Z0-9_]*").expect("Invalid regex"));
+
+        let mut variables = Vec::new();
+        for matched in REGEX.find_iter(code) {
+            let name = matched.as_str().to_string();
+            if !variables.contains(&name) {
+                variables.push(name);
+            }
+        }
+        variables
+    }
+
+    /// Extract the names of variables written by some code
+    ///
+    /// This default implementation uses a regex to match simple assignment
+    /// statements at the start of a line, including `let`, `const` and `var`
+    /// declarations, augmented assignments, and R's arrow assignment.
+    /// Language specific parsers should override this method.
+    fn variables_written(&self, code: &str) -> Vec<String> {
+        static REGEX: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(
+                r"(?m)^\s*(?:let\s+|const\s+|var\s+|global\s+)?([a-zA-Z_][a-zA-Z0-9_]*)\s*(?:<<?-|[+\-*/]?=[^=~>])",
+            )
+            .expect("Invalid regex")
+        });
+
+        let mut variables = Vec::new();
+        for captures in REGEX.captures_iter(code) {
+            let name = captures[1].to_string();
+            if !variables.contains(&name) {
+                variables.push(name);
+            }
+        }
+        variables
+    }
+
     /// Parse code in a language
     fn parse(&self, code: &str, format: &Format) -> ParseInfo;
 }
@@ -88,12 +142,31 @@ impl Parser for DefaultParser {
     }
 
     fn parse(&self, code: &str, format: &Format) -> ParseInfo {
+        let execution_tags = self.execution_tags(code);
+
+        // Allow tags such as `@uses` and `@assigns` to augment the
+        // variables detected in the code itself
+        let mut variables_read = self.variables_read(code);
+        let mut variables_written = self.variables_written(code);
+        for tag in execution_tags.iter().flatten() {
+            let variables = match tag.name.as_str() {
+                "uses" | "reads" | "watches" => &mut variables_read,
+                "assigns" | "writes" | "changes" => &mut variables_written,
+                _ => continue,
+            };
+            if !variables.contains(&tag.value) {
+                variables.push(tag.value.clone());
+            }
+        }
+
         ParseInfo {
             compilation_digest: CompilationDigest {
                 state_digest: self.state_digest(code, format),
                 ..Default::default()
             },
-            execution_tags: self.execution_tags(code),
+            execution_tags,
+            variables_read,
+            variables_written,
         }
     }
 }
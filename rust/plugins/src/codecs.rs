@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use codec::{schema::Node, status::Status, Codec, EncodeInfo, EncodeOptions};
+use codec::{
+    format::Format, schema::Node, status::Status, Codec, CodecSupport, DecodeInfo, DecodeOptions,
+    EncodeInfo, EncodeOptions,
+};
 use common::{
     async_trait::async_trait,
     eyre::{bail, Result},
@@ -8,7 +11,7 @@ use common::{
     tokio::sync::Mutex,
 };
 
-use crate::{plugins, Plugin, PluginInstance};
+use crate::{installed, plugins, Plugin, PluginInstance};
 
 /// A codec provided by a plugin
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -17,6 +20,14 @@ pub struct PluginCodec {
     /// The name of the codec
     name: String,
 
+    /// The formats that the codec can decode from
+    #[serde(alias = "from_format", default)]
+    from_formats: Vec<Format>,
+
+    /// The formats that the codec can encode to
+    #[serde(alias = "to_format", default)]
+    to_formats: Vec<Format>,
+
     /// The plugin that provides this codec
     ///
     /// Used to be able to create a plugin instance, which in
@@ -51,6 +62,62 @@ impl Codec for PluginCodec {
         Status::Alpha
     }
 
+    fn supports_from_format(&self, format: &Format) -> CodecSupport {
+        if self.from_formats.contains(format) {
+            CodecSupport::LowLoss
+        } else {
+            CodecSupport::None
+        }
+    }
+
+    fn supports_to_format(&self, format: &Format) -> CodecSupport {
+        if self.to_formats.contains(format) {
+            CodecSupport::LowLoss
+        } else {
+            CodecSupport::None
+        }
+    }
+
+    async fn from_str(
+        &self,
+        str: &str,
+        _options: Option<DecodeOptions>,
+    ) -> Result<(Node, DecodeInfo)> {
+        // Create the plugin instance if necessary
+        let mut guard = self.plugin_instance.lock().await;
+        let instance = match &mut *guard {
+            Some(instance) => instance,
+            None => {
+                let Some(plugin) = self.plugin.as_ref() else {
+                    bail!("Not bound yet")
+                };
+
+                let inst = plugin.start(None).await?;
+                *guard = Some(inst);
+                guard.as_mut().expect("should have been set above")
+            }
+        };
+
+        // Call the plugin's from_string method
+        #[derive(Serialize)]
+        #[serde(crate = "common::serde")]
+        struct Params<'content> {
+            codec: String,
+            content: &'content str,
+        }
+        let node: Node = instance
+            .call(
+                "codec_from_string",
+                Params {
+                    codec: self.name.clone(),
+                    content: str,
+                },
+            )
+            .await?;
+
+        Ok((node, DecodeInfo::none()))
+    }
+
     async fn to_string(
         &self,
         node: &Node,
@@ -100,3 +167,15 @@ pub async fn list() -> Result<Vec<Box<dyn Codec>>> {
         .flat_map(|plugin| plugin.codecs())
         .collect())
 }
+
+/// List the codecs provided by installed and enabled plugins
+///
+/// Unlike [`list`], does not fetch the plugin registry, so does not require
+/// network access and can be used in synchronous contexts such as the
+/// codecs registry's `list` function.
+pub fn list_installed() -> Vec<Box<dyn Codec>> {
+    installed()
+        .into_iter()
+        .flat_map(|plugin| plugin.codecs())
+        .collect()
+}
@@ -1,7 +1,7 @@
 use std::{
     collections::HashMap,
     env,
-    fs::{create_dir_all, remove_file, File},
+    fs::{create_dir_all, read_dir, remove_file, File},
     net::TcpListener,
     path::{Path, PathBuf},
     process::Stdio,
@@ -556,6 +556,7 @@ impl Plugin {
 pub enum PluginRuntime {
     Python,
     Node,
+    Wasm,
 }
 
 impl PluginRuntime {
@@ -570,10 +571,16 @@ impl PluginRuntime {
             }
         }
 
+        let name = match self {
+            // WASM plugins are run using the `wasmtime` WASI runtime
+            PluginRuntime::Wasm => "wasmtime".to_string(),
+            _ => self.to_string(),
+        };
+
         let name = if cfg!(windows) {
-            format!("{self}.exe")
+            format!("{name}.exe")
         } else {
-            self.to_string()
+            name
         };
 
         Ok(which(name)?)
@@ -596,6 +603,11 @@ impl PluginRuntime {
         let version = match &self {
             PluginRuntime::Python => output.strip_prefix("Python "),
             PluginRuntime::Node => output.strip_prefix('v'),
+            // Output is like `wasmtime-cli 9.0.1` or `wasmtime 24.0.0 (e452b2d3c 2024-08-20)`
+            PluginRuntime::Wasm => output
+                .strip_prefix("wasmtime-cli")
+                .or_else(|| output.strip_prefix("wasmtime"))
+                .and_then(|rest| rest.split_whitespace().next()),
         }
         .ok_or_else(|| eyre!("Unable to extract version using {:?}", path))?
         .trim();
@@ -611,6 +623,7 @@ impl PluginRuntime {
         match self {
             PluginRuntime::Node => Self::install_node(url, dir).await,
             PluginRuntime::Python => Self::install_python(url, dir).await,
+            PluginRuntime::Wasm => Self::install_wasm(url, dir).await,
         }
     }
 
@@ -660,12 +673,37 @@ impl PluginRuntime {
         Ok(())
     }
 
+    /// Install a WASM plugin
+    ///
+    /// Downloads the plugin's WebAssembly module (e.g. a WASI module built
+    /// using `cargo build --target wasm32-wasip1` or `componentize-py`)
+    /// into the plugin's directory.
+    async fn install_wasm(url: &Url, dir: &Path) -> Result<()> {
+        let response = reqwest::get(url.clone()).await?;
+        if let Err(error) = response.error_for_status_ref() {
+            let message = response.text().await?;
+            bail!("While downloading WASM plugin: {error}: {message}");
+        }
+
+        let filename = url
+            .path_segments()
+            .and_then(|segments| segments.last())
+            .filter(|name| name.ends_with(".wasm"))
+            .unwrap_or("plugin.wasm");
+
+        let bytes = response.bytes().await?;
+        tokio::fs::write(dir.join(filename), bytes).await?;
+
+        Ok(())
+    }
+
     /// Build the command to run the plugin.
     /// This should provide the correct binary and arguments to run the plugin in this dir.
     async fn get_command(&self, command_str: &str, dir: &Path) -> Result<Command> {
         match self {
             PluginRuntime::Node => Self::get_command_node(command_str, dir).await,
             PluginRuntime::Python => Self::get_command_python(command_str, dir).await,
+            PluginRuntime::Wasm => Self::get_command_wasm(command_str, dir).await,
         }
     }
 
@@ -708,6 +746,28 @@ impl PluginRuntime {
         command.args(args).current_dir(dir);
         Ok(command)
     }
+
+    async fn get_command_wasm(command_str: &str, dir: &Path) -> Result<Command> {
+        // For WASM, the command is the name of the WebAssembly file within the
+        // plugin directory, followed by any arguments for the guest.
+        let mut args = command_str.split(' ').collect_vec();
+        let module = args.remove(0);
+
+        let program = PluginRuntime::Wasm.path(None)?;
+
+        // The guest inherits environment variables (so that it can read
+        // `STENCILA_TRANSPORT` etc) and is granted access to the plugin
+        // directory only.
+        let mut command = Command::new(program);
+        command
+            .arg("run")
+            .args(["-S", "inherit-env"])
+            .args(["--dir", "."])
+            .arg(module)
+            .args(args)
+            .current_dir(dir);
+        Ok(command)
+    }
 }
 
 /// An operating system platform that a plugin supports
@@ -805,6 +865,32 @@ async fn plugins() -> Vec<Plugin> {
     }
 }
 
+/// Get a list of the plugins installed and enabled on the current machine
+///
+/// Unlike [`plugins`], does not fetch the plugin registry, only reading the
+/// manifests of plugins in the plugins directory. As such it is infallible,
+/// does not require network access, and can be used in synchronous contexts
+/// (e.g. the codecs registry's `list` function).
+fn installed() -> Vec<Plugin> {
+    let Ok(dir) = get_app_dir(DirType::Plugins, false) else {
+        return vec![];
+    };
+    let Ok(entries) = read_dir(dir) else {
+        return vec![];
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir() || entry.path().is_symlink())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            matches!(Plugin::read_enabled(&name), Ok(PluginEnabled::Yes))
+                .then(|| Plugin::read_manifest(&name).ok())
+                .flatten()
+        })
+        .collect()
+}
+
 /// A list of plugins
 #[derive(Default, Deref, Serialize, Deserialize)]
 #[serde(crate = "common::serde")]
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use cli_utils::{
     table::{self, Attribute, Cell, Color},
     Code, ToStdout,
@@ -7,10 +9,14 @@ use model::{
     common::{
         clap::{self, Args, Parser, Subcommand},
         eyre::Result,
+        itertools::Itertools,
+        serde_json,
     },
     schema::{InstructionMessage, InstructionType, Node, Prompt, StringOrNumber},
 };
 
+use crate::eval::EvalSuite;
+
 /// Manage prompts
 #[derive(Debug, Parser)]
 pub struct Cli {
@@ -23,6 +29,7 @@ enum Command {
     List(List),
     Show(Show),
     Select(Select),
+    Eval(Eval),
     Update(Update),
     Reset(Reset),
 }
@@ -38,6 +45,7 @@ impl Cli {
             Command::List(list) => list.run().await?,
             Command::Show(show) => show.run().await?,
             Command::Select(select) => select.run().await?,
+            Command::Eval(eval) => eval.run().await?,
             Command::Update(update) => update.run().await?,
             Command::Reset(update) => update.run().await?,
         }
@@ -150,6 +158,70 @@ impl Select {
     }
 }
 
+/// Evaluate prompts against a suite of instruction examples
+///
+/// Runs each case in the suite against each combination of models
+/// and prompts and reports the scores for comparison
+#[derive(Debug, Args)]
+struct Eval {
+    /// The path of the evaluation suite (YAML file)
+    suite: PathBuf,
+
+    /// Output the results as JSON, rather than as a table
+    #[arg(long)]
+    json: bool,
+}
+
+impl Eval {
+    async fn run(self) -> Result<()> {
+        let suite = EvalSuite::read(&self.suite).await?;
+        let results = suite.run().await?;
+
+        if self.json {
+            Code::new(Format::Json, &serde_json::to_string_pretty(&results)?).to_stdout();
+            return Ok(());
+        }
+
+        let mut table = table::new();
+        table.set_header(["Model", "Prompt", "Case", "Scores", "Mean"]);
+
+        for result in results {
+            let scores = if let Some(error) = &result.error {
+                Cell::new(error).fg(Color::Red)
+            } else {
+                Cell::new(
+                    result
+                        .scores
+                        .iter()
+                        .map(|score| format!("{:?}: {:.2}", score.scorer, score.score))
+                        .join(", "),
+                )
+            };
+
+            let mean = match result.mean_score() {
+                Some(mean) => Cell::new(format!("{mean:.2}")).fg(if mean >= 0.5 {
+                    Color::Green
+                } else {
+                    Color::Red
+                }),
+                None => Cell::new(""),
+            };
+
+            table.add_row([
+                Cell::new(&result.model),
+                Cell::new(&result.prompt),
+                Cell::new(&result.case).add_attribute(Attribute::Bold),
+                scores,
+                mean,
+            ]);
+        }
+
+        println!("{table}");
+
+        Ok(())
+    }
+}
+
 /// Update builtin prompts
 #[derive(Debug, Args)]
 struct Update {}
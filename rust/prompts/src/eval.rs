@@ -0,0 +1,346 @@
+//! Evaluation harness for prompts
+//!
+//! Runs a suite of instruction examples against one or more models and/or
+//! prompts, scores the generated outputs, and produces a comparison report.
+//! Intended to allow changes to prompts in this repository to be
+//! regression-tested.
+
+use std::{path::Path, str::FromStr};
+
+use codec_markdown_trait::to_markdown;
+use codecs::{DecodeOptions, Format};
+use common::{
+    eyre::{eyre, Result},
+    itertools::Itertools,
+    serde::{Deserialize, Serialize},
+    serde_yaml,
+    tokio::fs::read_to_string,
+    tracing,
+};
+
+use model::{
+    schema::{
+        Article, InstructionMessage, InstructionModel, InstructionType, Node, NodeType,
+    },
+    ModelOutput, ModelOutputKind, ModelTask,
+};
+
+/// A suite of prompt evaluation cases
+///
+/// Usually deserialized from a YAML file with a list of `cases` to run
+/// against each combination of `models` and `prompts`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", crate = "common::serde")]
+pub struct EvalSuite {
+    /// The name of the suite
+    pub name: String,
+
+    /// A description of the suite
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The ids (or id patterns) of the models to evaluate
+    ///
+    /// Defaults to a single entry for the best available model.
+    #[serde(default)]
+    pub models: Vec<String>,
+
+    /// The ids of the prompts to evaluate
+    ///
+    /// If empty, a prompt will be selected for each case as is done
+    /// when executing an instruction.
+    #[serde(default)]
+    pub prompts: Vec<String>,
+
+    /// The id of the model to use as a judge for rubric scoring
+    #[serde(default)]
+    pub judge: Option<String>,
+
+    /// The evaluation cases in the suite
+    pub cases: Vec<EvalCase>,
+}
+
+/// An evaluation case: an example instruction and expectations for its output
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", crate = "common::serde")]
+pub struct EvalCase {
+    /// The name of the case
+    pub name: String,
+
+    /// The type of instruction
+    #[serde(default)]
+    pub instruction_type: Option<InstructionType>,
+
+    /// The instruction message
+    pub message: String,
+
+    /// The expected output for exact match scoring
+    ///
+    /// Whitespace is trimmed and collapsed before comparison.
+    #[serde(default)]
+    pub expected: Option<String>,
+
+    /// The expected node type of the first block of the output
+    /// for schema validity scoring e.g. `Table` or `CodeChunk`
+    #[serde(default)]
+    pub node_type: Option<String>,
+
+    /// A rubric for scoring the output with a judge model
+    #[serde(default)]
+    pub rubric: Option<String>,
+}
+
+impl EvalSuite {
+    /// Read a suite from a YAML file
+    pub async fn read(path: &Path) -> Result<Self> {
+        let yaml = read_to_string(path).await?;
+        Ok(serde_yaml::from_str(&yaml)?)
+    }
+
+    /// Run the suite, returning a result for each (model, prompt, case) combination
+    pub async fn run(&self) -> Result<Vec<EvalResult>> {
+        let models = if self.models.is_empty() {
+            vec![String::new()]
+        } else {
+            self.models.clone()
+        };
+
+        let prompts = if self.prompts.is_empty() {
+            vec![String::new()]
+        } else {
+            self.prompts.clone()
+        };
+
+        let mut results = Vec::new();
+        for model in &models {
+            for prompt in &prompts {
+                for case in &self.cases {
+                    tracing::debug!(
+                        "Evaluating case `{}` with model `{model}` and prompt `{prompt}`",
+                        case.name
+                    );
+                    results.push(self.run_case(model, prompt, case).await);
+                }
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Run a single case against a model and prompt
+    async fn run_case(&self, model: &str, prompt: &str, case: &EvalCase) -> EvalResult {
+        let mut result = EvalResult {
+            model: model.to_string(),
+            prompt: prompt.to_string(),
+            case: case.name.clone(),
+            scores: Vec::new(),
+            error: None,
+        };
+
+        let output = match self.generate(model, prompt, case).await {
+            Ok(output) => output,
+            Err(error) => {
+                result.error = Some(error.to_string());
+                return result;
+            }
+        };
+
+        if let Some(expected) = &case.expected {
+            result.scores.push(EvalScore {
+                scorer: EvalScorer::ExactMatch,
+                score: exact_match(expected, &output),
+            });
+        }
+
+        if let Some(node_type) = &case.node_type {
+            let score = match schema_validity(node_type, &output).await {
+                Ok(score) => score,
+                Err(error) => {
+                    result.error = Some(error.to_string());
+                    return result;
+                }
+            };
+            result.scores.push(EvalScore {
+                scorer: EvalScorer::SchemaValidity,
+                score,
+            });
+        }
+
+        if let Some(rubric) = &case.rubric {
+            let score = match self.judge(rubric, case, &output).await {
+                Ok(score) => score,
+                Err(error) => {
+                    result.error = Some(error.to_string());
+                    return result;
+                }
+            };
+            result.scores.push(EvalScore {
+                scorer: EvalScorer::Rubric,
+                score,
+            });
+        }
+
+        result
+    }
+
+    /// Generate an output for a case using a model and prompt
+    async fn generate(&self, model: &str, prompt: &str, case: &EvalCase) -> Result<String> {
+        let instruction_type = case.instruction_type.clone().unwrap_or_default();
+
+        let prompt = if prompt.is_empty() {
+            super::select(
+                &instruction_type,
+                &Some(InstructionMessage::from(case.message.clone())),
+                &None,
+                &None,
+            )
+            .await?
+        } else {
+            super::get(prompt, &instruction_type).await?
+        };
+
+        let system_prompt = to_markdown(&prompt.content);
+
+        let messages = vec![
+            InstructionMessage::system(system_prompt, None),
+            InstructionMessage::user(case.message.clone(), None),
+        ];
+
+        let instruction_model = (!model.is_empty()).then(|| InstructionModel {
+            id_pattern: Some(model.to_string()),
+            ..Default::default()
+        });
+
+        let task = ModelTask::new(instruction_type, instruction_model, messages);
+
+        let ModelOutput { kind, content, .. } = models::perform_task(task).await?;
+        match kind {
+            ModelOutputKind::Text => Ok(content),
+            ModelOutputKind::Url => Ok(content),
+        }
+    }
+
+    /// Score an output against a rubric using a judge model
+    async fn judge(&self, rubric: &str, case: &EvalCase, output: &str) -> Result<f64> {
+        let system_prompt = "You are an impartial judge of the quality of content generated by an AI assistant. Score the content against the rubric provided. Respond with a single number between 0.0 (fails the rubric entirely) and 1.0 (meets the rubric fully). Do not provide any other text.";
+
+        let user_prompt = format!(
+            "Instruction:\n\n{}\n\nRubric:\n\n{rubric}\n\nContent:\n\n{output}",
+            case.message
+        );
+
+        let messages = vec![
+            InstructionMessage::system(system_prompt, None),
+            InstructionMessage::user(user_prompt, None),
+        ];
+
+        let instruction_model = self.judge.as_ref().map(|judge| InstructionModel {
+            id_pattern: Some(judge.clone()),
+            ..Default::default()
+        });
+
+        let task = ModelTask::new(InstructionType::Create, instruction_model, messages);
+
+        let ModelOutput { content, .. } = models::perform_task(task).await?;
+
+        content
+            .trim()
+            .parse()
+            .map(|score: f64| score.clamp(0., 1.))
+            .map_err(|..| eyre!("Judge model did not respond with a number: {content}"))
+    }
+}
+
+/// The scorers that can be applied to an output
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", crate = "common::serde")]
+pub enum EvalScorer {
+    /// Normalized string equality with the expected output
+    ExactMatch,
+    /// Output decodes to the Stencila schema with the expected node type
+    SchemaValidity,
+    /// Scored against a rubric by a judge model
+    Rubric,
+}
+
+/// A score given to an output by a scorer
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", crate = "common::serde")]
+pub struct EvalScore {
+    /// The scorer that produced the score
+    pub scorer: EvalScorer,
+
+    /// The score, between 0.0 and 1.0
+    pub score: f64,
+}
+
+/// The result of running a case against a model and prompt
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", crate = "common::serde")]
+pub struct EvalResult {
+    /// The id (or id pattern) of the model evaluated
+    pub model: String,
+
+    /// The id of the prompt evaluated
+    pub prompt: String,
+
+    /// The name of the case evaluated
+    pub case: String,
+
+    /// The scores given to the output
+    pub scores: Vec<EvalScore>,
+
+    /// Any error that occurred while generating or scoring the output
+    pub error: Option<String>,
+}
+
+impl EvalResult {
+    /// The mean of the scores for the result
+    pub fn mean_score(&self) -> Option<f64> {
+        (!self.scores.is_empty())
+            .then(|| self.scores.iter().map(|score| score.score).sum::<f64>())
+            .map(|sum| sum / self.scores.len() as f64)
+    }
+}
+
+/// Score an output by normalized string equality with the expected output
+fn exact_match(expected: &str, output: &str) -> f64 {
+    fn normalize(text: &str) -> String {
+        text.split_whitespace().join(" ")
+    }
+
+    if normalize(expected) == normalize(output) {
+        1.
+    } else {
+        0.
+    }
+}
+
+/// Score an output by decoding it to the Stencila schema and checking
+/// the node type of the first block
+async fn schema_validity(node_type: &str, output: &str) -> Result<f64> {
+    let node_type = NodeType::from_str(node_type)
+        .map_err(|..| eyre!("Unknown node type for schema validity scoring: {node_type}"))?;
+
+    let node = match codecs::from_str(
+        output,
+        Some(DecodeOptions {
+            format: Some(Format::Markdown),
+            ..Default::default()
+        }),
+    )
+    .await
+    {
+        Ok(node) => node,
+        Err(..) => return Ok(0.),
+    };
+
+    let Node::Article(Article { content, .. }) = node else {
+        return Ok(0.);
+    };
+
+    Ok(match content.first() {
+        Some(block) if block.node_type() == node_type => 1.,
+        _ => 0.,
+    })
+}
@@ -4,6 +4,7 @@ use std::{
     cmp::Ordering,
     io::Cursor,
     path::{Path, PathBuf},
+    sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
 
@@ -16,16 +17,22 @@ use common::{
     futures::future::{join_all, try_join_all},
     glob::glob,
     itertools::Itertools,
+    once_cell::sync::Lazy,
     regex::Regex,
     reqwest::Client,
     serde::{Deserialize, Serialize},
     serde_json,
     tar::Archive,
-    tokio::fs::{create_dir_all, read_to_string, remove_dir_all, write},
+    tokio::{
+        self,
+        fs::{create_dir_all, read_to_string, remove_dir_all, write},
+        sync::{mpsc, Mutex},
+    },
     tracing,
 };
 use flate2::read::GzDecoder;
 use images::ensure_http_or_data_uri;
+use kernels::Kernels;
 use rust_embed::RustEmbed;
 
 use model::{
@@ -39,6 +46,7 @@ use model::{
 };
 
 pub mod cli;
+pub mod eval;
 
 // Re-export
 pub use prompt;
@@ -111,8 +119,9 @@ impl PromptInstance {
 pub async fn list() -> Vec<PromptInstance> {
     let futures = (0..=3).map(|provider| async move {
         let (provider, result) = match provider {
-            0 => ("builtin", list_builtin().await),
+            0 => ("workspace", list_workspace().await),
             1 => ("local", list_local().await),
+            2 => ("builtin", list_builtin().await),
             _ => return vec![],
         };
 
@@ -129,6 +138,15 @@ pub async fn list() -> Vec<PromptInstance> {
         .await
         .into_iter()
         .flatten()
+        // Providers are listed in priority order so that, for prompts with the
+        // same id, those in the workspace override local prompts, which in turn
+        // override builtin prompts
+        .unique_by(|prompt| {
+            prompt
+                .id
+                .clone()
+                .unwrap_or_else(|| prompt.path.to_string_lossy().to_string())
+        })
         .sorted_by(|a, b| {
             match a
                 .instruction_types
@@ -182,6 +200,22 @@ pub async fn list_builtin() -> Result<Vec<PromptInstance>> {
     list_dir(&dir).await
 }
 
+/// List any prompts in the current workspace
+///
+/// Walks up from the current working directory looking for a `prompts`
+/// directory so that prompts can be version-controlled alongside documents.
+async fn list_workspace() -> Result<Vec<PromptInstance>> {
+    let current_dir = std::env::current_dir()?;
+    for ancestor in current_dir.ancestors() {
+        let dir = ancestor.join("prompts");
+        if dir.exists() {
+            return list_dir(&dir).await;
+        }
+    }
+
+    Ok(Vec::new())
+}
+
 /// List any local prompts
 async fn list_local() -> Result<Vec<PromptInstance>> {
     let dir = get_app_dir(DirType::Prompts, false)?.join("local");
@@ -416,14 +450,46 @@ pub async fn select(
     prompt.ok_or_eyre("No prompts found for instruction")
 }
 
+/// Instructions added to the system prompt when the model is able to
+/// execute code snippets in a forked kernel
+const TOOL_USE_INSTRUCTIONS: &str = r#"
+
+## Code execution
+
+Before finalizing your answer, you may execute short code snippets to inspect data or the environment (e.g. to check the columns of a dataframe). To do so, respond with ONLY a fenced code block with the language of the code followed by the keyword `run` e.g.
+
+```python run
+data.columns
+```
+
+The result of the execution will be returned to you in the next message. When you are ready, finalize your answer without using the `run` keyword.
+"#;
+
+/// Regex for detecting a request by the model to execute a code snippet
+static TOOL_CALL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)^\s*```([a-zA-Z0-9-]+)[ \t]+run[ \t]*\n(.*?)\n?```\s*$")
+        .expect("invalid regex")
+});
+
 /// Execute an [`InstructionBlock`]
 pub async fn execute_instruction_block(
     mut instructors: Vec<AuthorRole>,
     prompter: AuthorRole,
     system_prompt: &str,
+    expected_node_types: &[String],
+    kernels: Option<Arc<Mutex<Kernels>>>,
+    content_sender: Option<mpsc::UnboundedSender<String>>,
     instruction: &InstructionBlock,
     dry_run: bool,
 ) -> Result<SuggestionBlock> {
+    // If kernels are available for the model to execute code snippets in
+    // then extend the system prompt with instructions for doing so
+    let system_prompt = if kernels.is_some() {
+        [system_prompt, TOOL_USE_INSTRUCTIONS].concat()
+    } else {
+        system_prompt.to_string()
+    };
+
     // Create a vector of messages beginning with the system message
     let mut messages = vec![InstructionMessage::system(
         system_prompt,
@@ -536,71 +602,165 @@ pub async fn execute_instruction_block(
     );
     task.dry_run = dry_run;
 
-    // Perform the task
+    // The maximum number of times to re-prompt the model when its output
+    // does not validate against the expected node types
+    const MAX_RETRIES: u32 = 2;
+
+    // The maximum number of code snippets the model can execute before
+    // finalizing its answer
+    const MAX_TOOL_CALLS: u32 = 5;
+
+    // Perform the task, executing any code snippets requested by the model,
+    // and re-prompting if the output does not have the expected node types
     let started = Timestamp::now();
-    let ModelOutput {
-        mut authors,
-        kind,
-        format,
-        content,
-    } = models::perform_task(task).await?;
-    let ended = Timestamp::now();
+    let mut retries = 0;
+    let mut tool_calls = 0;
+    let (mut authors, blocks) = loop {
+        // If streaming content, use a new channel for this attempt, forwarding the
+        // accumulated content so the receiver always has the full content of the
+        // current attempt (and not content from any previous attempts)
+        if let Some(content_sender) = content_sender.clone() {
+            let (sender, mut receiver) = mpsc::unbounded_channel::<String>();
+            task.content_sender = Some(sender);
+            tokio::spawn(async move {
+                let mut content = String::new();
+                while let Some(chunk) = receiver.recv().await {
+                    content.push_str(&chunk);
+                    if content_sender.send(content.clone()).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
 
-    let blocks = match kind {
-        ModelOutputKind::Text => {
-            // Decode the model output into blocks
-            let node = codecs::from_str(
-                &content,
-                Some(DecodeOptions {
-                    format: format
-                        .is_unknown()
-                        .then_some(Format::Markdown)
-                        .or(Some(format)),
-                    ..Default::default()
-                }),
-            )
-            .await?;
+        let ModelOutput {
+            authors,
+            kind,
+            format,
+            content,
+        } = models::perform_task(task.clone()).await?;
+
+        // If the model has requested execution of a code snippet then execute
+        // it in the forked kernels and send back the result
+        if let (Some(kernels), ModelOutputKind::Text) = (&kernels, &kind) {
+            if let Some(captures) = TOOL_CALL_REGEX.captures(&content) {
+                if tool_calls >= MAX_TOOL_CALLS {
+                    bail!("Model requested more than {MAX_TOOL_CALLS} code executions")
+                }
+                tool_calls += 1;
+
+                let language = captures[1].to_string();
+                let code = captures[2].to_string();
+                tracing::debug!("Model requested execution of {language} code snippet");
+
+                let result = match kernels.lock().await.execute(&code, Some(&language)).await {
+                    Ok((outputs, messages, ..)) => {
+                        let outputs = outputs
+                            .iter()
+                            .map(|output| serde_json::to_string(output).unwrap_or_default())
+                            .join("\n");
+                        let messages = messages
+                            .iter()
+                            .map(|message| message.formatted())
+                            .join("\n");
+                        [outputs, messages].join("\n").trim().to_string()
+                    }
+                    Err(error) => format!("Error while executing code: {error}"),
+                };
 
-            let Node::Article(Article { content, .. }) = node else {
-                bail!("Expected content to be decoded to an article")
-            };
+                task.messages
+                    .push(InstructionMessage::assistant(&content, None));
+                task.messages.push(InstructionMessage::user(result, None));
 
-            content
+                continue;
+            }
         }
-        ModelOutputKind::Url => {
-            let content_url = content;
-            let media_type = Some(format.media_type());
-
-            let node = if format.is_audio() {
-                Inline::AudioObject(AudioObject {
-                    content_url,
-                    media_type,
-                    ..Default::default()
-                })
-            } else if format.is_image() {
-                Inline::ImageObject(ImageObject {
-                    content_url,
-                    media_type,
-                    ..Default::default()
-                })
-            } else if format.is_video() {
-                Inline::VideoObject(VideoObject {
-                    content_url,
-                    media_type,
-                    ..Default::default()
-                })
-            } else {
-                Inline::Link(Link {
-                    target: content_url,
-                    ..Default::default()
-                })
-            };
 
-            vec![p([node])]
+        let blocks = match kind {
+            ModelOutputKind::Text => {
+                // Decode the model output into blocks
+                let node = codecs::from_str(
+                    &content,
+                    Some(DecodeOptions {
+                        format: format
+                            .is_unknown()
+                            .then_some(Format::Markdown)
+                            .or(Some(format)),
+                        ..Default::default()
+                    }),
+                )
+                .await?;
+
+                let Node::Article(Article { content, .. }) = node else {
+                    bail!("Expected content to be decoded to an article")
+                };
+
+                content
+            }
+            ModelOutputKind::Url => {
+                let content_url = content;
+                let media_type = Some(format.media_type());
+
+                let node = if format.is_audio() {
+                    Inline::AudioObject(AudioObject {
+                        content_url,
+                        media_type,
+                        ..Default::default()
+                    })
+                } else if format.is_image() {
+                    Inline::ImageObject(ImageObject {
+                        content_url,
+                        media_type,
+                        ..Default::default()
+                    })
+                } else if format.is_video() {
+                    Inline::VideoObject(VideoObject {
+                        content_url,
+                        media_type,
+                        ..Default::default()
+                    })
+                } else {
+                    Inline::Link(Link {
+                        target: content_url,
+                        ..Default::default()
+                    })
+                };
+
+                // URL outputs are not validated against expected node types
+                break (authors, vec![p([node])]);
+            }
+        };
+
+        // Check that the blocks have the node types expected by the prompt
+        if !expected_node_types.is_empty() && !dry_run {
+            if let Some(block) = blocks
+                .iter()
+                .find(|block| !expected_node_types.contains(&block.node_type().to_string()))
+            {
+                let message = format!(
+                    "The response contained a `{got}` node but only {expected} nodes are expected. Please respond with only {expected} nodes.",
+                    got = block.node_type(),
+                    expected = expected_node_types.iter().map(|name| ["`", name, "`"].concat()).join(", ")
+                );
+
+                if retries < MAX_RETRIES {
+                    retries += 1;
+                    tracing::debug!("Invalid model output, re-prompting: {message}");
+
+                    task.messages
+                        .push(InstructionMessage::assistant(to_markdown(&blocks), None));
+                    task.messages.push(InstructionMessage::user(message, None));
+
+                    continue;
+                }
+
+                bail!("Model output is still invalid after {MAX_RETRIES} retries: {message}")
+            }
         }
-    };
 
-    // TODO: check that blocks are the correct type
+        break (authors, blocks);
+    };
+    let ended = Timestamp::now();
 
     let mut suggestion = SuggestionBlock::new(blocks);
 
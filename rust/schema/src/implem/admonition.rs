@@ -82,12 +82,21 @@ impl MarkdownCodec for Admonition {
 
             context.push_str(":::\n\n");
         } else {
+            // Use upper case for the alert types recognized by GitHub, with
+            // `Danger` mapped to its closest equivalent `CAUTION` so that
+            // GitHub alerts round-trip, and lower case for the rest
+            let name = match &self.admonition_type {
+                AdmonitionType::Note => "NOTE".to_string(),
+                AdmonitionType::Tip => "TIP".to_string(),
+                AdmonitionType::Important => "IMPORTANT".to_string(),
+                AdmonitionType::Warning => "WARNING".to_string(),
+                AdmonitionType::Danger => "CAUTION".to_string(),
+                other => other.to_string().to_lowercase(),
+            };
+
             context
                 .push_str("> [!")
-                .push_prop_str(
-                    NodeProperty::AdmonitionType,
-                    &self.admonition_type.to_string().to_lowercase(),
-                )
+                .push_prop_str(NodeProperty::AdmonitionType, &name)
                 .push_str("]");
 
             if let Some(is_folded) = self.is_folded {
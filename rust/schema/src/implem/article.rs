@@ -65,6 +65,12 @@ impl MarkdownCodec for Article {
             title_string = Some(to_markdown(title))
         }
 
+        // If there is an abstract, represent it as Markdown
+        let mut abstract_string: Option<String> = None;
+        if let Some(abs) = &header.r#abstract {
+            abstract_string = Some(to_markdown(abs).trim_end().to_string())
+        }
+
         // Unwrap `AuthorRoles`. These can be added when the document is authored
         // in some tools but have too many/unnecessary details for a YAML header.
         // Also remove any un-named authors.
@@ -123,6 +129,11 @@ impl MarkdownCodec for Article {
                 yaml.insert(Value::from("title"), Value::from(title));
             }
 
+            // Set abstract string if any
+            if let Some(abs) = abstract_string {
+                yaml.insert(Value::from("abstract"), Value::from(abs));
+            }
+
             // Only add a YAML header if there are remaining keys
             if !yaml.is_empty() {
                 let yaml = serde_yaml::to_string(&yaml).unwrap_or_default();
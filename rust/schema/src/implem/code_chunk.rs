@@ -1,7 +1,7 @@
 use codec_info::{lost_exec_options, lost_options};
 use codec_markdown_trait::to_markdown;
 
-use crate::{prelude::*, CodeChunk, Duration, LabelType, Timestamp};
+use crate::{prelude::*, CodeChunk, Duration, ExecutionMode, LabelType, Timestamp};
 
 use super::utils::caption_to_dom;
 
@@ -284,6 +284,48 @@ impl MarkdownCodec for CodeChunk {
                     }
                 })
                 .push_str("```\n\n");
+        } else if matches!(context.format, Format::Rmd) {
+            context.push_str("```{").push_prop_str(
+                NodeProperty::ProgrammingLanguage,
+                self.programming_language.as_deref().unwrap_or("r"),
+            );
+
+            if !self.label_automatically.unwrap_or(true) {
+                if let Some(label) = &self.label {
+                    context
+                        .push_str(" ")
+                        .push_prop_str(NodeProperty::Label, label);
+                }
+            }
+
+            if matches!(self.is_invisible, Some(true)) {
+                context.push_str(", echo=FALSE");
+            }
+
+            if matches!(self.execution_mode, Some(ExecutionMode::Locked)) {
+                context.push_str(", eval=FALSE");
+            }
+
+            if let Some(caption) = &self.caption {
+                // Note: caption must be a single line
+                context
+                    .push_str(", fig.cap=\"")
+                    .push_prop_str(
+                        NodeProperty::Caption,
+                        &to_markdown(caption).replace('\n', " "),
+                    )
+                    .push_str("\"");
+            }
+
+            context
+                .push_str("}\n")
+                .push_prop_fn(NodeProperty::Code, |context| {
+                    self.code.to_markdown(context);
+                    if !self.code.ends_with('\n') {
+                        context.newline();
+                    }
+                })
+                .push_str("```\n\n");
         } else {
             let wrapped =
                 if self.label_type.is_some() || self.label.is_some() || self.caption.is_some() {
@@ -16,6 +16,9 @@ impl DomCodec for Datatable {
         context
             .enter_node(self.node_type(), self.node_id())
             .push_id(&self.id)
+            // Total number of rows so that clients can show pagination
+            // controls even when not all rows are encoded
+            .push_attr("rows", &self.rows().to_string())
             .enter_elem("table");
 
         // Create a <thead><tr> elem with a <th> row describing each column
@@ -45,30 +48,46 @@ impl DomCodec for Datatable {
             })
             .collect_vec();
 
-        // Create a <tbody> elem with a <td> for each value in each column
-        context.enter_elem("tbody");
-        for row in 0..self.rows().min(context.max_datatable_rows) {
-            context.enter_elem("tr");
-            for (column_index, column) in self.columns.iter().enumerate() {
-                context.enter_elem("td");
+        // Create a <tbody> elem for each page of rows with a <td> for each
+        // value in each column. Pages after the first are `hidden` so that,
+        // for large datatables, first paint is fast and the browser does not
+        // need to layout rows that the user may never scroll to.
+        let rows = self.rows().min(context.max_datatable_rows);
+        let page_rows = context.datatable_page_rows;
+        let mut row = 0;
+        while row < rows {
+            if row == 0 {
+                context.enter_elem("tbody");
+            } else {
+                context.enter_elem_attrs("tbody", [("hidden", "")]);
+            }
 
-                if let Some(Some(data_type)) = data_types.get(column_index) {
-                    context.push_attr("data-type", data_type);
-                }
+            let page_end = rows.min(row + page_rows);
+            while row < page_end {
+                context.enter_elem("tr");
+                for (column_index, column) in self.columns.iter().enumerate() {
+                    context.enter_elem("td");
+
+                    if let Some(Some(data_type)) = data_types.get(column_index) {
+                        context.push_attr("data-type", data_type);
+                    }
 
-                if let Some(value) = column.values.get(row) {
-                    let text = if let Primitive::String(value) = &value {
-                        value.clone()
-                    } else {
-                        serde_json::to_string(value).unwrap_or_default()
-                    };
-                    context.push_text(&text);
+                    if let Some(value) = column.values.get(row) {
+                        let text = if let Primitive::String(value) = &value {
+                            value.clone()
+                        } else {
+                            serde_json::to_string(value).unwrap_or_default()
+                        };
+                        context.push_text(&text);
+                    }
+                    context.exit_elem();
                 }
                 context.exit_elem();
+                row += 1;
             }
+
             context.exit_elem();
         }
-        context.exit_elem();
 
         context.exit_elem().exit_node();
     }
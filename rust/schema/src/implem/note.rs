@@ -1,21 +1,23 @@
-use codec_info::lost_options;
-
 use crate::{prelude::*, Note, NoteType};
 
 impl MarkdownCodec for Note {
     fn to_markdown(&self, context: &mut MarkdownEncodeContext) {
-        context.merge_losses(lost_options!(self, id));
-
         if self.note_type != NoteType::Footnote {
             context.add_loss("Note.noteType");
         }
 
-        let index = context.footnotes.len() + 1;
+        // Use the id of the note, if any, as the footnote identifier so that
+        // identifiers are stable across round-trips; otherwise use the
+        // one-based index of the footnote
+        let label = self
+            .id
+            .clone()
+            .unwrap_or_else(|| (context.footnotes.len() + 1).to_string());
 
         let mut footnote_context = MarkdownEncodeContext::default();
         footnote_context.enter_node(NodeType::Note, self.node_id());
         footnote_context.push_str("[^");
-        footnote_context.push_str(&index.to_string());
+        footnote_context.push_str(&label);
         footnote_context.push_str("]: ");
         footnote_context.push_line_prefix("    ");
         self.content.to_markdown(&mut footnote_context);
@@ -23,7 +25,7 @@ impl MarkdownCodec for Note {
         context.footnotes.push(footnote_context);
 
         context.push_str("[^");
-        context.push_str(&index.to_string());
+        context.push_str(&label);
         context.push_str("]");
     }
 }
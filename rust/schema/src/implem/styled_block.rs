@@ -62,13 +62,30 @@ impl MarkdownCodec for StyledBlock {
             ));
 
         if matches!(context.format, Format::Myst) {
+            // Styled blocks decoded from MyST layout directives (e.g. `grid`,
+            // `card`, `tab-set`, `glossary`, `margin`) have the directive name
+            // as the first word of the code; encode these back to the original
+            // directive so that they round-trip
+            let (first, rest) = match self.code.split_once(' ') {
+                Some((first, rest)) => (first, rest.trim()),
+                None => (self.code.trim(), ""),
+            };
+            let (name, args) = if matches!(
+                first,
+                "grid" | "card" | "tab-set" | "tab-item" | "glossary" | "margin"
+            ) {
+                (first, rest)
+            } else {
+                ("style", self.code.as_str())
+            };
+
             context.myst_directive(
                 ':',
-                "style",
+                name,
                 |context| {
-                    context
-                        .push_str(" ")
-                        .push_prop_str(NodeProperty::Code, &self.code);
+                    if !args.is_empty() {
+                        context.push_str(" ").push_prop_str(NodeProperty::Code, args);
+                    }
                 },
                 |_| {},
                 |context| {
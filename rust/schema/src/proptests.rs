@@ -6,6 +6,7 @@ pub use proptest::{
     collection::{size_range, vec},
     option,
     prelude::*,
+    strategy::BoxedStrategy,
 };
 
 use crate::*;
@@ -223,6 +224,56 @@ prop_compose! {
     }
 }
 
+/// Generate a vector of block content with an explicit maximum nesting depth
+///
+/// Unlike [`vec_blocks`], where the nesting of generated blocks is determined
+/// by the recursion of `Block::arbitrary`, this allows the depth of nesting to
+/// be controlled explicitly. At each level above zero, blocks that contain
+/// other blocks (currently quote blocks and sections) are generated with
+/// content from the level below.
+pub fn vec_blocks_depth(max_depth: usize, max_size: usize) -> BoxedStrategy<Vec<Block>> {
+    if max_depth == 0 {
+        return vec_blocks_non_recursive(max_size).boxed();
+    }
+
+    let inner = vec_blocks_depth(max_depth - 1, max_size);
+    vec(
+        prop_oneof![
+            CodeBlock::arbitrary().prop_map(Block::CodeBlock),
+            Heading::arbitrary().prop_map(Block::Heading),
+            MathBlock::arbitrary().prop_map(Block::MathBlock),
+            Paragraph::arbitrary().prop_map(Block::Paragraph),
+            inner
+                .clone()
+                .prop_map(|content| Block::QuoteBlock(QuoteBlock::new(content))),
+            inner.prop_map(|content| Block::Section(Section::new(content))),
+        ],
+        1..=max_size,
+    )
+    .boxed()
+}
+
+prop_compose! {
+    /// Generate an arbitrary article with bounded size and nesting depth
+    ///
+    /// Useful for fuzz-testing codecs, patching and other operations against
+    /// arbitrary documents rather than only hand-written fixtures.
+    pub fn article(max_depth: usize, max_size: usize)(
+        content in vec_blocks_depth(max_depth, max_size)
+    ) -> Article {
+        Article::new(content)
+    }
+}
+
+prop_compose! {
+    /// Generate an arbitrary document node with bounded size and nesting depth
+    pub fn node(max_depth: usize, max_size: usize)(
+        article in article(max_depth, max_size)
+    ) -> Node {
+        Node::Article(article)
+    }
+}
+
 prop_compose! {
     /// Generate a vector with an arbitrary heading and an arbitrary paragraph
     pub fn vec_heading_paragraph()(
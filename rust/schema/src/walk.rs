@@ -33,6 +33,56 @@ impl WalkControl {
     }
 }
 
+/// Check whether a node contains any of a set of node types
+///
+/// Walks over the node, short-circuiting as soon as one of the types is
+/// found. Intended as a cheap pre-check so that more expensive visitors can
+/// skip subtrees that cannot contain the nodes they are looking for (e.g.
+/// skipping the inlines of a section that contains no `Link` nodes).
+pub fn contains_type<T: WalkNode>(node: &T, node_types: &[NodeType]) -> bool {
+    let mut probe = TypeProbe {
+        node_types,
+        found: false,
+    };
+    probe.visit(node);
+    probe.found
+}
+
+/// A visitor that searches for any of a set of node types
+struct TypeProbe<'lt> {
+    node_types: &'lt [NodeType],
+    found: bool,
+}
+
+impl TypeProbe<'_> {
+    fn check(&mut self, node_type: NodeType) -> WalkControl {
+        if self.found {
+            return WalkControl::Break;
+        }
+
+        if self.node_types.contains(&node_type) {
+            self.found = true;
+            return WalkControl::Break;
+        }
+
+        WalkControl::Continue
+    }
+}
+
+impl Visitor for TypeProbe<'_> {
+    fn visit_node(&mut self, node: &Node) -> WalkControl {
+        self.check(node.node_type())
+    }
+
+    fn visit_block(&mut self, block: &Block) -> WalkControl {
+        self.check(block.node_type())
+    }
+
+    fn visit_inline(&mut self, inline: &Inline) -> WalkControl {
+        self.check(inline.node_type())
+    }
+}
+
 /// A node visitor
 ///
 /// The methods of this trait are called while walking over nodes in a node tree.